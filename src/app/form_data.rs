@@ -1,50 +1,276 @@
+use std::ops::Range;
+
+use crate::env_file::EnvFile;
+use crate::platform;
+use crate::templates::{ConfigTemplate, Credential};
+
+pub const DEFAULT_GENERATION_MODEL: &str = "gpt-4o-mini";
+pub const DEFAULT_HOST_PORT: &str = "3000";
+pub const DEFAULT_AI_SERVICE_PORT: &str = "5555";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FormSection {
+    Credentials,
+    Options,
+}
+
 #[derive(Debug, Clone)]
 pub struct FormData {
-    pub(crate) openai_api_key: String,
+    pub(crate) credentials: Vec<Credential>,
+    pub(crate) credential_values: Vec<String>,
+    pub(crate) placeholders: Vec<String>,
+    pub(crate) placeholder_values: Vec<String>,
     pub(crate) generation_model: String,
     pub(crate) host_port: String,
     pub(crate) ai_service_port: String,
+    pub(crate) port_overrides: Vec<(String, String)>,
+    pub(crate) platform: String,
     pub(crate) current_field: usize,
     pub(crate) editing: bool,
     pub(crate) error_message: String,
 }
 
 impl FormData {
-    pub fn new() -> Self {
+    pub fn new(template: &ConfigTemplate) -> Self {
+        let placeholders: Vec<String> = template
+            .placeholders()
+            .into_iter()
+            .filter(|placeholder| {
+                !template
+                    .credentials
+                    .iter()
+                    .any(|credential| credential.placeholder.as_ref() == Some(placeholder))
+            })
+            .collect();
+
         Self {
-            openai_api_key: String::new(),
-            generation_model: "gpt-4o-mini".to_string(),
-            host_port: "3000".to_string(),
-            ai_service_port: "5555".to_string(),
+            credentials: template.credentials.clone(),
+            credential_values: template
+                .credentials
+                .iter()
+                .map(|credential| credential.default.clone())
+                .collect(),
+            placeholder_values: vec![String::new(); placeholders.len()],
+            placeholders,
+            generation_model: template
+                .generation_model()
+                .unwrap_or_else(|| DEFAULT_GENERATION_MODEL.to_string()),
+            host_port: DEFAULT_HOST_PORT.to_string(),
+            ai_service_port: DEFAULT_AI_SERVICE_PORT.to_string(),
+            port_overrides: Vec::new(),
+            platform: platform::host_platform().to_string(),
             current_field: 0,
             editing: false,
             error_message: String::new(),
         }
     }
 
+    pub fn section_fields(&self, section: FormSection) -> Range<usize> {
+        let provider_count = self.credentials.len() + self.placeholders.len();
+        match section {
+            FormSection::Credentials => 0..provider_count,
+            FormSection::Options => provider_count..provider_count + 3,
+        }
+    }
+
+    pub fn set_credential(&mut self, env_var: &str, value: &str) -> bool {
+        match self
+            .credentials
+            .iter()
+            .position(|credential| credential.env_var == env_var)
+        {
+            Some(index) => {
+                self.credential_values[index] = value.to_string();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn load_env(&mut self, env: &EnvFile) {
+        for (credential, value) in self.credentials.iter().zip(&mut self.credential_values) {
+            if let Some(existing) = env.get(&credential.env_var)
+                && !existing.trim().is_empty()
+            {
+                *value = existing.to_string();
+            }
+        }
+
+        let options = [
+            ("GENERATION_MODEL", &mut self.generation_model),
+            ("HOST_PORT", &mut self.host_port),
+            ("ANALYTICS_AI_SERVICE_PORT", &mut self.ai_service_port),
+        ];
+        for (key, value) in options {
+            if let Some(existing) = env.get(key)
+                && !existing.trim().is_empty()
+            {
+                *value = existing.to_string();
+            }
+        }
+    }
+
+    pub fn set_port(&mut self, env_var: &str, port: u16) {
+        let value = port.to_string();
+        match env_var {
+            "HOST_PORT" => self.host_port = value,
+            "ANALYTICS_AI_SERVICE_PORT" | "AI_SERVICE_FORWARD_PORT" => self.ai_service_port = value,
+            _ => match self
+                .port_overrides
+                .iter_mut()
+                .find(|(existing, _)| existing == env_var)
+            {
+                Some((_, existing)) => *existing = value,
+                None => self.port_overrides.push((env_var.to_string(), value)),
+            },
+        }
+    }
+
+    pub fn set_placeholder(&mut self, name: &str, value: &str) -> bool {
+        match self
+            .placeholders
+            .iter()
+            .position(|placeholder| placeholder == name)
+        {
+            Some(index) => {
+                self.placeholder_values[index] = value.to_string();
+                true
+            }
+            None => match self
+                .credentials
+                .iter()
+                .find(|credential| credential.placeholder.as_deref() == Some(name))
+            {
+                Some(credential) => {
+                    let env_var = credential.env_var.clone();
+                    self.set_credential(&env_var, value)
+                }
+                None => false,
+            },
+        }
+    }
+
+    pub fn placeholder_pairs(&self) -> Vec<(String, String)> {
+        let mut pairs: Vec<(String, String)> = self
+            .placeholders
+            .iter()
+            .zip(&self.placeholder_values)
+            .map(|(name, value)| (name.clone(), value.trim().to_string()))
+            .collect();
+
+        for (credential, value) in self.credentials.iter().zip(&self.credential_values) {
+            if let Some(placeholder) = &credential.placeholder {
+                pairs.push((placeholder.clone(), value.trim().to_string()));
+            }
+        }
+
+        pairs.retain(|(_, value)| !value.is_empty());
+        pairs
+    }
+
+    pub fn resolved_generation_model(&self) -> String {
+        self.placeholder_pairs().iter().fold(
+            self.generation_model.trim().to_string(),
+            |model, (name, value)| model.replace(&format!("<{}>", name), value),
+        )
+    }
+
+    pub fn credential_pairs(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.credentials
+            .iter()
+            .zip(&self.credential_values)
+            .map(|(credential, value)| (credential.env_var.as_str(), value.trim()))
+    }
+
     pub fn validate(&mut self) -> bool {
-        if self.openai_api_key.trim().is_empty() {
-            self.error_message = "OpenAI API Key is required!".to_string();
-            return false;
+        let result = self.check_credentials().and_then(|_| self.check_options());
+        self.apply_result(result)
+    }
+
+    pub fn validate_section(&mut self, section: FormSection) -> bool {
+        let result = match section {
+            FormSection::Credentials => self
+                .check_credentials()
+                .and_then(|_| self.check_placeholders()),
+            FormSection::Options => self.check_options(),
+        };
+        self.apply_result(result)
+    }
+
+    fn apply_result(&mut self, result: Result<(), String>) -> bool {
+        match result {
+            Ok(()) => {
+                self.error_message.clear();
+                true
+            }
+            Err(message) => {
+                self.error_message = message;
+                false
+            }
         }
+    }
 
-        if !self.openai_api_key.starts_with("sk-") {
-            self.error_message =
-                "Invalid OpenAI API Key format (should start with 'sk-')".to_string();
-            return false;
+    fn check_credentials(&self) -> Result<(), String> {
+        for (credential, value) in self.credentials.iter().zip(&self.credential_values) {
+            credential.validate(value)?;
         }
+        Ok(())
+    }
 
-        self.error_message.clear();
-        true
+    fn check_placeholders(&self) -> Result<(), String> {
+        for (placeholder, value) in self.placeholders.iter().zip(&self.placeholder_values) {
+            let value = value.trim();
+            if value.is_empty() {
+                return Err(format!("<{}> is required!", placeholder));
+            }
+            if value.contains(['<', '>', '\n']) {
+                return Err(format!(
+                    "<{}> must not contain '<', '>' or newlines",
+                    placeholder
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_options(&self) -> Result<(), String> {
+        if self.generation_model.trim().is_empty() {
+            return Err("Generation Model is required!".to_string());
+        }
+
+        let host_port = parse_port("UI Port", &self.host_port)?;
+        let ai_service_port = parse_port("AI Service Port", &self.ai_service_port)?;
+        if host_port == ai_service_port {
+            return Err("UI Port and AI Service Port must be different".to_string());
+        }
+
+        Ok(())
     }
 
     pub fn get_current_value_mut(&mut self) -> &mut String {
-        match self.current_field {
-            0 => &mut self.openai_api_key,
-            1 => &mut self.generation_model,
-            2 => &mut self.host_port,
-            3 => &mut self.ai_service_port,
-            _ => &mut self.openai_api_key,
+        let credential_count = self.credentials.len();
+        let provider_count = credential_count + self.placeholders.len();
+
+        if self.current_field < credential_count {
+            return &mut self.credential_values[self.current_field];
         }
+
+        if self.current_field < provider_count {
+            return &mut self.placeholder_values[self.current_field - credential_count];
+        }
+
+        match self.current_field - provider_count {
+            0 => &mut self.generation_model,
+            1 => &mut self.host_port,
+            _ => &mut self.ai_service_port,
+        }
+    }
+}
+
+fn parse_port(label: &str, value: &str) -> Result<u16, String> {
+    match value.trim().parse::<u16>() {
+        Ok(port) if port > 0 => Ok(port),
+        _ => Err(format!("{} must be a number between 1 and 65535", label)),
     }
 }
@@ -1,69 +1,143 @@
-use color_eyre::Result;
+use color_eyre::{Result, eyre::eyre};
 use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
 use ratatui::{DefaultTerminal, Frame};
-use std::fs;
-use std::process::Stdio;
-use tokio::io::{AsyncBufReadExt, BufReader};
-use tokio::process::Command;
+use tokio::sync::mpsc::error::TryRecvError;
+use tokio::sync::mpsc::{self, UnboundedReceiver};
+use tokio::task::JoinHandle;
 
-use crate::templates::{self, ConfigTemplate};
+use crate::compose::{self, ComposeService};
+use crate::doctor::{self, DoctorCheck};
+use crate::drift::{self, EnvDrift};
+use crate::env_file::EnvFile;
+use crate::existing::{self, ConfigOrigin, ExistingConfig};
+use crate::generate::{self, PendingFile};
+use crate::installer::{self, Backend, InstallEvent, InstallOptions, InstallProgress};
+use crate::platform::{self, PlatformChoice};
+use crate::ports::{self, PortConflict};
+use crate::readiness::{self, Readiness};
+use crate::runtime;
+use crate::templates::{ConfigTemplate, ModelSummary, TemplateCatalog};
 use crate::ui::{
-    self, ConfigSelectionView, ConfirmationView, EnvSetupView, ErrorView, InstallingView,
-    SuccessView,
+    self, ConfigSelectionView, ConfirmationView, DiffView, DoctorView, EnvSetupView, ErrorView,
+    InstallingView, PipesView, PortsView, ReviewView, SettingsView, SuccessView,
 };
 use crate::utils;
+use crate::validate;
 
 pub mod form_data;
+pub mod pipe_editor;
+pub mod settings_editor;
 pub mod state;
 
-pub use form_data::FormData;
+pub use form_data::{FormData, FormSection};
+pub use pipe_editor::{PipeEditor, PipeField};
+pub use settings_editor::SettingsEditor;
 pub use state::{AppState, MenuSelection};
 
+#[derive(Debug)]
+enum CheckEvent {
+    Platform(PlatformChoice),
+    Doctor(Vec<DoctorCheck>),
+}
+
 #[derive(Debug)]
 pub struct App {
     running: bool,
     pub(crate) state: AppState,
-    logs: Vec<String>,
-    progress: f64,
-    current_service: String,
-    total_services: usize,
-    completed_services: usize,
+    install_progress: InstallProgress,
+    install_task: Option<JoinHandle<()>>,
+    install_events: Option<UnboundedReceiver<InstallEvent>>,
+    check_events: Option<UnboundedReceiver<CheckEvent>>,
     pub(crate) env_exists: bool,
     pub(crate) config_exists: bool,
+    stack_services: Vec<String>,
+    env_drift: Option<EnvDrift>,
+    readiness: Readiness,
+    existing_config: Option<std::result::Result<ExistingConfig, String>>,
+    keep_config: bool,
+    load_existing: bool,
     pub(crate) form_data: FormData,
+    pipe_editor: PipeEditor,
+    settings_editor: SettingsEditor,
     pub(crate) menu_selection: MenuSelection,
+    templates: TemplateCatalog,
     config_selection_index: usize,
+    selected_template_index: usize,
+    install_template_index: Option<usize>,
+    review_models: Vec<ModelSummary>,
+    review_services: Vec<ComposeService>,
+    review_warnings: Vec<String>,
+    port_conflicts: Vec<PortConflict>,
+    platform_choice: Option<PlatformChoice>,
+    pending_files: Vec<PendingFile>,
+    doctor_checks: Vec<DoctorCheck>,
+    doctor_notes: Vec<String>,
+    confirm_failed_doctor: bool,
+    ui_port: String,
+    options: InstallOptions,
+    diff_scroll: usize,
 }
 
 impl App {
-    pub fn new() -> Self {
+    pub fn new(options: InstallOptions) -> Result<Self> {
         let env_exists = utils::find_file(".env");
         let config_exists = utils::find_file("config.yaml");
+        let templates = TemplateCatalog::load();
+        let Some(first_template) = templates.templates.first() else {
+            return Err(eyre!(
+                "no configuration templates available{}",
+                templates
+                    .warnings
+                    .iter()
+                    .map(|warning| format!("\n  {}", warning))
+                    .collect::<String>()
+            ));
+        };
 
         let initial_state = AppState::Confirmation;
 
-        let initial_menu = if !env_exists {
-            MenuSelection::GenerateEnv
-        } else if !config_exists {
-            MenuSelection::GenerateConfig
-        } else {
-            MenuSelection::Proceed
-        };
+        let env_drift = env_exists.then(|| drift::load(&templates)).flatten();
+        let existing_config =
+            config_exists.then(|| existing::load(&templates).map_err(|e| e.to_string()));
 
-        Self {
+        let mut app = Self {
             running: true,
             state: initial_state,
-            logs: Vec::new(),
-            progress: 0.0,
-            current_service: String::new(),
-            total_services: 4,
-            completed_services: 0,
+            install_progress: InstallProgress::new(),
+            install_task: None,
+            install_events: None,
+            check_events: None,
             env_exists,
             config_exists,
-            form_data: FormData::new(),
-            menu_selection: initial_menu,
+            stack_services: compose::service_names(),
+            env_drift,
+            readiness: Readiness::default(),
+            existing_config,
+            keep_config: false,
+            load_existing: false,
+            form_data: form_data_for(first_template),
+            pipe_editor: PipeEditor::new(first_template),
+            settings_editor: SettingsEditor::new(first_template),
+            menu_selection: MenuSelection::Setup,
+            templates,
             config_selection_index: 0,
-        }
+            selected_template_index: 0,
+            install_template_index: None,
+            review_models: Vec::new(),
+            review_services: Vec::new(),
+            review_warnings: Vec::new(),
+            port_conflicts: Vec::new(),
+            platform_choice: None,
+            pending_files: Vec::new(),
+            doctor_checks: Vec::new(),
+            doctor_notes: Vec::new(),
+            confirm_failed_doctor: false,
+            ui_port: form_data::DEFAULT_HOST_PORT.to_string(),
+            options,
+            diff_scroll: 0,
+        };
+        app.refresh_readiness();
+        Ok(app)
     }
 
     pub async fn run(mut self, mut terminal: DefaultTerminal) -> Result<()> {
@@ -75,39 +149,21 @@ impl App {
                     if let Some(action) = self.handle_confirmation_events()? {
                         match action {
                             MenuSelection::Proceed => {
-                                if self.env_exists && self.config_exists {
-                                    self.state = AppState::Installing;
-                                    self.logs
-                                        .push("🚀 Starting Analytics installation...".to_string());
-
-                                    let result = self.run_docker_compose().await;
-
-                                    match result {
-                                        Ok(_) => {
-                                            self.state = AppState::Success;
-                                            self.progress = 100.0;
-                                        }
-                                        Err(e) => {
-                                            self.state = AppState::Error(format!(
-                                                "Installation failed: {}",
-                                                e
-                                            ));
-                                        }
-                                    }
+                                if self.env_exists
+                                    && self.config_exists
+                                    && self.readiness.is_ready()
+                                {
+                                    self.enter_doctor(Vec::new(), self.existing_template_index());
                                 }
                             }
-                            MenuSelection::GenerateEnv => {
-                                self.state = AppState::EnvSetup;
+                            MenuSelection::AddMissingKeys => {
+                                self.add_missing_env_keys();
                             }
-                            MenuSelection::GenerateConfig => {
-                                if templates::CONFIG_TEMPLATES.is_empty() {
-                                    self.state = AppState::Error(
-                                        "No configuration templates available".to_string(),
-                                    );
-                                } else {
-                                    self.config_selection_index = 0;
-                                    self.state = AppState::ConfigSelection;
-                                }
+                            MenuSelection::Keep
+                            | MenuSelection::Edit
+                            | MenuSelection::Regenerate
+                            | MenuSelection::Setup => {
+                                self.start_wizard(&action);
                             }
                             MenuSelection::Cancel => {
                                 self.running = false;
@@ -115,53 +171,81 @@ impl App {
                         }
                     }
                 }
+                AppState::ConfigSelection => {
+                    self.handle_config_selection_events()?;
+                }
                 AppState::EnvSetup => {
-                    if let Some(proceed) = self.handle_form_events()? {
+                    if let Some(proceed) = self.handle_form_events(FormSection::Credentials)? {
                         if proceed {
-                            if let Err(e) = self.generate_env_file() {
-                                self.state =
-                                    AppState::Error(format!("Failed to generate .env: {}", e));
-                            } else {
-                                self.env_exists = true;
-                                self.state = AppState::Confirmation;
-                                if !self.config_exists {
-                                    self.menu_selection = MenuSelection::GenerateConfig;
-                                } else {
-                                    self.menu_selection = MenuSelection::Proceed;
-                                }
-                            }
+                            self.enter_form_section(FormSection::Options);
+                            self.state = AppState::Options;
                         } else {
-                            self.state = AppState::Confirmation;
+                            self.config_selection_index = self.selected_template_index;
+                            self.state = AppState::ConfigSelection;
                         }
                     }
                 }
-                AppState::ConfigSelection => {
-                    self.handle_config_selection_events()?;
+                AppState::Options => {
+                    if let Some(proceed) = self.handle_form_events(FormSection::Options)? {
+                        if proceed {
+                            self.check_ports();
+                        } else {
+                            self.enter_form_section(FormSection::Credentials);
+                            self.state = AppState::EnvSetup;
+                        }
+                    }
+                }
+                AppState::Ports => {
+                    self.handle_ports_events()?;
+                }
+                AppState::Review => {
+                    self.handle_review_events()?;
+                }
+                AppState::Pipes => {
+                    self.handle_pipes_events()?;
+                }
+                AppState::Settings => {
+                    self.handle_settings_events()?;
+                }
+                AppState::Diff => {
+                    self.handle_diff_events()?;
+                }
+                AppState::Doctor => {
+                    self.handle_doctor_events()?;
+                }
+                AppState::Checking(_) => {
+                    self.drain_check_events();
+
+                    if event::poll(std::time::Duration::from_millis(50))?
+                        && let Event::Key(key) = event::read()?
+                        && key.kind == KeyEventKind::Press
+                        && let KeyCode::Char('c') = key.code
+                        && key.modifiers.contains(KeyModifiers::CONTROL)
+                    {
+                        self.running = false;
+                    }
                 }
                 AppState::Installing => {
-                    if event::poll(std::time::Duration::from_millis(100))? {
-                        if let Event::Key(key) = event::read()? {
-                            if key.kind == KeyEventKind::Press {
-                                if let KeyCode::Char('c') = key.code {
-                                    if key.modifiers.contains(KeyModifiers::CONTROL) {
-                                        self.running = false;
-                                    }
-                                }
-                            }
-                        }
+                    self.drain_install_events();
+
+                    if event::poll(std::time::Duration::from_millis(50))?
+                        && let Event::Key(key) = event::read()?
+                        && key.kind == KeyEventKind::Press
+                        && let KeyCode::Char('c') = key.code
+                        && key.modifiers.contains(KeyModifiers::CONTROL)
+                    {
+                        self.cancel_installation();
+                        self.running = false;
                     }
                 }
                 AppState::Success | AppState::Error(_) => {
-                    if event::poll(std::time::Duration::from_millis(100))? {
-                        if let Event::Key(key) = event::read()? {
-                            if key.kind == KeyEventKind::Press {
-                                if let KeyCode::Char('c') = key.code {
-                                    if key.modifiers.contains(KeyModifiers::CONTROL) {
-                                        self.running = false;
-                                    }
-                                }
-                            }
-                        }
+                    if event::poll(std::time::Duration::from_millis(100))?
+                        && let Event::Key(key) = event::read()?
+                        && key.kind == KeyEventKind::Press
+                        && let KeyCode::Char('c') = key.code
+                        && key.modifiers.contains(KeyModifiers::CONTROL)
+                    {
+                        self.running = false;
                     }
                 }
             }
@@ -169,128 +253,369 @@ impl App {
         Ok(())
     }
 
+    fn start_installation(&mut self, notes: &[String]) {
+        self.install_progress = InstallProgress::new();
+        for note in notes {
+            self.install_progress.add_log(note);
+        }
+
+        let mut issues = match validate::validate_file(&validate::config_path()) {
+            Ok(issues) => issues,
+            Err(e) => vec![format!("Failed to read config.yaml: {}", e)],
+        };
+        let template = self
+            .install_template_index
+            .map(|index| &self.templates.templates[index]);
+        issues.extend(readiness::load(template).problems().map(str::to_string));
+        if !issues.is_empty() {
+            for issue in &issues {
+                self.install_progress.add_log(&format!("✗ {}", issue));
+            }
+            self.state = AppState::Error(format!(
+                ".env or config.yaml has {} problem(s), fix them and run the installer again",
+                issues.len()
+            ));
+            return;
+        }
+
+        if let Some(port) = EnvFile::load(&generate::env_path())
+            .and_then(|env| env.get("HOST_PORT").map(|port| port.trim().to_string()))
+            .filter(|port| !port.is_empty())
+        {
+            self.ui_port = port;
+        }
+
+        let (runtime, version) = match runtime::detect(self.options.runtime) {
+            Ok(detected) => detected,
+            Err(e) => {
+                self.state = AppState::Error(e.to_string());
+                return;
+            }
+        };
+        let backend = match Backend::new(self.options) {
+            Ok(backend) => backend,
+            Err(e) => {
+                self.state = AppState::Error(e.to_string());
+                return;
+            }
+        };
+
+        self.state = AppState::Installing;
+        self.install_progress.add_log(&format!(
+            "🚀 Starting Analytics installation with {} {}...",
+            runtime.command_line(&[], &[]),
+            version
+        ));
+
+        let (task, events) = installer::spawn(runtime, backend);
+        self.install_task = Some(task);
+        self.install_events = Some(events);
+    }
+
+    fn drain_install_events(&mut self) {
+        let Some(events) = self.install_events.as_mut() else {
+            return;
+        };
+
+        loop {
+            let result = match events.try_recv() {
+                Ok(InstallEvent::Finished(result)) => result,
+                Ok(event) => {
+                    self.install_progress.apply(&event);
+                    continue;
+                }
+                Err(TryRecvError::Empty) => return,
+                Err(TryRecvError::Disconnected) => {
+                    Err("installer task stopped unexpectedly".to_string())
+                }
+            };
+
+            self.install_task = None;
+            self.install_events = None;
+            match result {
+                Ok(()) => {
+                    self.install_progress.progress = 100.0;
+                    self.state = AppState::Success;
+                }
+                Err(e) => {
+                    self.state = AppState::Error(format!("Installation failed: {}", e));
+                }
+            }
+            return;
+        }
+    }
+
+    fn spawn_check<F>(&mut self, message: &str, check: F)
+    where
+        F: FnOnce() -> CheckEvent + Send + 'static,
+    {
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::task::spawn_blocking(move || {
+            let _ = tx.send(check());
+        });
+        self.check_events = Some(rx);
+        self.state = AppState::Checking(message.to_string());
+    }
+
+    fn drain_check_events(&mut self) {
+        let Some(events) = self.check_events.as_mut() else {
+            return;
+        };
+
+        let event = match events.try_recv() {
+            Ok(event) => event,
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => {
+                self.check_events = None;
+                self.state = AppState::Error("the check stopped unexpectedly".to_string());
+                return;
+            }
+        };
+        self.check_events = None;
+        match event {
+            CheckEvent::Platform(choice) => {
+                self.platform_choice = Some(choice);
+                self.check_ports();
+            }
+            CheckEvent::Doctor(checks) => {
+                self.doctor_checks = checks;
+                self.state = AppState::Doctor;
+            }
+        }
+    }
+
+    fn cancel_installation(&mut self) {
+        if let Some(task) = self.install_task.take() {
+            task.abort();
+        }
+        self.install_events = None;
+    }
+
+    fn menu_items(&self) -> Vec<MenuSelection> {
+        let mut items = Vec::new();
+        if self.config_exists {
+            if self.env_exists && self.readiness.is_ready() {
+                items.push(MenuSelection::Proceed);
+            } else {
+                items.push(MenuSelection::Keep);
+            }
+            if self
+                .env_drift
+                .as_ref()
+                .is_some_and(|drift| !drift.missing.is_empty())
+            {
+                items.push(MenuSelection::AddMissingKeys);
+            }
+            if matches!(self.existing_config, Some(Ok(_))) {
+                items.push(MenuSelection::Edit);
+            }
+            items.push(MenuSelection::Regenerate);
+        } else {
+            items.push(MenuSelection::Setup);
+        }
+        items.push(MenuSelection::Cancel);
+        items
+    }
+
+    fn add_missing_env_keys(&mut self) {
+        let Some(env_drift) = &self.env_drift else {
+            return;
+        };
+        match drift::add_missing(env_drift) {
+            Ok(_) => {
+                self.env_drift = drift::load(&self.templates);
+                self.refresh_readiness();
+            }
+            Err(e) => {
+                self.state = AppState::Error(format!("Failed to update .env: {}", e));
+            }
+        }
+    }
+
+    fn refresh_files(&mut self) {
+        self.env_exists = utils::find_file(".env");
+        self.config_exists = utils::find_file("config.yaml");
+        self.stack_services = compose::service_names();
+        self.env_drift = self
+            .env_exists
+            .then(|| drift::load(&self.templates))
+            .flatten();
+        self.existing_config = self
+            .config_exists
+            .then(|| existing::load(&self.templates).map_err(|e| e.to_string()));
+        self.refresh_readiness();
+    }
+
+    fn refresh_readiness(&mut self) {
+        let template = self
+            .existing_template_index()
+            .map(|index| &self.templates.templates[index]);
+        self.readiness = readiness::load(template);
+
+        let items = self.menu_items();
+        if !items.contains(&self.menu_selection) {
+            self.menu_selection = items[0].clone();
+        }
+    }
+
+    fn existing_template_index(&self) -> Option<usize> {
+        let Some(Ok(ExistingConfig {
+            origin: ConfigOrigin::Template(key),
+            ..
+        })) = &self.existing_config
+        else {
+            return None;
+        };
+        self.templates
+            .templates
+            .iter()
+            .position(|template| template.key == *key)
+    }
+
+    fn existing_config_label(&self) -> Option<String> {
+        match self.existing_config.as_ref()? {
+            Ok(config) => Some(match &config.origin {
+                ConfigOrigin::Template(key) => match self.templates.find(key) {
+                    Some(template) => format!("from the {} template", template.name),
+                    None => format!("from the {} template", key),
+                },
+                ConfigOrigin::Custom => {
+                    "custom, not generated from a known template; Edit rebuilds it from a template \
+                     you pick and keeps its pipes and settings"
+                        .to_string()
+                }
+            }),
+            Err(e) => Some(format!("unreadable: {}", e)),
+        }
+    }
+
+    fn start_wizard(&mut self, action: &MenuSelection) {
+        self.keep_config = *action == MenuSelection::Keep;
+        self.load_existing = matches!(action, MenuSelection::Keep | MenuSelection::Edit);
+
+        let existing_index = self.existing_template_index();
+        let index = match action {
+            MenuSelection::Keep | MenuSelection::Edit => {
+                existing_index.unwrap_or(self.selected_template_index)
+            }
+            _ => self.selected_template_index,
+        };
+        let template = &self.templates.templates[index];
+        self.selected_template_index = index;
+        self.form_data = form_data_for(template);
+        self.pipe_editor = PipeEditor::new(template);
+        self.settings_editor = SettingsEditor::new(template);
+        self.load_existing_config();
+
+        self.config_selection_index = self.selected_template_index;
+        self.state = AppState::ConfigSelection;
+    }
+
+    fn load_existing_config(&mut self) {
+        if !self.load_existing {
+            return;
+        }
+        let same_template = self.existing_template_index() == Some(self.selected_template_index);
+        let Some(Ok(config)) = &self.existing_config else {
+            return;
+        };
+        if same_template {
+            for (name, value) in &config.placeholders {
+                self.form_data.set_placeholder(name, value);
+            }
+        }
+        self.pipe_editor.load(&config.pipes);
+        if let Some(settings) = &config.settings {
+            self.settings_editor
+                .load(settings, &config.settings_warnings);
+        }
+    }
+
     fn handle_confirmation_events(&mut self) -> Result<Option<MenuSelection>> {
-        if event::poll(std::time::Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
-                if key.kind == KeyEventKind::Press {
-                    match key.code {
-                        KeyCode::Up => {
-                            self.menu_selection = match self.menu_selection {
-                                MenuSelection::Proceed => {
-                                    if !self.config_exists {
-                                        MenuSelection::GenerateConfig
-                                    } else if !self.env_exists {
-                                        MenuSelection::GenerateEnv
-                                    } else {
-                                        MenuSelection::Cancel
-                                    }
-                                }
-                                MenuSelection::GenerateEnv => MenuSelection::Cancel,
-                                MenuSelection::GenerateConfig => {
-                                    if !self.env_exists {
-                                        MenuSelection::GenerateEnv
-                                    } else {
-                                        MenuSelection::Cancel
-                                    }
-                                }
-                                MenuSelection::Cancel => {
-                                    if self.env_exists && self.config_exists {
-                                        MenuSelection::Proceed
-                                    } else if !self.config_exists {
-                                        MenuSelection::GenerateConfig
-                                    } else {
-                                        MenuSelection::GenerateEnv
-                                    }
-                                }
-                            };
-                        }
-                        KeyCode::Down | KeyCode::Tab => {
-                            self.menu_selection = match self.menu_selection {
-                                MenuSelection::Proceed => MenuSelection::Cancel,
-                                MenuSelection::GenerateEnv => {
-                                    if !self.config_exists {
-                                        MenuSelection::GenerateConfig
-                                    } else {
-                                        MenuSelection::Cancel
-                                    }
-                                }
-                                MenuSelection::GenerateConfig => MenuSelection::Cancel,
-                                MenuSelection::Cancel => {
-                                    if !self.env_exists {
-                                        MenuSelection::GenerateEnv
-                                    } else if !self.config_exists {
-                                        MenuSelection::GenerateConfig
-                                    } else {
-                                        MenuSelection::Proceed
-                                    }
-                                }
-                            };
-                        }
-                        KeyCode::Enter => {
-                            return Ok(Some(self.menu_selection.clone()));
-                        }
-                        KeyCode::Esc | KeyCode::Char('q') => {
-                            return Ok(Some(MenuSelection::Cancel));
-                        }
-                        KeyCode::Char('c') => {
-                            if key.modifiers.contains(KeyModifiers::CONTROL) {
-                                return Ok(Some(MenuSelection::Cancel));
-                            }
-                        }
-                        _ => {}
-                    }
+        if event::poll(std::time::Duration::from_millis(100))?
+            && let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+        {
+            let items = self.menu_items();
+            let index = items
+                .iter()
+                .position(|item| *item == self.menu_selection)
+                .unwrap_or(0);
+
+            match key.code {
+                KeyCode::Up => {
+                    self.menu_selection = items[(index + items.len() - 1) % items.len()].clone();
+                }
+                KeyCode::Down | KeyCode::Tab => {
+                    self.menu_selection = items[(index + 1) % items.len()].clone();
                 }
+                KeyCode::Enter => {
+                    return Ok(Some(items[index].clone()));
+                }
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    return Ok(Some(MenuSelection::Cancel));
+                }
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    return Ok(Some(MenuSelection::Cancel));
+                }
+                _ => {}
             }
         }
         Ok(None)
     }
 
-    fn handle_form_events(&mut self) -> Result<Option<bool>> {
-        if event::poll(std::time::Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
-                if key.kind == KeyEventKind::Press {
-                    if self.form_data.editing {
-                        match key.code {
-                            KeyCode::Enter | KeyCode::Esc => {
-                                self.form_data.editing = false;
-                            }
-                            KeyCode::Char(c) => {
-                                self.form_data.get_current_value_mut().push(c);
-                            }
-                            KeyCode::Backspace => {
-                                self.form_data.get_current_value_mut().pop();
-                            }
-                            _ => {}
-                        }
-                    } else {
-                        match key.code {
-                            KeyCode::Up => {
-                                if self.form_data.current_field > 0 {
-                                    self.form_data.current_field -= 1;
-                                }
-                            }
-                            KeyCode::Down | KeyCode::Tab => {
-                                if self.form_data.current_field < 3 {
-                                    self.form_data.current_field += 1;
-                                }
-                            }
-                            KeyCode::Enter => {
-                                self.form_data.editing = true;
-                            }
-                            KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                                if self.form_data.validate() {
-                                    return Ok(Some(true));
-                                }
-                            }
-                            KeyCode::Esc | KeyCode::Char('q') => {
-                                return Ok(Some(false));
-                            }
-                            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                                return Ok(Some(false));
-                            }
-                            _ => {}
-                        }
+    fn enter_form_section(&mut self, section: FormSection) {
+        self.form_data.current_field = self.form_data.section_fields(section).start;
+        self.form_data.editing = false;
+        self.form_data.error_message.clear();
+    }
+
+    fn handle_form_events(&mut self, section: FormSection) -> Result<Option<bool>> {
+        let fields = self.form_data.section_fields(section);
+
+        if event::poll(std::time::Duration::from_millis(100))?
+            && let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+        {
+            if self.form_data.editing {
+                match key.code {
+                    KeyCode::Enter | KeyCode::Esc => {
+                        self.form_data.editing = false;
+                    }
+                    KeyCode::Char(c) => {
+                        self.form_data.get_current_value_mut().push(c);
                     }
+                    KeyCode::Backspace => {
+                        self.form_data.get_current_value_mut().pop();
+                    }
+                    _ => {}
+                }
+            } else {
+                match key.code {
+                    KeyCode::Up if self.form_data.current_field > fields.start => {
+                        self.form_data.current_field -= 1;
+                    }
+                    KeyCode::Down | KeyCode::Tab
+                        if self.form_data.current_field + 1 < fields.end =>
+                    {
+                        self.form_data.current_field += 1;
+                    }
+                    KeyCode::Enter if !fields.is_empty() => {
+                        self.form_data.editing = true;
+                    }
+                    KeyCode::Char('s')
+                        if key.modifiers.contains(KeyModifiers::CONTROL)
+                            && self.form_data.validate_section(section) =>
+                    {
+                        return Ok(Some(true));
+                    }
+                    KeyCode::Esc | KeyCode::Char('q') => {
+                        return Ok(Some(false));
+                    }
+                    KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        return Ok(Some(false));
+                    }
+                    _ => {}
                 }
             }
         }
@@ -298,7 +623,7 @@ impl App {
     }
 
     fn handle_config_selection_events(&mut self) -> Result<()> {
-        let total = templates::CONFIG_TEMPLATES.len();
+        let total = self.templates.templates.len();
 
         if total == 0 {
             self.state = AppState::Error("No configuration templates available".to_string());
@@ -309,261 +634,360 @@ impl App {
             self.config_selection_index = total - 1;
         }
 
-        if event::poll(std::time::Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
-                if key.kind == KeyEventKind::Press {
-                    match key.code {
-                        KeyCode::Up => {
-                            if self.config_selection_index == 0 {
-                                self.config_selection_index = total - 1;
-                            } else {
-                                self.config_selection_index -= 1;
-                            }
-                        }
-                        KeyCode::Down | KeyCode::Tab => {
-                            self.config_selection_index = (self.config_selection_index + 1) % total;
-                        }
-                        KeyCode::Enter => {
-                            if let Some(template) =
-                                templates::CONFIG_TEMPLATES.get(self.config_selection_index)
-                            {
-                                match self.write_config_yaml(template) {
-                                    Ok(_) => {
-                                        self.config_exists = true;
-                                        self.state = AppState::Confirmation;
-                                        if !self.env_exists {
-                                            self.menu_selection = MenuSelection::GenerateEnv;
-                                        } else {
-                                            self.menu_selection = MenuSelection::Proceed;
-                                        }
-                                    }
-                                    Err(e) => {
-                                        self.state = AppState::Error(format!(
-                                            "Failed to generate config.yaml: {}",
-                                            e
-                                        ));
-                                    }
-                                }
-                            }
-                        }
-                        KeyCode::Esc | KeyCode::Char('q') => {
-                            self.state = AppState::Confirmation;
-                        }
-                        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                            self.running = false;
+        if event::poll(std::time::Duration::from_millis(100))?
+            && let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+        {
+            match key.code {
+                KeyCode::Up => {
+                    if self.config_selection_index == 0 {
+                        self.config_selection_index = total - 1;
+                    } else {
+                        self.config_selection_index -= 1;
+                    }
+                }
+                KeyCode::Down | KeyCode::Tab => {
+                    self.config_selection_index = (self.config_selection_index + 1) % total;
+                }
+                KeyCode::Enter => {
+                    if let Some(template) =
+                        self.templates.templates.get(self.config_selection_index)
+                    {
+                        if self.selected_template_index != self.config_selection_index {
+                            self.selected_template_index = self.config_selection_index;
+                            self.form_data = form_data_for(template);
+                            self.pipe_editor = PipeEditor::new(template);
+                            self.settings_editor = SettingsEditor::new(template);
+                            self.load_existing_config();
                         }
-                        _ => {}
+                        self.enter_form_section(FormSection::Credentials);
+                        self.state = AppState::EnvSetup;
                     }
                 }
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    self.state = AppState::Confirmation;
+                }
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    self.running = false;
+                }
+                _ => {}
             }
         }
 
         Ok(())
     }
 
-    fn generate_env_file(&self) -> Result<()> {
-        let project_root = utils::project_root();
-        let env_path = project_root.join(".env");
-
-        let uuid_fragment = uuid::Uuid::new_v4()
-            .to_string()
-            .split('-')
-            .next()
-            .unwrap_or("123")
-            .to_string();
-        let user_uuid = format!("demo-user-{}", uuid_fragment);
-
-        let mut env_content = utils::ENV_TEMPLATE.to_string();
-        env_content = env_content.replace(
-            "{{ANALYTICS_AI_SERVICE_PORT}}",
-            self.form_data.ai_service_port.as_str(),
-        );
-        env_content =
-            env_content.replace("{{OPENAI_API_KEY}}", self.form_data.openai_api_key.as_str());
-        env_content = env_content.replace("{{USER_UUID}}", user_uuid.as_str());
-        env_content = env_content.replace(
-            "{{GENERATION_MODEL}}",
-            self.form_data.generation_model.as_str(),
-        );
-        env_content = env_content.replace("{{HOST_PORT}}", self.form_data.host_port.as_str());
-        env_content = env_content.replace(
-            "{{AI_SERVICE_FORWARD_PORT}}",
-            self.form_data.ai_service_port.as_str(),
-        );
-
-        fs::write(env_path, env_content)?;
-        Ok(())
+    fn check_ports(&mut self) {
+        let services = compose::load_services().unwrap_or_default();
+        let Some(choice) = &self.platform_choice else {
+            self.spawn_check(
+                "Checking which platforms the images are published for...",
+                move || CheckEvent::Platform(platform::select(&services)),
+            );
+            return;
+        };
+        self.form_data.platform = choice.platform.clone();
+        let env = EnvFile::parse(&generate::env_content(&self.form_data).content);
+        self.port_conflicts = ports::find_conflicts(&ports::published_ports(&services, &env));
+        if self.port_conflicts.is_empty() {
+            self.enter_review();
+        } else {
+            self.state = AppState::Ports;
+        }
     }
 
-    fn write_config_yaml(&self, template: &ConfigTemplate) -> Result<()> {
-        let project_root = utils::project_root();
-        let config_path = project_root.join("config.yaml");
-        fs::write(config_path, template.render())?;
+    fn handle_ports_events(&mut self) -> Result<()> {
+        if event::poll(std::time::Duration::from_millis(100))?
+            && let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+        {
+            match key.code {
+                KeyCode::Enter => {
+                    for conflict in &self.port_conflicts {
+                        if let (Some(env_var), Some(port)) =
+                            (&conflict.published.env_var, conflict.suggestion)
+                        {
+                            self.form_data.set_port(env_var, port);
+                        }
+                    }
+                    self.enter_form_section(FormSection::Options);
+                    self.state = AppState::Options;
+                }
+                KeyCode::Char('i') => self.enter_review(),
+                KeyCode::Esc => {
+                    self.enter_form_section(FormSection::Options);
+                    self.state = AppState::Options;
+                }
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    self.running = false;
+                }
+                _ => {}
+            }
+        }
+
         Ok(())
     }
 
-    async fn run_docker_compose(&mut self) -> Result<()> {
-        self.add_log("🔨 Step 1/2: Building images (no cache)...");
-        self.add_log("📦 Executing: docker compose build --no-cache");
-
-        let mut build_child = Command::new("docker")
-            .args(["compose", "build", "--no-cache"])
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
-
-        let build_stdout = build_child.stdout.take().expect("Failed to capture stdout");
-        let build_stderr = build_child.stderr.take().expect("Failed to capture stderr");
+    fn enter_review(&mut self) {
+        self.review_models = self.selected_template().models();
+        let env = EnvFile::parse(&generate::env_content(&self.form_data).content);
+        self.review_services = compose::load_services().unwrap_or_default();
+        for service in &mut self.review_services {
+            for port in &mut service.ports {
+                *port = ports::resolve(port, &env);
+            }
+        }
+        self.refresh_review_warnings();
+        self.state = AppState::Review;
+    }
 
-        let mut build_stdout_reader = BufReader::new(build_stdout).lines();
-        let mut build_stderr_reader = BufReader::new(build_stderr).lines();
+    fn refresh_review_warnings(&mut self) {
+        if self.keep_config {
+            self.review_warnings.clear();
+            return;
+        }
+        self.review_warnings = self
+            .selected_template()
+            .embedding_warnings(&self.form_data.placeholder_pairs(), &self.pipe_editor.pipes);
+        self.review_warnings
+            .extend(self.settings_editor.warning_messages());
+    }
 
-        loop {
-            tokio::select! {
-                result = build_stdout_reader.next_line() => {
-                    match result {
-                        Ok(Some(line)) => self.process_log_line(&line),
-                        Ok(None) => break,
-                        Err(e) => {
-                            self.add_log(&format!("❌ Error reading stdout: {}", e));
-                            break;
+    fn handle_review_events(&mut self) -> Result<()> {
+        if event::poll(std::time::Duration::from_millis(100))?
+            && let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+        {
+            match key.code {
+                KeyCode::Enter => match self.prepare_setup_files() {
+                    Ok(files) => {
+                        let has_changes = files
+                            .iter()
+                            .any(|file| !file.is_new && !file.diff.is_empty());
+                        if has_changes {
+                            self.pending_files = files;
+                            self.diff_scroll = 0;
+                            self.state = AppState::Diff;
+                        } else {
+                            self.write_setup_files(files);
                         }
                     }
+                    Err(e) => self.state = AppState::Error(e),
+                },
+                KeyCode::Char('p') if !self.keep_config => {
+                    self.state = AppState::Pipes;
                 }
-                result = build_stderr_reader.next_line() => {
-                    match result {
-                        Ok(Some(line)) => self.process_log_line(&line),
-                        Ok(None) => break,
-                        Err(e) => {
-                            self.add_log(&format!("❌ Error reading stderr: {}", e));
-                            break;
-                        }
-                    }
+                KeyCode::Char('s') if !self.keep_config => {
+                    self.state = AppState::Settings;
+                }
+                KeyCode::Esc | KeyCode::Backspace | KeyCode::Char('q') => {
+                    self.enter_form_section(FormSection::Options);
+                    self.state = AppState::Options;
                 }
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    self.running = false;
+                }
+                _ => {}
             }
         }
 
-        let build_status = build_child.wait().await?;
+        Ok(())
+    }
 
-        if !build_status.success() {
-            return Err(color_eyre::eyre::eyre!("Docker Compose build failed"));
+    fn handle_pipes_events(&mut self) -> Result<()> {
+        if event::poll(std::time::Duration::from_millis(100))?
+            && let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+        {
+            match key.code {
+                KeyCode::Up => self.pipe_editor.select_previous(),
+                KeyCode::Down => self.pipe_editor.select_next(),
+                KeyCode::Left => self.pipe_editor.next_field(false),
+                KeyCode::Right | KeyCode::Tab => self.pipe_editor.next_field(true),
+                KeyCode::Enter | KeyCode::Char(' ') => self.pipe_editor.cycle_binding(true),
+                KeyCode::Backspace => self.pipe_editor.cycle_binding(false),
+                KeyCode::Char('r') => self.pipe_editor.reset_selected(),
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    self.refresh_review_warnings();
+                    self.state = AppState::Review;
+                }
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    self.running = false;
+                }
+                _ => {}
+            }
         }
 
-        self.add_log("✅ Build completed successfully!");
-        self.progress = 50.0;
+        Ok(())
+    }
 
-        self.add_log("🚀 Step 2/2: Starting services...");
-        self.add_log("📦 Executing: docker compose up -d");
+    fn handle_settings_events(&mut self) -> Result<()> {
+        if event::poll(std::time::Duration::from_millis(100))?
+            && let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+        {
+            let editor = &mut self.settings_editor;
+            if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+                self.running = false;
+            } else if editor.input.is_some() {
+                match key.code {
+                    KeyCode::Enter => editor.activate(),
+                    KeyCode::Esc => editor.cancel_input(),
+                    KeyCode::Backspace => editor.pop_char(),
+                    KeyCode::Char(c) => editor.push_char(c),
+                    _ => {}
+                }
+            } else {
+                match key.code {
+                    KeyCode::Up => editor.select_previous(),
+                    KeyCode::Down => editor.select_next(),
+                    KeyCode::Left => editor.cycle(false),
+                    KeyCode::Right => editor.cycle(true),
+                    KeyCode::Enter | KeyCode::Char(' ') => editor.activate(),
+                    KeyCode::Char('r') => editor.reset(),
+                    KeyCode::Esc | KeyCode::Char('q') => {
+                        editor.cancel_input();
+                        self.state = AppState::Review;
+                    }
+                    _ => {}
+                }
+            }
+        }
 
-        let mut up_child = Command::new("docker")
-            .args(["compose", "up", "-d"])
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+        Ok(())
+    }
 
-        let up_stdout = up_child.stdout.take().expect("Failed to capture stdout");
-        let up_stderr = up_child.stderr.take().expect("Failed to capture stderr");
+    fn handle_diff_events(&mut self) -> Result<()> {
+        if event::poll(std::time::Duration::from_millis(100))?
+            && let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+        {
+            let last_line = ui::diff_line_count(&self.pending_files).saturating_sub(1);
+            match key.code {
+                KeyCode::Up => self.diff_scroll = self.diff_scroll.saturating_sub(1),
+                KeyCode::Down => self.diff_scroll = (self.diff_scroll + 1).min(last_line),
+                KeyCode::PageUp => self.diff_scroll = self.diff_scroll.saturating_sub(10),
+                KeyCode::PageDown => self.diff_scroll = (self.diff_scroll + 10).min(last_line),
+                KeyCode::Enter => {
+                    let files = std::mem::take(&mut self.pending_files);
+                    self.write_setup_files(files);
+                }
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    self.pending_files.clear();
+                    self.state = AppState::Review;
+                }
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    self.running = false;
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
 
-        let mut up_stdout_reader = BufReader::new(up_stdout).lines();
-        let mut up_stderr_reader = BufReader::new(up_stderr).lines();
+    fn prepare_setup_files(&self) -> std::result::Result<Vec<PendingFile>, String> {
+        let env = generate::env_content(&self.form_data);
+        let mut files =
+            vec![PendingFile::new(generate::env_path(), env.content).with_notes(env.changes)];
 
-        loop {
-            tokio::select! {
-                result = up_stdout_reader.next_line() => {
-                    match result {
-                        Ok(Some(line)) => self.process_log_line(&line),
-                        Ok(None) => break,
-                        Err(e) => {
-                            self.add_log(&format!("❌ Error reading stdout: {}", e));
-                            break;
-                        }
+        if !self.keep_config {
+            let content = self
+                .selected_template()
+                .render(
+                    &self.form_data.placeholder_pairs(),
+                    &self.pipe_editor.pipes,
+                    &self.settings_editor.settings,
+                )
+                .map_err(|e| format!("Failed to generate config.yaml: {}", e))?;
+            files.push(PendingFile::new(validate::config_path(), content));
+        }
+
+        Ok(files)
+    }
+
+    fn write_setup_files(&mut self, files: Vec<PendingFile>) {
+        let mut notes = Vec::new();
+        for file in &files {
+            match file.write() {
+                Ok(written) => {
+                    for note in &file.notes {
+                        notes.push(format!("📝 .env: {}", note));
                     }
-                }
-                result = up_stderr_reader.next_line() => {
-                    match result {
-                        Ok(Some(line)) => self.process_log_line(&line),
-                        Ok(None) => break,
-                        Err(e) => {
-                            self.add_log(&format!("❌ Error reading stderr: {}", e));
-                            break;
-                        }
+                    if let Some(backup) = written.backup {
+                        notes.push(format!(
+                            "💾 Saved the previous {} to {}",
+                            file.path.display(),
+                            backup.display()
+                        ));
                     }
                 }
+                Err(e) => {
+                    self.state =
+                        AppState::Error(format!("Failed to write {}: {}", file.path.display(), e));
+                    return;
+                }
             }
         }
 
-        let up_status = up_child.wait().await?;
-
-        if up_status.success() {
-            self.add_log("✅ All services started successfully!");
-            self.progress = 100.0;
-            Ok(())
-        } else {
-            Err(color_eyre::eyre::eyre!("Docker Compose up failed"))
-        }
-    }
-
-    fn process_log_line(&mut self, line: &str) {
-        let lower = line.to_lowercase();
-
-        if lower.contains("pulling") {
-            if let Some(service) = self.extract_service_name(line) {
-                self.current_service = service.clone();
-                self.add_log(&format!("⬇️  Pulling image for {}...", service));
-            }
-        } else if lower.contains("pulled") {
-            self.add_log("✓ Image pulled");
-        } else if lower.contains("creating") {
-            if let Some(service) = self.extract_service_name(line) {
-                self.current_service = service.clone();
-                self.add_log(&format!("🔨 Creating container {}...", service));
-            }
-        } else if lower.contains("created") {
-            self.add_log("✓ Container created");
-        } else if lower.contains("starting") {
-            if let Some(service) = self.extract_service_name(line) {
-                self.current_service = service.clone();
-                self.add_log(&format!("▶️  Starting service {}...", service));
-            }
-        } else if lower.contains("started") {
-            self.completed_services += 1;
-            self.progress =
-                50.0 + (self.completed_services as f64 / self.total_services as f64) * 50.0;
-            self.add_log(&format!(
-                "✅ Service started ({}/{})",
-                self.completed_services, self.total_services
-            ));
-        } else if lower.contains("running") {
-            self.add_log("🟢 Service is running");
-        } else if lower.contains("error") || lower.contains("failed") {
-            self.add_log(&format!("❌ {}", line));
-        } else if !line.trim().is_empty() {
-            self.add_log(&format!("ℹ️  {}", line));
+        self.env_exists = true;
+        if !self.keep_config {
+            self.config_exists = true;
         }
+        self.enter_doctor(notes, Some(self.selected_template_index));
     }
 
-    fn extract_service_name(&self, line: &str) -> Option<String> {
-        let services = [
-            "analytics-service",
-            "qdrant",
-            "northwind-db",
-            "analytics-ui",
-        ];
+    fn enter_doctor(&mut self, notes: Vec<String>, template_index: Option<usize>) {
+        self.doctor_notes = notes;
+        self.install_template_index = template_index;
+        self.run_doctor();
+    }
+
+    fn run_doctor(&mut self) {
+        self.confirm_failed_doctor = false;
+        let options = self.options;
+        self.spawn_check("Running the host checks...", move || {
+            CheckEvent::Doctor(doctor::run(options))
+        });
+    }
 
-        for service in services {
-            if line.to_lowercase().contains(service) {
-                return Some(service.to_string());
+    fn handle_doctor_events(&mut self) -> Result<()> {
+        if event::poll(std::time::Duration::from_millis(100))?
+            && let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+        {
+            let failed = doctor::has_failures(&self.doctor_checks);
+            let confirmed = std::mem::take(&mut self.confirm_failed_doctor);
+            match key.code {
+                KeyCode::Enter if !failed => {
+                    let notes = std::mem::take(&mut self.doctor_notes);
+                    self.start_installation(&notes);
+                }
+                KeyCode::Char('i') if failed && !confirmed => {
+                    self.confirm_failed_doctor = true;
+                }
+                KeyCode::Char('i') => {
+                    let notes = std::mem::take(&mut self.doctor_notes);
+                    self.start_installation(&notes);
+                }
+                KeyCode::Char('r') => self.run_doctor(),
+                KeyCode::Esc => {
+                    self.doctor_notes.clear();
+                    self.refresh_files();
+                    self.state = AppState::Confirmation;
+                }
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    self.running = false;
+                }
+                _ => {}
             }
         }
-        None
-    }
 
-    fn add_log(&mut self, message: &str) {
-        self.logs.push(message.to_string());
+        Ok(())
+    }
 
-        if self.logs.len() > 100 {
-            self.logs.remove(0);
-        }
+    fn selected_template(&self) -> &ConfigTemplate {
+        &self.templates.templates[self.selected_template_index]
     }
 
     fn render(&mut self, frame: &mut Frame) {
@@ -572,41 +996,120 @@ impl App {
                 let view = ConfirmationView {
                     env_exists: self.env_exists,
                     config_exists: self.config_exists,
+                    config_origin: self.existing_config_label(),
+                    env_drift: self.env_drift.as_ref(),
+                    readiness: &self.readiness,
+                    services: &self.stack_services,
+                    menu_items: &self.menu_items(),
                     menu_selection: &self.menu_selection,
                 };
                 ui::render_confirmation(frame, &view);
             }
-            AppState::EnvSetup => {
+            AppState::EnvSetup | AppState::Options => {
+                let section = if self.state == AppState::EnvSetup {
+                    FormSection::Credentials
+                } else {
+                    FormSection::Options
+                };
                 let view = EnvSetupView {
+                    template: self.selected_template(),
                     form_data: &self.form_data,
+                    section,
                 };
                 ui::render_env_setup(frame, &view);
             }
+            AppState::Review => {
+                let view = ReviewView {
+                    template: self.selected_template(),
+                    models: &self.review_models,
+                    form_data: &self.form_data,
+                    pipes: &self.pipe_editor,
+                    settings: &self.settings_editor,
+                    keep_config: self.keep_config,
+                    services: &self.review_services,
+                    warnings: &self.review_warnings,
+                    platform_notes: self
+                        .platform_choice
+                        .as_ref()
+                        .map(|choice| choice.notes.as_slice())
+                        .unwrap_or_default(),
+                };
+                ui::render_review(frame, &view);
+            }
+            AppState::Pipes => {
+                let view = PipesView {
+                    template: self.selected_template(),
+                    editor: &self.pipe_editor,
+                };
+                ui::render_pipes(frame, &view);
+            }
+            AppState::Ports => {
+                let view = PortsView {
+                    conflicts: &self.port_conflicts,
+                };
+                ui::render_ports(frame, &view);
+            }
+            AppState::Doctor => {
+                let view = DoctorView {
+                    checks: &self.doctor_checks,
+                    confirm_install: self.confirm_failed_doctor,
+                };
+                ui::render_doctor(frame, &view);
+            }
+            AppState::Diff => {
+                let view = DiffView {
+                    files: &self.pending_files,
+                    scroll: self.diff_scroll,
+                };
+                ui::render_diff(frame, &view);
+            }
+            AppState::Settings => {
+                let view = SettingsView {
+                    template: self.selected_template(),
+                    editor: &self.settings_editor,
+                };
+                ui::render_settings(frame, &view);
+            }
             AppState::ConfigSelection => {
                 let view = ConfigSelectionView {
-                    templates: templates::CONFIG_TEMPLATES,
+                    templates: &self.templates.templates,
+                    warnings: &self.templates.warnings,
                     selected_index: self.config_selection_index,
                 };
                 ui::render_config_selection(frame, &view);
             }
+            AppState::Checking(message) => ui::render_checking(frame, message),
             AppState::Installing => {
                 let view = InstallingView {
-                    progress: self.progress,
-                    current_service: &self.current_service,
-                    completed_services: self.completed_services,
-                    total_services: self.total_services,
-                    logs: &self.logs,
+                    progress: self.install_progress.progress,
+                    current_service: &self.install_progress.current_service,
+                    completed_services: self.install_progress.completed_services,
+                    total_services: self.install_progress.total_services,
+                    logs: &self.install_progress.logs,
                 };
                 ui::render_installing(frame, &view);
             }
             AppState::Success => {
-                let view = SuccessView { logs: &self.logs };
+                let view = SuccessView {
+                    logs: &self.install_progress.logs,
+                    ui_port: &self.ui_port,
+                };
                 ui::render_success(frame, &view);
             }
             AppState::Error(err) => {
-                let view = ErrorView { logs: &self.logs };
+                let view = ErrorView {
+                    logs: &self.install_progress.logs,
+                };
                 ui::render_error(frame, err, &view);
             }
         }
     }
 }
+
+fn form_data_for(template: &ConfigTemplate) -> FormData {
+    let mut form_data = FormData::new(template);
+    if let Some(env) = EnvFile::load(&generate::env_path()) {
+        form_data.load_env(&env);
+    }
+    form_data
+}
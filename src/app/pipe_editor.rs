@@ -0,0 +1,221 @@
+use crate::templates::{ConfigTemplate, Pipe};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PipeField {
+    Llm,
+    Embedder,
+    Engine,
+    DocumentStore,
+}
+
+const PIPE_FIELDS: &[PipeField] = &[
+    PipeField::Llm,
+    PipeField::Embedder,
+    PipeField::Engine,
+    PipeField::DocumentStore,
+];
+
+#[derive(Debug, Clone)]
+pub struct PipeEditor {
+    pub(crate) pipes: Vec<Pipe>,
+    defaults: Vec<Pipe>,
+    pub(crate) llm_bindings: Vec<String>,
+    pub(crate) embedder_bindings: Vec<String>,
+    pub(crate) engines: Vec<String>,
+    pub(crate) document_stores: Vec<String>,
+    pub(crate) selected: usize,
+    pub(crate) field: PipeField,
+}
+
+impl PipeEditor {
+    pub fn new(template: &ConfigTemplate) -> Self {
+        let pipes = template.pipes();
+        let models = template.models();
+        let bindings = |kind: &str| -> Vec<String> {
+            models
+                .iter()
+                .filter(|model| model.kind == kind)
+                .map(|model| model.binding())
+                .collect()
+        };
+
+        Self {
+            defaults: pipes.clone(),
+            pipes,
+            llm_bindings: bindings("llm"),
+            embedder_bindings: bindings("embedder"),
+            engines: template.providers("engine"),
+            document_stores: template.providers("document_store"),
+            selected: 0,
+            field: PipeField::Llm,
+        }
+    }
+
+    pub fn options(&self, field: PipeField) -> &[String] {
+        match field {
+            PipeField::Llm => &self.llm_bindings,
+            PipeField::Embedder => &self.embedder_bindings,
+            PipeField::Engine => &self.engines,
+            PipeField::DocumentStore => &self.document_stores,
+        }
+    }
+
+    pub fn load(&mut self, pipes: &[Pipe]) {
+        for index in 0..self.pipes.len() {
+            let Some(mut existing) = pipes
+                .iter()
+                .find(|existing| existing.name == self.pipes[index].name)
+                .cloned()
+            else {
+                continue;
+            };
+            for field in PIPE_FIELDS {
+                let value = field_value(&mut existing, *field).take();
+                if value
+                    .as_ref()
+                    .is_none_or(|value| self.options(*field).contains(value))
+                {
+                    *field_value(&mut self.pipes[index], *field) = value;
+                }
+            }
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        if !self.pipes.is_empty() {
+            self.selected = (self.selected + 1) % self.pipes.len();
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        if !self.pipes.is_empty() {
+            self.selected = self.selected.checked_sub(1).unwrap_or(self.pipes.len() - 1);
+        }
+    }
+
+    pub fn next_field(&mut self, forward: bool) {
+        let index = PIPE_FIELDS
+            .iter()
+            .position(|field| *field == self.field)
+            .unwrap_or(0);
+        let next = if forward {
+            (index + 1) % PIPE_FIELDS.len()
+        } else {
+            index.checked_sub(1).unwrap_or(PIPE_FIELDS.len() - 1)
+        };
+        self.field = PIPE_FIELDS[next];
+    }
+
+    pub fn cycle_binding(&mut self, forward: bool) {
+        let options = self.options(self.field).to_vec();
+        if options.is_empty() {
+            return;
+        }
+        let Some(pipe) = self.pipes.get_mut(self.selected) else {
+            return;
+        };
+        let current = field_value(pipe, self.field);
+
+        // Position 0 leaves the reference unset.
+        let position = current
+            .as_ref()
+            .and_then(|current| options.iter().position(|option| option == current))
+            .map_or(0, |index| index + 1);
+        let count = options.len() + 1;
+        let next = if forward {
+            (position + 1) % count
+        } else {
+            (position + count - 1) % count
+        };
+        *current = next.checked_sub(1).map(|index| options[index].clone());
+    }
+
+    pub fn reset_selected(&mut self) {
+        if let (Some(pipe), Some(default)) = (
+            self.pipes.get_mut(self.selected),
+            self.defaults.get(self.selected),
+        ) {
+            *pipe = default.clone();
+        }
+    }
+
+    pub fn is_customized(&self, index: usize) -> bool {
+        self.pipes.get(index) != self.defaults.get(index)
+    }
+
+    pub fn customized_count(&self) -> usize {
+        (0..self.pipes.len())
+            .filter(|index| self.is_customized(*index))
+            .count()
+    }
+}
+
+fn field_value(pipe: &mut Pipe, field: PipeField) -> &mut Option<String> {
+    match field {
+        PipeField::Llm => &mut pipe.llm,
+        PipeField::Embedder => &mut pipe.embedder,
+        PipeField::Engine => &mut pipe.engine,
+        PipeField::DocumentStore => &mut pipe.document_store,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::templates::TemplateCatalog;
+
+    fn editor() -> PipeEditor {
+        let catalog = TemplateCatalog::built_in();
+        PipeEditor::new(catalog.find("openai").unwrap())
+    }
+
+    #[test]
+    fn engine_and_store_options_come_from_the_template() {
+        let editor = editor();
+        assert_eq!(editor.engines, ["analytics_ui", "analytics_ibis"]);
+        assert_eq!(editor.document_stores, ["qdrant"]);
+    }
+
+    #[test]
+    fn cycle_through_unset() {
+        let mut editor = editor();
+        editor.selected = editor
+            .pipes
+            .iter()
+            .position(|pipe| pipe.document_store.is_none())
+            .unwrap();
+        editor.field = PipeField::Llm;
+        editor.next_field(false);
+        assert_eq!(editor.field, PipeField::DocumentStore);
+
+        editor.cycle_binding(true);
+        assert_eq!(
+            editor.pipes[editor.selected].document_store.as_deref(),
+            Some("qdrant")
+        );
+        assert!(editor.is_customized(editor.selected));
+        editor.cycle_binding(true);
+        assert_eq!(editor.pipes[editor.selected].document_store, None);
+        editor.cycle_binding(false);
+        assert_eq!(
+            editor.pipes[editor.selected].document_store.as_deref(),
+            Some("qdrant")
+        );
+
+        editor.reset_selected();
+        assert!(!editor.is_customized(editor.selected));
+    }
+
+    #[test]
+    fn load_keeps_known_references() {
+        let mut editor = editor();
+        let mut existing = editor.pipes[0].clone();
+        existing.engine = Some("analytics_ibis".to_string());
+        existing.document_store = Some("not_in_template".to_string());
+        let default_store = editor.pipes[0].document_store.clone();
+
+        editor.load(&[existing]);
+        assert_eq!(editor.pipes[0].engine.as_deref(), Some("analytics_ibis"));
+        assert_eq!(editor.pipes[0].document_store, default_store);
+    }
+}
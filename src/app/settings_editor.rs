@@ -0,0 +1,223 @@
+use crate::settings::{PRESETS, SETTING_FIELDS, SettingField, SettingKind, Settings};
+use crate::templates::ConfigTemplate;
+
+#[derive(Debug, Clone)]
+pub struct SettingsEditor {
+    pub(crate) settings: Settings,
+    defaults: Settings,
+    pub(crate) selected: usize,
+    pub(crate) input: Option<String>,
+    pub(crate) error: Option<String>,
+    pub(crate) warnings: Vec<(String, String)>,
+}
+
+impl SettingsEditor {
+    pub fn new(template: &ConfigTemplate) -> Self {
+        Self {
+            settings: template.settings().clone(),
+            defaults: template.settings().clone(),
+            selected: 0,
+            input: None,
+            error: None,
+            warnings: Vec::new(),
+        }
+    }
+
+    pub fn load(&mut self, settings: &Settings, warnings: &[(String, String)]) {
+        self.settings = settings.clone();
+        self.warnings = warnings.to_vec();
+        self.cancel_input();
+    }
+
+    pub fn warning_messages(&self) -> Vec<String> {
+        self.warnings
+            .iter()
+            .map(|(_, message)| message.clone())
+            .collect()
+    }
+
+    pub fn selected_field(&self) -> Option<&'static SettingField> {
+        self.selected
+            .checked_sub(1)
+            .and_then(|index| SETTING_FIELDS.get(index))
+    }
+
+    pub fn select_next(&mut self) {
+        self.cancel_input();
+        self.selected = (self.selected + 1) % (SETTING_FIELDS.len() + 1);
+    }
+
+    pub fn select_previous(&mut self) {
+        self.cancel_input();
+        self.selected = self.selected.checked_sub(1).unwrap_or(SETTING_FIELDS.len());
+    }
+
+    pub fn cycle(&mut self, forward: bool) {
+        self.error = None;
+        match self.selected_field() {
+            Some(field) => {
+                self.settings.cycle(field.key, forward);
+                self.clear_warning(field.key);
+            }
+            None => {
+                let next = match self
+                    .settings
+                    .preset()
+                    .and_then(|preset| PRESETS.iter().position(|known| *known == preset))
+                {
+                    Some(index) if forward => (index + 1) % PRESETS.len(),
+                    Some(index) => index.checked_sub(1).unwrap_or(PRESETS.len() - 1),
+                    None => 0,
+                };
+                self.settings = PRESETS[next].settings();
+                self.warnings
+                    .retain(|(key, _)| SETTING_FIELDS.iter().all(|field| field.key != key));
+            }
+        }
+    }
+
+    pub fn activate(&mut self) {
+        if self.input.is_some() {
+            self.commit_input();
+            return;
+        }
+
+        match self.selected_field() {
+            Some(field) if matches!(field.kind, SettingKind::Toggle | SettingKind::Choice(_)) => {
+                self.cycle(true)
+            }
+            Some(field) => {
+                self.error = None;
+                self.input = Some(self.settings.display(field.key));
+            }
+            None => self.cycle(true),
+        }
+    }
+
+    pub fn push_char(&mut self, c: char) {
+        if let Some(input) = self.input.as_mut() {
+            input.push(c);
+        }
+    }
+
+    pub fn pop_char(&mut self) {
+        if let Some(input) = self.input.as_mut() {
+            input.pop();
+        }
+    }
+
+    pub fn cancel_input(&mut self) {
+        self.input = None;
+        self.error = None;
+    }
+
+    pub fn reset(&mut self) {
+        self.settings = self.defaults.clone();
+        self.warnings
+            .retain(|(key, _)| SETTING_FIELDS.iter().all(|field| field.key != key));
+        self.cancel_input();
+    }
+
+    pub fn changed_keys(&self) -> Vec<&'static str> {
+        self.settings.changed_keys(&self.defaults)
+    }
+
+    fn commit_input(&mut self) {
+        let (Some(field), Some(input)) = (self.selected_field(), self.input.as_ref()) else {
+            return;
+        };
+        match self.settings.set(field.key, input) {
+            Ok(()) => {
+                self.clear_warning(field.key);
+                self.cancel_input();
+            }
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    fn clear_warning(&mut self, key: &str) {
+        self.warnings.retain(|(warned, _)| warned != key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::SettingsPreset;
+    use crate::templates::TemplateCatalog;
+
+    fn editor() -> SettingsEditor {
+        let catalog = TemplateCatalog::built_in();
+        SettingsEditor::new(catalog.find("openai").unwrap())
+    }
+
+    fn select(editor: &mut SettingsEditor, key: &str) {
+        editor.selected = SETTING_FIELDS
+            .iter()
+            .position(|field| field.key == key)
+            .unwrap()
+            + 1;
+    }
+
+    fn enter(editor: &mut SettingsEditor, key: &str, input: &str) {
+        select(editor, key);
+        editor.activate();
+        editor.input = Some(input.to_string());
+        editor.activate();
+    }
+
+    #[test]
+    fn input_is_checked_before_it_is_saved() {
+        let mut editor = editor();
+
+        enter(&mut editor, "instructions_similarity_threshold", "2");
+        assert_eq!(
+            editor.error.as_deref(),
+            Some("instructions_similarity_threshold must be a number from 0 to 1")
+        );
+        assert!(editor.input.is_some());
+        assert!(editor.changed_keys().is_empty());
+
+        editor.input = Some("0.5".to_string());
+        editor.activate();
+        assert_eq!(editor.error, None);
+        assert_eq!(editor.input, None);
+        assert_eq!(editor.changed_keys(), ["instructions_similarity_threshold"]);
+        assert_eq!(editor.settings.preset(), None);
+
+        editor.reset();
+        assert!(editor.changed_keys().is_empty());
+    }
+
+    #[test]
+    fn presets_cycle_from_the_preset_row() {
+        let mut editor = editor();
+        assert_eq!(editor.settings.preset(), Some(SettingsPreset::Production));
+
+        editor.cycle(true);
+        assert_eq!(editor.settings.preset(), Some(SettingsPreset::Development));
+        editor.cycle(false);
+        editor.cycle(false);
+        assert_eq!(editor.settings.preset(), Some(SettingsPreset::Debug));
+    }
+
+    #[test]
+    fn loaded_warnings_clear_when_the_setting_is_fixed() {
+        let mut editor = editor();
+        let (settings, warnings) = Settings::from_config(
+            &serde_yaml::from_str("logging_level: TRACE\nengine_timeout: soon\nretired: 1\n")
+                .unwrap(),
+        );
+        editor.load(&settings, &warnings);
+        assert_eq!(editor.warning_messages().len(), 3);
+
+        select(&mut editor, "logging_level");
+        editor.cycle(true);
+        assert_eq!(editor.settings.logging_level, "DEBUG");
+        enter(&mut editor, "engine_timeout", "60");
+        assert_eq!(
+            editor.warning_messages(),
+            ["unknown setting 'retired' is dropped"]
+        );
+    }
+}
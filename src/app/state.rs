@@ -1,8 +1,16 @@
 #[derive(Debug, Clone, PartialEq)]
 pub enum AppState {
     Confirmation,
-    EnvSetup,
     ConfigSelection,
+    EnvSetup,
+    Options,
+    Ports,
+    Review,
+    Pipes,
+    Settings,
+    Diff,
+    Doctor,
+    Checking(String),
     Installing,
     Success,
     Error(String),
@@ -11,7 +19,24 @@ pub enum AppState {
 #[derive(Debug, Clone, PartialEq)]
 pub enum MenuSelection {
     Proceed,
-    GenerateEnv,
-    GenerateConfig,
+    AddMissingKeys,
+    Keep,
+    Edit,
+    Regenerate,
+    Setup,
     Cancel,
 }
+
+impl MenuSelection {
+    pub fn label(&self) -> &'static str {
+        match self {
+            MenuSelection::Proceed => "[ Proceed with Installation ]",
+            MenuSelection::AddMissingKeys => "[ Add Missing .env Keys ]",
+            MenuSelection::Keep => "[ Keep config.yaml, Set Up .env ]",
+            MenuSelection::Edit => "[ Edit config.yaml ]",
+            MenuSelection::Regenerate => "[ Regenerate from Template ]",
+            MenuSelection::Setup => "[ Run Setup Wizard ]",
+            MenuSelection::Cancel => "[ Cancel ]",
+        }
+    }
+}
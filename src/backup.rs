@@ -0,0 +1,329 @@
+use color_eyre::{Result, eyre::eyre};
+use std::fs;
+use std::io;
+#[cfg(unix)]
+use std::io::Write;
+#[cfg(unix)]
+use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::utils;
+
+pub const MANAGED_FILES: &[&str] = &[".env", "config.yaml", "docker-compose.yaml"];
+
+#[cfg(unix)]
+const BACKUP_DIR_MODE: u32 = 0o700;
+#[cfg(unix)]
+const BACKUP_FILE_MODE: u32 = 0o600;
+
+#[derive(Debug, Clone)]
+pub struct Written {
+    pub path: PathBuf,
+    pub backup: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Backup {
+    pub name: String,
+    pub file: &'static str,
+    pub path: PathBuf,
+}
+
+pub fn backups_dir() -> PathBuf {
+    utils::project_root().join("backups")
+}
+
+pub fn write_file(path: &Path, content: &str) -> Result<Written> {
+    let backup = match fs::read_to_string(path) {
+        Ok(current) if current == content => None,
+        Ok(current) => Some(store_backup(&backups_dir(), path, &current)?),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => return Err(eyre!("failed to read {}: {}", path.display(), e)),
+    };
+
+    write_atomic(path, content)?;
+    Ok(Written {
+        path: path.to_path_buf(),
+        backup,
+    })
+}
+
+pub fn write_atomic(path: &Path, content: &str) -> Result<()> {
+    let permissions = fs::metadata(path)
+        .ok()
+        .map(|metadata| metadata.permissions());
+    write_with_permissions(path, content, permissions)
+}
+
+fn write_with_permissions(
+    path: &Path,
+    content: &str,
+    permissions: Option<fs::Permissions>,
+) -> Result<()> {
+    let file_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| eyre!("{} is not a file path", path.display()))?;
+    let temp_path = path.with_file_name(format!(".{}.{}.tmp", file_name, std::process::id()));
+
+    let result = write_temp(&temp_path, content, permissions)
+        .map_err(|e| eyre!("failed to write {}: {}", temp_path.display(), e))
+        .and_then(|()| {
+            fs::rename(&temp_path, path)
+                .map_err(|e| eyre!("failed to replace {}: {}", path.display(), e))
+        });
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+#[cfg(unix)]
+fn write_temp(path: &Path, content: &str, permissions: Option<fs::Permissions>) -> io::Result<()> {
+    let mode = permissions
+        .as_ref()
+        .map(|permissions| permissions.mode() & 0o777)
+        .unwrap_or(0o666);
+    fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(mode)
+        .open(path)
+        .and_then(|mut file| file.write_all(content.as_bytes()))?;
+    match permissions {
+        Some(permissions) => fs::set_permissions(path, permissions),
+        None => Ok(()),
+    }
+}
+
+#[cfg(not(unix))]
+fn write_temp(path: &Path, content: &str, _permissions: Option<fs::Permissions>) -> io::Result<()> {
+    fs::write(path, content)
+}
+
+pub fn list() -> Result<Vec<Backup>> {
+    list_in(&backups_dir())
+}
+
+fn list_in(dir: &Path) -> Result<Vec<Backup>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(eyre!("failed to read {}: {}", dir.display(), e)),
+    };
+
+    let mut backups: Vec<Backup> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_str()?.to_string();
+            let file = MANAGED_FILES
+                .iter()
+                .find(|file| name.starts_with(&format!("{}.", file)))?;
+            Some(Backup {
+                file,
+                path: entry.path(),
+                name,
+            })
+        })
+        .collect();
+    backups.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(backups)
+}
+
+pub fn restore(name: &str) -> Result<Written> {
+    let backups = list()?;
+    let backup = find(&backups, name)
+        .ok_or_else(|| eyre!("no backup named {} in {}", name, backups_dir().display()))?;
+
+    let content = fs::read_to_string(&backup.path)
+        .map_err(|e| eyre!("failed to read {}: {}", backup.path.display(), e))?;
+    write_file(&utils::project_root().join(backup.file), &content)
+}
+
+fn find<'a>(backups: &'a [Backup], name: &str) -> Option<&'a Backup> {
+    if MANAGED_FILES.contains(&name) {
+        backups.iter().rev().find(|backup| backup.file == name)
+    } else {
+        backups.iter().find(|backup| backup.name == name)
+    }
+}
+
+fn store_backup(dir: &Path, path: &Path, content: &str) -> Result<PathBuf> {
+    create_backup_dir(dir).map_err(|e| eyre!("failed to create {}: {}", dir.display(), e))?;
+
+    let file_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("file");
+    let backup_path = backup_path(dir, file_name, &timestamp());
+
+    write_with_permissions(&backup_path, content, backup_permissions())?;
+    Ok(backup_path)
+}
+
+#[cfg(unix)]
+fn create_backup_dir(dir: &Path) -> io::Result<()> {
+    fs::DirBuilder::new()
+        .recursive(true)
+        .mode(BACKUP_DIR_MODE)
+        .create(dir)?;
+    fs::set_permissions(dir, fs::Permissions::from_mode(BACKUP_DIR_MODE))
+}
+
+#[cfg(not(unix))]
+fn create_backup_dir(dir: &Path) -> io::Result<()> {
+    fs::create_dir_all(dir)
+}
+
+#[cfg(unix)]
+fn backup_permissions() -> Option<fs::Permissions> {
+    Some(fs::Permissions::from_mode(BACKUP_FILE_MODE))
+}
+
+#[cfg(not(unix))]
+fn backup_permissions() -> Option<fs::Permissions> {
+    None
+}
+
+fn backup_path(dir: &Path, file_name: &str, stamp: &str) -> PathBuf {
+    let mut backup_path = dir.join(format!("{}.{}", file_name, stamp));
+    let mut attempt = 2;
+    while backup_path.exists() {
+        backup_path = dir.join(format!("{}.{}-{}", file_name, stamp, attempt));
+        attempt += 1;
+    }
+    backup_path
+}
+
+fn timestamp() -> String {
+    format_timestamp(
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default(),
+    )
+}
+
+fn format_timestamp(seconds: u64) -> String {
+    let days = (seconds / 86_400) as i64;
+    let time = seconds % 86_400;
+
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{:04}{:02}{:02}-{:02}{:02}{:02}",
+        year,
+        month,
+        day,
+        time / 3600,
+        time % 3600 / 60,
+        time % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    fn mode(path: &Path) -> u32 {
+        fs::metadata(path).unwrap().permissions().mode() & 0o777
+    }
+
+    #[test]
+    fn timestamps_follow_the_civil_calendar() {
+        assert_eq!(format_timestamp(0), "19700101-000000");
+        assert_eq!(format_timestamp(951_782_400), "20000229-000000");
+        assert_eq!(format_timestamp(1_709_164_799), "20240228-235959");
+        assert_eq!(format_timestamp(1_709_251_199), "20240229-235959");
+        assert_eq!(format_timestamp(1_735_689_600), "20250101-000000");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn write_atomic_keeps_the_file_mode() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".env");
+        fs::write(&path, "OPENAI_API_KEY=old\n").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600)).unwrap();
+
+        write_atomic(&path, "OPENAI_API_KEY=new\n").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "OPENAI_API_KEY=new\n");
+        assert_eq!(mode(&path), 0o600);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn backups_are_private() {
+        let dir = tempfile::tempdir().unwrap();
+        let backups = dir.path().join("backups");
+
+        let backup = store_backup(&backups, &dir.path().join(".env"), "KEY=secret\n").unwrap();
+
+        assert_eq!(mode(&backups), 0o700);
+        assert_eq!(mode(&backup), 0o600);
+        assert_eq!(fs::read_to_string(&backup).unwrap(), "KEY=secret\n");
+    }
+
+    #[test]
+    fn colliding_backups_get_a_suffix() {
+        let dir = tempfile::tempdir().unwrap();
+        let stamp = "20250101-120000";
+
+        let first = backup_path(dir.path(), ".env", stamp);
+        fs::write(&first, "1").unwrap();
+        let second = backup_path(dir.path(), ".env", stamp);
+        fs::write(&second, "2").unwrap();
+        let third = backup_path(dir.path(), ".env", stamp);
+
+        assert_eq!(first, dir.path().join(".env.20250101-120000"));
+        assert_eq!(second, dir.path().join(".env.20250101-120000-2"));
+        assert_eq!(third, dir.path().join(".env.20250101-120000-3"));
+    }
+
+    #[test]
+    fn restore_picks_the_newest_backup_of_a_file() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in [
+            ".env.20250101-120000",
+            ".env.20250301-080000",
+            "config.yaml.20250401-090000",
+            ".env.20250201-100000",
+            "notes.txt",
+        ] {
+            fs::write(dir.path().join(name), name).unwrap();
+        }
+
+        let backups = list_in(dir.path()).unwrap();
+        let names: Vec<&str> = backups.iter().map(|backup| backup.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                ".env.20250101-120000",
+                ".env.20250201-100000",
+                ".env.20250301-080000",
+                "config.yaml.20250401-090000",
+            ]
+        );
+
+        assert_eq!(find(&backups, ".env").unwrap().name, ".env.20250301-080000");
+        assert_eq!(find(&backups, ".env.20250101-120000").unwrap().file, ".env");
+        assert!(find(&backups, "docker-compose.yaml").is_none());
+    }
+}
@@ -0,0 +1,688 @@
+use clap::{Args, Parser, Subcommand};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use crate::app::{FormData, form_data};
+use crate::backup::{self, Written};
+use crate::compose;
+use crate::doctor::{self, CheckStatus, DoctorCheck};
+use crate::env_file::EnvFile;
+use crate::generate;
+use crate::installer::{self, Backend, InstallEvent, InstallOptions};
+use crate::platform;
+use crate::readiness;
+use crate::runtime::{self, RuntimeKind};
+use crate::settings::{self, Settings, SettingsPreset};
+use crate::stack::{self, StackOptions};
+use crate::templates::{self, ConfigTemplate, TemplateCatalog};
+use crate::utils;
+use crate::validate;
+
+const EXIT_INVALID_INPUT: u8 = 2;
+const EXIT_WRITE_FAILED: u8 = 3;
+const EXIT_INSTALL_FAILED: u8 = 4;
+
+#[derive(Debug, Parser)]
+#[command(
+    version,
+    about = "Installer for the Analytics stack",
+    long_about = "Installer for the Analytics stack.\n\nRun without a subcommand to start the interactive installer.",
+    after_help = "Exit codes: 0 success, 2 invalid input or config.yaml failed validation, 3 failed to write files, 4 docker compose or a doctor check failed"
+)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Compose runtime: docker, docker-compose or podman; detected in that order when omitted
+    #[arg(long, global = true, env = "INSTALLER_RUNTIME", value_parser = parse_runtime)]
+    pub runtime: Option<RuntimeKind>,
+
+    /// Pull and start the images through the Docker Engine API on DOCKER_HOST or
+    /// /var/run/docker.sock instead of parsing the compose output (unix only)
+    #[arg(long, global = true, env = "INSTALLER_ENGINE_API")]
+    pub engine_api: bool,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Generate missing files, then build and start the stack
+    Install(InstallArgs),
+    /// Generate the .env file
+    GenerateEnv(GenerateEnvArgs),
+    /// Generate config.yaml from a provider template
+    GenerateConfig(GenerateConfigArgs),
+    /// Generate docker-compose.yaml from the built-in service model
+    GenerateCompose(GenerateComposeArgs),
+    /// List the available provider templates and where they were loaded from
+    Templates,
+    /// Check that config.yaml is coherent before running the stack
+    Validate(ValidateArgs),
+    /// Check the host for the container runtime, image platforms, disk, memory and free ports
+    Doctor,
+    /// Restore .env, config.yaml or docker-compose.yaml from a backup, or list the backups
+    Restore(RestoreArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct EnvArgs {
+    /// Provider credential as ENV_VAR=value, e.g. ANTHROPIC_API_KEY=sk-ant-...;
+    /// credentials not given here are read from the environment
+    #[arg(long = "credential", value_name = "ENV_VAR=VALUE", value_parser = parse_assignment)]
+    pub credentials: Vec<(String, String)>,
+
+    /// Model written to GENERATION_MODEL; keeps the value in .env when omitted
+    #[arg(long)]
+    pub generation_model: Option<String>,
+
+    /// Host port for the Analytics UI; keeps the value in .env, or 3000, when omitted
+    #[arg(long)]
+    pub host_port: Option<u16>,
+
+    /// Port for the AI service; keeps the value in .env, or 5555, when omitted
+    #[arg(long)]
+    pub ai_service_port: Option<u16>,
+
+    /// Platform written to PLATFORM, e.g. linux/arm64; detected from the host
+    /// architecture and the published image manifests when omitted
+    #[arg(long, value_parser = parse_platform)]
+    pub platform: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct ProviderArgs {
+    /// Key of the configuration template, e.g. openai, anthropic, bedrock
+    #[arg(long, default_value = "openai")]
+    pub provider: String,
+}
+
+#[derive(Debug, Args)]
+pub struct PlaceholderArgs {
+    /// Value for a template placeholder such as <region-name>, given as NAME=VALUE
+    #[arg(long = "set", value_name = "NAME=VALUE", value_parser = parse_assignment)]
+    pub values: Vec<(String, String)>,
+}
+
+#[derive(Debug, Args)]
+pub struct SettingsArgs {
+    /// Settings preset: production, development or debug; defaults to the template's settings
+    #[arg(long)]
+    pub preset: Option<String>,
+
+    /// Override a single setting, e.g. engine_timeout=60
+    #[arg(long = "setting", value_name = "KEY=VALUE", value_parser = parse_assignment)]
+    pub settings: Vec<(String, String)>,
+}
+
+#[derive(Debug, Args)]
+pub struct InstallArgs {
+    #[command(flatten)]
+    pub provider: ProviderArgs,
+
+    #[command(flatten)]
+    pub env: EnvArgs,
+
+    #[command(flatten)]
+    pub placeholders: PlaceholderArgs,
+
+    #[command(flatten)]
+    pub settings: SettingsArgs,
+
+    /// Regenerate .env and config.yaml even if they already exist
+    #[arg(long)]
+    pub force: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct GenerateEnvArgs {
+    #[command(flatten)]
+    pub provider: ProviderArgs,
+
+    #[command(flatten)]
+    pub env: EnvArgs,
+
+    /// Overwrite an existing .env
+    #[arg(long)]
+    pub force: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct GenerateConfigArgs {
+    #[command(flatten)]
+    pub provider: ProviderArgs,
+
+    #[command(flatten)]
+    pub placeholders: PlaceholderArgs,
+
+    #[command(flatten)]
+    pub settings: SettingsArgs,
+
+    /// Overwrite an existing config.yaml
+    #[arg(long)]
+    pub force: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct GenerateComposeArgs {
+    /// Leave out the Northwind PostgreSQL demo database
+    #[arg(long)]
+    pub without_northwind: bool,
+
+    /// Leave out the ibis-server data source connector
+    #[arg(long)]
+    pub without_ibis: bool,
+
+    /// Tag of the qdrant/qdrant image
+    #[arg(long, default_value = stack::DEFAULT_QDRANT_VERSION, value_parser = stack::validate_image_tag)]
+    pub qdrant_version: String,
+
+    /// Pin every image to this platform, e.g. linux/amd64, instead of using PLATFORM from .env
+    #[arg(long, value_parser = parse_platform)]
+    pub platform: Option<String>,
+
+    /// Overwrite an existing docker-compose.yaml
+    #[arg(long)]
+    pub force: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct ValidateArgs {
+    /// Path to the config file, defaults to config.yaml in the project root
+    #[arg(long)]
+    pub file: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+pub struct RestoreArgs {
+    /// Backup to restore, e.g. config.yaml.20250101-120000, or .env, config.yaml or
+    /// docker-compose.yaml for the latest backup of that file; lists the backups when omitted
+    pub backup: Option<String>,
+}
+
+pub async fn run(command: Command, options: InstallOptions) -> ExitCode {
+    let catalog = TemplateCatalog::load();
+    for warning in &catalog.warnings {
+        eprintln!("warning: {}", warning);
+    }
+
+    let result = match command {
+        Command::Install(args) => install(&catalog, args, options).await,
+        Command::GenerateEnv(args) => {
+            find_template(&catalog, &args.provider).and_then(|template| {
+                let form_data = build_form_data(template, Some(&args.env), &[])?;
+                generate_env(form_data, args.env.platform.as_deref(), args.force)
+            })
+        }
+        Command::GenerateConfig(args) => {
+            find_template(&catalog, &args.provider).and_then(|template| {
+                let form_data = build_form_data(template, None, &args.placeholders.values)?;
+                let settings = build_settings(template, &args.settings)?;
+                generate_config(template, &form_data, &settings, args.force)
+            })
+        }
+        Command::GenerateCompose(args) => generate_compose(args),
+        Command::Templates => {
+            list_templates(&catalog);
+            Ok(())
+        }
+        Command::Validate(args) => {
+            let path = args.file.unwrap_or_else(validate::config_path);
+            validate_config(&path)
+        }
+        Command::Doctor => {
+            let checks = doctor::run(options);
+            print_checks(&checks);
+            if doctor::has_failures(&checks) {
+                Err(EXIT_INSTALL_FAILED)
+            } else {
+                Ok(())
+            }
+        }
+        Command::Restore(args) => restore(args.backup.as_deref()),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(code) => ExitCode::from(code),
+    }
+}
+
+async fn install(
+    catalog: &TemplateCatalog,
+    args: InstallArgs,
+    options: InstallOptions,
+) -> Result<(), u8> {
+    let template = find_template(catalog, &args.provider)?;
+    let form_data = build_form_data(template, Some(&args.env), &args.placeholders.values)?;
+    let settings = build_settings(template, &args.settings)?;
+    generate_config(template, &form_data, &settings, args.force)?;
+    generate_env(form_data, args.env.platform.as_deref(), args.force)?;
+    check_readiness(template)?;
+    validate_config(&validate::config_path())?;
+
+    let checks = doctor::run(options);
+    if doctor::has_failures(&checks) {
+        print_checks(&checks);
+        eprintln!("Fix the failed checks above, then run install again");
+        return Err(EXIT_INSTALL_FAILED);
+    }
+    for check in checks
+        .iter()
+        .filter(|check| check.status == CheckStatus::Warn)
+    {
+        eprintln!("warning: {}: {}", check.name, check.detail);
+    }
+
+    let (runtime, version) = match runtime::detect(options.runtime) {
+        Ok(detected) => detected,
+        Err(e) => {
+            eprintln!("{}", e);
+            return Err(EXIT_INSTALL_FAILED);
+        }
+    };
+    let backend = match Backend::new(options) {
+        Ok(backend) => backend,
+        Err(e) => {
+            eprintln!("{}", e);
+            return Err(EXIT_INSTALL_FAILED);
+        }
+    };
+    println!(
+        "🚀 Starting Analytics installation with {} {}...",
+        runtime.command_line(&[], &[]),
+        version
+    );
+
+    let (_task, mut events) = installer::spawn(runtime, backend);
+    let mut result = Err("installer task stopped unexpectedly".to_string());
+
+    while let Some(event) = events.recv().await {
+        match event {
+            InstallEvent::Log(message) => println!("{}", message),
+            InstallEvent::ContainerFailed { container, failure } => {
+                eprintln!("❌ {} {}", container, failure)
+            }
+            InstallEvent::Finished(finished) => {
+                result = finished;
+                break;
+            }
+            InstallEvent::Service(_) | InstallEvent::Progress { .. } => {}
+        }
+    }
+
+    match result {
+        Ok(()) => {
+            let host_port = EnvFile::load(&generate::env_path())
+                .and_then(|env| env.get("HOST_PORT").map(|port| port.trim().to_string()))
+                .filter(|port| !port.is_empty())
+                .unwrap_or_else(|| form_data::DEFAULT_HOST_PORT.to_string());
+            println!("Analytics is running at http://localhost:{}", host_port);
+            Ok(())
+        }
+        Err(e) => {
+            eprintln!("Installation failed: {}", e);
+            Err(EXIT_INSTALL_FAILED)
+        }
+    }
+}
+
+fn build_form_data(
+    template: &ConfigTemplate,
+    env: Option<&EnvArgs>,
+    placeholders: &[(String, String)],
+) -> Result<FormData, u8> {
+    let mut form_data = FormData::new(template);
+    if let Some(env_file) = EnvFile::load(&generate::env_path()) {
+        form_data.load_env(&env_file);
+    }
+
+    for credential in &template.credentials {
+        if let Ok(value) = std::env::var(&credential.env_var) {
+            form_data.set_credential(&credential.env_var, &value);
+        }
+    }
+
+    if let Some(args) = env {
+        for (env_var, value) in &args.credentials {
+            if !form_data.set_credential(env_var, value) {
+                eprintln!(
+                    "{} is not used by the {} template. Expected: {}",
+                    env_var,
+                    template.key,
+                    credential_names(template)
+                );
+                return Err(EXIT_INVALID_INPUT);
+            }
+        }
+        if let Some(model) = &args.generation_model {
+            form_data.generation_model = model.clone();
+        }
+        if let Some(port) = args.host_port {
+            form_data.host_port = port.to_string();
+        }
+        if let Some(port) = args.ai_service_port {
+            form_data.ai_service_port = port.to_string();
+        }
+    }
+
+    for (name, value) in placeholders {
+        let name = name.trim_start_matches('<').trim_end_matches('>');
+        if !form_data.set_placeholder(name, value) {
+            eprintln!(
+                "<{}> is not a placeholder of the {} template",
+                name, template.key
+            );
+            return Err(EXIT_INVALID_INPUT);
+        }
+    }
+
+    Ok(form_data)
+}
+
+fn build_settings(template: &ConfigTemplate, args: &SettingsArgs) -> Result<Settings, u8> {
+    let mut settings = match &args.preset {
+        Some(name) => match SettingsPreset::parse(name) {
+            Some(preset) => preset.settings(),
+            None => {
+                eprintln!(
+                    "Unknown settings preset '{}'. Available: {}",
+                    name,
+                    settings::preset_names()
+                );
+                return Err(EXIT_INVALID_INPUT);
+            }
+        },
+        None => template.settings().clone(),
+    };
+
+    for (key, value) in &args.settings {
+        if let Err(e) = settings.set(key, value) {
+            eprintln!("Invalid --setting {}={}: {}", key, value, e);
+            return Err(EXIT_INVALID_INPUT);
+        }
+    }
+
+    Ok(settings)
+}
+
+fn generate_env(mut form_data: FormData, platform: Option<&str>, force: bool) -> Result<(), u8> {
+    if utils::find_file(".env") && !force {
+        println!("✓ .env already exists, keeping it (use --force to regenerate)");
+        return Ok(());
+    }
+
+    form_data.platform = match platform {
+        Some(platform) => platform.to_string(),
+        None => {
+            let choice = platform::select(&compose::load_services().unwrap_or_default());
+            for note in &choice.notes {
+                eprintln!("warning: {}", note);
+            }
+            choice.platform
+        }
+    };
+
+    if !form_data.validate() {
+        eprintln!("{}", form_data.error_message);
+        return Err(EXIT_INVALID_INPUT);
+    }
+
+    match generate::generate_env_file(&form_data) {
+        Ok((written, changes)) => {
+            println!("✓ Wrote {}", written.path.display());
+            for change in &changes {
+                println!("  {}", change);
+            }
+            report_backup(&written);
+            Ok(())
+        }
+        Err(e) => {
+            eprintln!("Failed to generate .env: {}", e);
+            Err(EXIT_WRITE_FAILED)
+        }
+    }
+}
+
+fn generate_config(
+    template: &ConfigTemplate,
+    form_data: &FormData,
+    settings: &Settings,
+    force: bool,
+) -> Result<(), u8> {
+    if utils::find_file("config.yaml") && !force {
+        println!("✓ config.yaml already exists, keeping it (use --force to regenerate)");
+        return Ok(());
+    }
+
+    let values = form_data.placeholder_pairs();
+    let missing = template.missing_placeholders(&values);
+    if !missing.is_empty() {
+        eprintln!(
+            "The {} template needs values for {}. Pass them with --set NAME=VALUE",
+            template.key,
+            templates::format_placeholders(&missing)
+        );
+        return Err(EXIT_INVALID_INPUT);
+    }
+
+    let pipes = template.pipes();
+    for warning in template.embedding_warnings(&values, &pipes) {
+        eprintln!("warning: {}", warning);
+    }
+
+    match generate::write_config_yaml(template, &values, &pipes, settings) {
+        Ok(written) => {
+            println!(
+                "✓ Wrote {} using the {} template",
+                written.path.display(),
+                template.name
+            );
+            report_backup(&written);
+            Ok(())
+        }
+        Err(e) => {
+            eprintln!("Failed to generate config.yaml: {}", e);
+            Err(EXIT_WRITE_FAILED)
+        }
+    }
+}
+
+fn generate_compose(args: GenerateComposeArgs) -> Result<(), u8> {
+    if generate::compose_path().exists() && !args.force {
+        println!("✓ docker-compose.yaml already exists, keeping it (use --force to regenerate)");
+        return Ok(());
+    }
+    if args.without_ibis
+        && fs::read_to_string(validate::config_path())
+            .is_ok_and(|config| config.contains("ibis-server"))
+    {
+        eprintln!(
+            "⚠ config.yaml still points the analytics_ibis engine at http://ibis-server:8000, \
+             which --without-ibis leaves out; update its endpoint or drop the flag"
+        );
+    }
+
+    let options = StackOptions {
+        include_northwind: !args.without_northwind,
+        include_ibis: !args.without_ibis,
+        qdrant_version: args.qdrant_version,
+        platform: args.platform,
+    };
+    match generate::write_compose_file(&options) {
+        Ok(written) => {
+            println!("✓ Wrote {}", written.path.display());
+            report_backup(&written);
+            Ok(())
+        }
+        Err(e) => {
+            eprintln!("Failed to generate docker-compose.yaml: {}", e);
+            Err(EXIT_WRITE_FAILED)
+        }
+    }
+}
+
+fn print_checks(checks: &[DoctorCheck]) {
+    for check in checks {
+        println!(
+            "{} {:<18} {}",
+            check.status.symbol(),
+            check.name,
+            check.detail
+        );
+        if let Some(hint) = &check.hint {
+            println!("  {:<18} fix: {}", "", hint);
+        }
+    }
+}
+
+fn report_backup(written: &Written) {
+    if let Some(backup) = &written.backup {
+        println!("  previous version saved to {}", backup.display());
+    }
+}
+
+fn restore(name: Option<&str>) -> Result<(), u8> {
+    let Some(name) = name else {
+        return list_backups();
+    };
+
+    let known = backup::list()
+        .unwrap_or_default()
+        .iter()
+        .any(|backup| backup.name == name || backup.file == name);
+    if !known {
+        eprintln!(
+            "No backup named {}. Run `installer-analytics restore` to list them",
+            name
+        );
+        return Err(EXIT_INVALID_INPUT);
+    }
+
+    match backup::restore(name) {
+        Ok(written) => {
+            println!("✓ Restored {} from {}", written.path.display(), name);
+            report_backup(&written);
+            Ok(())
+        }
+        Err(e) => {
+            eprintln!("Failed to restore {}: {}", name, e);
+            Err(EXIT_WRITE_FAILED)
+        }
+    }
+}
+
+fn list_backups() -> Result<(), u8> {
+    let backups = match backup::list() {
+        Ok(backups) => backups,
+        Err(e) => {
+            eprintln!("{}", e);
+            return Err(EXIT_INVALID_INPUT);
+        }
+    };
+
+    if backups.is_empty() {
+        println!("No backups in {}", backup::backups_dir().display());
+    }
+    for backup in &backups {
+        println!("{:<12} {}", backup.file, backup.name);
+    }
+    Ok(())
+}
+
+fn check_readiness(template: &ConfigTemplate) -> Result<(), u8> {
+    let readiness = readiness::load(Some(template));
+    if readiness.is_ready() {
+        return Ok(());
+    }
+
+    eprintln!("✗ .env or config.yaml is not ready:");
+    for problem in readiness.problems() {
+        eprintln!("  - {}", problem);
+    }
+    Err(EXIT_INVALID_INPUT)
+}
+
+fn validate_config(path: &Path) -> Result<(), u8> {
+    let issues = match validate::validate_file(path) {
+        Ok(issues) => issues,
+        Err(e) => {
+            eprintln!("Failed to read {}: {}", path.display(), e);
+            return Err(EXIT_INVALID_INPUT);
+        }
+    };
+
+    if issues.is_empty() {
+        println!("✓ {} is valid", path.display());
+        return Ok(());
+    }
+
+    eprintln!("✗ {} has {} problem(s):", path.display(), issues.len());
+    for issue in &issues {
+        eprintln!("  - {}", issue);
+    }
+    Err(EXIT_INVALID_INPUT)
+}
+
+fn list_templates(catalog: &TemplateCatalog) {
+    for template in &catalog.templates {
+        let source = match template.source.path() {
+            Some(path) => format!("{} ({})", template.source.label(), path.display()),
+            None => template.source.label().to_string(),
+        };
+        println!("{:<18} {:<30} {}", template.key, template.name, source);
+    }
+}
+
+fn find_template<'a>(
+    catalog: &'a TemplateCatalog,
+    args: &ProviderArgs,
+) -> Result<&'a ConfigTemplate, u8> {
+    catalog.find(&args.provider).ok_or_else(|| {
+        let keys: Vec<&str> = catalog
+            .templates
+            .iter()
+            .map(|template| template.key.as_str())
+            .collect();
+        eprintln!(
+            "Unknown provider '{}'. Available: {}",
+            args.provider,
+            keys.join(", ")
+        );
+        EXIT_INVALID_INPUT
+    })
+}
+
+fn credential_names(template: &ConfigTemplate) -> String {
+    if template.credentials.is_empty() {
+        return "none".to_string();
+    }
+
+    template
+        .credentials
+        .iter()
+        .map(|credential| credential.env_var.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn parse_runtime(value: &str) -> Result<RuntimeKind, String> {
+    RuntimeKind::parse(value).ok_or_else(|| format!("expected one of {}", runtime::runtime_names()))
+}
+
+fn parse_platform(value: &str) -> Result<String, String> {
+    let parts: Vec<&str> = value.trim().split('/').collect();
+    if (2..=3).contains(&parts.len()) && parts.iter().all(|part| !part.is_empty()) {
+        Ok(value.trim().to_string())
+    } else {
+        Err("expected OS/ARCH, e.g. linux/amd64 or linux/arm64".to_string())
+    }
+}
+
+fn parse_assignment(value: &str) -> Result<(String, String), String> {
+    match value.split_once('=') {
+        Some((name, assigned)) if !name.trim().is_empty() => {
+            Ok((name.trim().to_string(), assigned.to_string()))
+        }
+        _ => Err("expected NAME=VALUE".to_string()),
+    }
+}
@@ -0,0 +1,418 @@
+use color_eyre::{Result, eyre::eyre};
+use serde::Deserialize;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::backup;
+use crate::env_file::EnvFile;
+use crate::generate;
+use crate::runtime::ContainerRuntime;
+use crate::stack::{Stack, StackOptions};
+use crate::utils;
+
+pub const COMPOSE_FILE: &str = "docker-compose.yaml";
+pub const OVERRIDE_FILE: &str = "docker-compose.override.yaml";
+pub const PORTS_FILE: &str = "docker-compose.ports.yaml";
+
+const PORT_BINDINGS: &[(&str, &str, &str)] = &[
+    ("analytics-ui", "HOST_PORT", "3000"),
+    (
+        "analytics-service",
+        "AI_SERVICE_FORWARD_PORT",
+        "${ANALYTICS_AI_SERVICE_PORT}",
+    ),
+    ("northwind-db", "NORTHWIND_DB_PORT", "5432"),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComposeService {
+    pub name: String,
+    pub image: Option<String>,
+    pub platform: Option<String>,
+    pub ports: Vec<String>,
+    pub depends_on: Vec<String>,
+    pub healthcheck: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct ComposeFile {
+    #[serde(default)]
+    services: serde_yaml::Mapping,
+}
+
+#[derive(Debug, Deserialize)]
+struct ServiceDefinition {
+    image: Option<String>,
+    platform: Option<String>,
+    #[serde(default)]
+    ports: Vec<PortEntry>,
+    #[serde(default)]
+    depends_on: serde_yaml::Value,
+    healthcheck: Option<serde_yaml::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum PortEntry {
+    Short(String),
+    Number(u16),
+    Long {
+        target: serde_yaml::Value,
+        #[serde(default)]
+        published: Option<serde_yaml::Value>,
+        #[serde(default)]
+        host_ip: Option<String>,
+        #[serde(default)]
+        protocol: Option<String>,
+    },
+}
+
+impl PortEntry {
+    pub fn spec(self) -> String {
+        match self {
+            PortEntry::Short(spec) => spec,
+            PortEntry::Number(port) => port.to_string(),
+            PortEntry::Long {
+                target,
+                published,
+                host_ip,
+                protocol,
+            } => {
+                let mut spec = String::new();
+                if let Some(host_ip) = host_ip.filter(|ip| !ip.is_empty()) {
+                    if host_ip.contains(':') {
+                        spec.push_str(&format!("[{}]:", host_ip));
+                    } else {
+                        spec.push_str(&format!("{}:", host_ip));
+                    }
+                }
+                if let Some(published) = published.as_ref().and_then(scalar) {
+                    spec.push_str(&format!("{}:", published));
+                }
+                spec.push_str(&scalar(&target).unwrap_or_default());
+                if let Some(protocol) = protocol.filter(|protocol| protocol != "tcp") {
+                    spec.push_str(&format!("/{}", protocol));
+                }
+                spec
+            }
+        }
+    }
+}
+
+fn scalar(value: &serde_yaml::Value) -> Option<String> {
+    match value {
+        serde_yaml::Value::String(text) if !text.is_empty() => Some(text.clone()),
+        serde_yaml::Value::Number(number) => Some(number.to_string()),
+        _ => None,
+    }
+}
+
+fn port_specs(value: serde_yaml::Value) -> Result<Vec<String>> {
+    let entries: Vec<PortEntry> = serde_yaml::from_value(value)?;
+    Ok(entries.into_iter().map(PortEntry::spec).collect())
+}
+
+pub fn load_services() -> Result<Vec<ComposeService>> {
+    let root = utils::project_root();
+    let content = fs::read_to_string(root.join(COMPOSE_FILE))?;
+    let mut services = parse_services(&content)?;
+    let ports = ports_override(&services);
+
+    if let Ok(content) = fs::read_to_string(root.join(OVERRIDE_FILE)) {
+        apply_override(&mut services, &content)?;
+    }
+    if let Some(ports) = ports {
+        apply_override(&mut services, &ports)?;
+    }
+    Ok(services)
+}
+
+pub fn service_names() -> Vec<String> {
+    match load_services() {
+        Ok(services) => services.into_iter().map(|service| service.name).collect(),
+        Err(_) => Stack::new(&StackOptions::default())
+            .services
+            .iter()
+            .map(|service| service.name.to_string())
+            .collect(),
+    }
+}
+
+pub fn container_service<'a>(
+    services: &'a [String],
+    project: &str,
+    container: &str,
+) -> Option<&'a str> {
+    let container = container.trim_start_matches('/');
+    for separator in ['-', '_'] {
+        if let Some(rest) = container.strip_prefix(&format!("{}{}", project, separator))
+            && let Some((service, index)) = rest.rsplit_once(separator)
+            && index.chars().all(|c| c.is_ascii_digit())
+            && let Some(found) = services.iter().find(|name| *name == service)
+        {
+            return Some(found);
+        }
+    }
+
+    services
+        .iter()
+        .filter(|name| container.contains(name.as_str()))
+        .max_by_key(|name| name.len())
+        .map(String::as_str)
+}
+
+pub fn file_args() -> Vec<String> {
+    let root = utils::project_root();
+    let mut args = vec!["-f".to_string(), COMPOSE_FILE.to_string()];
+    if root.join(OVERRIDE_FILE).exists() {
+        args.extend(["-f".to_string(), OVERRIDE_FILE.to_string()]);
+    }
+    if root.join(PORTS_FILE).exists() {
+        args.extend(["-f".to_string(), PORTS_FILE.to_string()]);
+    }
+    args
+}
+
+pub fn write_ports_override(runtime: &dyn ContainerRuntime) -> Result<Option<PathBuf>> {
+    let root = utils::project_root();
+    let path = root.join(COMPOSE_FILE);
+    let content =
+        fs::read_to_string(&path).map_err(|e| eyre!("failed to read {}: {}", path.display(), e))?;
+    let services = parse_services(&content)?;
+
+    let path = root.join(PORTS_FILE);
+    let Some(content) = ports_override(&services) else {
+        if path.exists() {
+            fs::remove_file(&path)
+                .map_err(|e| eyre!("failed to remove {}: {}", path.display(), e))?;
+        }
+        return Ok(None);
+    };
+    if !runtime.supports_override_tag() {
+        return Err(eyre!(
+            "{} hardcodes the published ports and remapping them to .env needs an override \
+             file, but {}. Regenerate it with `installer-analytics generate-compose --force`",
+            COMPOSE_FILE,
+            runtime.override_tag_hint()
+        ));
+    }
+    backup::write_atomic(&path, &content)?;
+    Ok(Some(path))
+}
+
+pub fn ports_override(services: &[ComposeService]) -> Option<String> {
+    let bindings: Vec<_> = PORT_BINDINGS
+        .iter()
+        .filter(|(name, _, _)| {
+            services.iter().any(|service| {
+                service.name == *name && service.ports.iter().any(|port| !port.starts_with("${"))
+            })
+        })
+        .collect();
+    if bindings.is_empty() {
+        return None;
+    }
+
+    let mut content = format!(
+        "# Generated by installer-analytics from {}, do not edit.\n\
+         # Publishes the host ports configured in .env.\n",
+        COMPOSE_FILE
+    );
+    content.push_str("services:\n");
+    for (name, env_var, container_port) in bindings {
+        content.push_str(&format!(
+            "  {}:\n    ports: !override\n      - \"${{{}}}:{}\"\n",
+            name, env_var, container_port
+        ));
+    }
+    Some(content)
+}
+
+fn apply_override(services: &mut Vec<ComposeService>, content: &str) -> Result<()> {
+    let compose: ComposeFile = serde_yaml::from_str(content)?;
+
+    for (name, definition) in compose.services {
+        let Some(name) = name.as_str() else {
+            continue;
+        };
+        let (ports, replace) = match definition.get("ports") {
+            Some(serde_yaml::Value::Tagged(tagged)) => (port_specs(tagged.value.clone())?, true),
+            Some(ports) => (port_specs(ports.clone())?, false),
+            None => (Vec::new(), false),
+        };
+        let image = definition
+            .get("image")
+            .and_then(|image| image.as_str())
+            .map(|image| image.to_string());
+        let platform = definition
+            .get("platform")
+            .and_then(|platform| platform.as_str())
+            .map(|platform| platform.to_string());
+
+        match services.iter_mut().find(|service| service.name == name) {
+            Some(service) => {
+                if replace {
+                    service.ports = ports;
+                } else {
+                    for port in ports {
+                        if !service.ports.contains(&port) {
+                            service.ports.push(port);
+                        }
+                    }
+                }
+                if image.is_some() {
+                    service.image = image;
+                }
+                if platform.is_some() {
+                    service.platform = platform;
+                }
+                if definition.get("healthcheck").is_some() {
+                    service.healthcheck = has_healthcheck(definition.get("healthcheck"));
+                }
+            }
+            None => services.push(ComposeService {
+                name: name.to_string(),
+                image,
+                platform,
+                ports,
+                depends_on: dependencies(definition.get("depends_on")),
+                healthcheck: has_healthcheck(definition.get("healthcheck")),
+            }),
+        }
+    }
+
+    Ok(())
+}
+
+pub fn parse_services(content: &str) -> Result<Vec<ComposeService>> {
+    let compose: ComposeFile = serde_yaml::from_str(content)?;
+    let mut services = Vec::new();
+
+    for (name, definition) in compose.services {
+        let Some(name) = name.as_str() else {
+            continue;
+        };
+        let definition: ServiceDefinition = serde_yaml::from_value(definition)?;
+        services.push(ComposeService {
+            name: name.to_string(),
+            image: definition.image,
+            platform: definition.platform,
+            ports: definition.ports.into_iter().map(PortEntry::spec).collect(),
+            depends_on: dependencies(Some(&definition.depends_on)),
+            healthcheck: has_healthcheck(definition.healthcheck.as_ref()),
+        });
+    }
+
+    Ok(services)
+}
+
+pub fn project_name() -> String {
+    let env = EnvFile::load(&generate::env_path());
+    if let Some(name) = env
+        .as_ref()
+        .and_then(|env| env.get("COMPOSE_PROJECT_NAME"))
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+    {
+        return name.to_string();
+    }
+
+    utils::project_root()
+        .file_name()
+        .map(|name| name.to_string_lossy().to_lowercase())
+        .unwrap_or_else(|| "default".to_string())
+}
+
+fn has_healthcheck(value: Option<&serde_yaml::Value>) -> bool {
+    value.is_some_and(|healthcheck| {
+        healthcheck
+            .get("disable")
+            .and_then(|disable| disable.as_bool())
+            != Some(true)
+    })
+}
+
+pub fn dependencies(value: Option<&serde_yaml::Value>) -> Vec<String> {
+    match value {
+        Some(serde_yaml::Value::Sequence(names)) => names
+            .iter()
+            .filter_map(|name| name.as_str().map(str::to_string))
+            .collect(),
+        Some(serde_yaml::Value::Mapping(names)) => names
+            .keys()
+            .filter_map(|name| name.as_str().map(str::to_string))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ports_override_only_for_hardcoded_ports() {
+        let generated = Stack::new(&StackOptions::default()).render().unwrap();
+        assert_eq!(ports_override(&parse_services(&generated).unwrap()), None);
+
+        let legacy = "services:\n  analytics-ui:\n    ports:\n      - \"3000:3000\"\n  \
+                      northwind-db:\n    ports:\n      - \"${NORTHWIND_DB_PORT:-5432}:5432\"\n";
+        let content = ports_override(&parse_services(legacy).unwrap()).unwrap();
+        assert!(
+            content
+                .contains("  analytics-ui:\n    ports: !override\n      - \"${HOST_PORT}:3000\"\n")
+        );
+        assert!(!content.contains("northwind-db"));
+
+        let mut services = parse_services(legacy).unwrap();
+        apply_override(&mut services, &content).unwrap();
+        assert_eq!(services[0].ports, ["${HOST_PORT}:3000"]);
+    }
+
+    #[test]
+    fn port_syntaxes() {
+        let cases = [
+            ("short", "- \"3000:3000\"", "3000:3000"),
+            ("bare number", "- 6333", "6333"),
+            ("long", "- target: 3000\n  published: 8080", "8080:3000"),
+            (
+                "long with a variable",
+                "- target: 5432\n  published: ${NORTHWIND_DB_PORT:-5432}",
+                "${NORTHWIND_DB_PORT:-5432}:5432",
+            ),
+            (
+                "long with a string port and udp",
+                "- target: 53\n  published: \"5353\"\n  protocol: udp",
+                "5353:53/udp",
+            ),
+            (
+                "long with a host ip",
+                "- target: 3000\n  published: 3000\n  host_ip: 127.0.0.1\n  protocol: tcp",
+                "127.0.0.1:3000:3000",
+            ),
+            (
+                "long with an IPv6 host ip",
+                "- target: 3000\n  published: 3000\n  host_ip: \"::1\"",
+                "[::1]:3000:3000",
+            ),
+            ("long without a published port", "- target: 6334", "6334"),
+        ];
+
+        for (name, ports, expected) in cases {
+            let content = format!(
+                "services:\n  analytics-ui:\n    ports:\n{}\n",
+                ports
+                    .lines()
+                    .map(|line| format!("      {}", line))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            );
+            let services = parse_services(&content).unwrap();
+            assert_eq!(services[0].ports, [expected], "{}", name);
+
+            let mut overridden = parse_services("services:\n  analytics-ui: {}\n").unwrap();
+            apply_override(&mut overridden, &content).unwrap();
+            assert_eq!(overridden[0].ports, [expected], "{} in an override", name);
+        }
+    }
+}
@@ -0,0 +1,127 @@
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DiffKind {
+    Hunk,
+    Context,
+    Added,
+    Removed,
+}
+
+#[derive(Debug, Clone)]
+pub struct DiffLine {
+    pub kind: DiffKind,
+    pub text: String,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Edit {
+    Equal(usize, usize),
+    Delete(usize),
+    Insert(usize),
+}
+
+pub fn unified(old: &str, new: &str, context: usize) -> Vec<DiffLine> {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let edits = edit_script(&old_lines, &new_lines);
+
+    let changed: Vec<usize> = edits
+        .iter()
+        .enumerate()
+        .filter(|(_, edit)| !matches!(edit, Edit::Equal(..)))
+        .map(|(index, _)| index)
+        .collect();
+
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for index in changed {
+        let start = index.saturating_sub(context);
+        let end = (index + context + 1).min(edits.len());
+        match ranges.last_mut() {
+            Some((_, last_end)) if start <= *last_end => *last_end = end,
+            _ => ranges.push((start, end)),
+        }
+    }
+
+    let mut lines = Vec::new();
+    for (start, end) in ranges {
+        let hunk = &edits[start..end];
+        let (old_start, new_start) = positions(&edits[..start]);
+        let old_count = hunk
+            .iter()
+            .filter(|edit| !matches!(edit, Edit::Insert(_)))
+            .count();
+        let new_count = hunk
+            .iter()
+            .filter(|edit| !matches!(edit, Edit::Delete(_)))
+            .count();
+
+        lines.push(DiffLine {
+            kind: DiffKind::Hunk,
+            text: format!(
+                "@@ -{},{} +{},{} @@",
+                old_start + 1,
+                old_count,
+                new_start + 1,
+                new_count
+            ),
+        });
+        for edit in hunk {
+            lines.push(match *edit {
+                Edit::Equal(index, _) => DiffLine {
+                    kind: DiffKind::Context,
+                    text: format!(" {}", old_lines[index]),
+                },
+                Edit::Delete(index) => DiffLine {
+                    kind: DiffKind::Removed,
+                    text: format!("-{}", old_lines[index]),
+                },
+                Edit::Insert(index) => DiffLine {
+                    kind: DiffKind::Added,
+                    text: format!("+{}", new_lines[index]),
+                },
+            });
+        }
+    }
+
+    lines
+}
+
+fn positions(edits: &[Edit]) -> (usize, usize) {
+    edits.iter().fold((0, 0), |(old, new), edit| match edit {
+        Edit::Equal(..) => (old + 1, new + 1),
+        Edit::Delete(_) => (old + 1, new),
+        Edit::Insert(_) => (old, new + 1),
+    })
+}
+
+fn edit_script(old: &[&str], new: &[&str]) -> Vec<Edit> {
+    let width = new.len() + 1;
+    let mut lengths = vec![0u32; (old.len() + 1) * width];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i * width + j] = if old[i] == new[j] {
+                lengths[(i + 1) * width + j + 1] + 1
+            } else {
+                lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut edits = Vec::with_capacity(old.len() + new.len());
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            edits.push(Edit::Equal(i, j));
+            i += 1;
+            j += 1;
+        } else if lengths[(i + 1) * width + j] >= lengths[i * width + j + 1] {
+            edits.push(Edit::Delete(i));
+            i += 1;
+        } else {
+            edits.push(Edit::Insert(j));
+            j += 1;
+        }
+    }
+    edits.extend((i..old.len()).map(Edit::Delete));
+    edits.extend((j..new.len()).map(Edit::Insert));
+    edits
+}
@@ -0,0 +1,622 @@
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::compose;
+#[cfg(unix)]
+use crate::engine;
+use crate::env_file::EnvFile;
+use crate::generate;
+use crate::installer::InstallOptions;
+use crate::platform;
+use crate::ports;
+use crate::runtime::{self, RuntimeKind};
+use crate::utils;
+
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(10);
+const GIB: u64 = 1024 * 1024 * 1024;
+const MIN_DISK_BYTES: u64 = 5 * GIB;
+const RECOMMENDED_DISK_BYTES: u64 = 20 * GIB;
+const MIN_MEMORY_BYTES: u64 = 2 * GIB;
+const RECOMMENDED_MEMORY_BYTES: u64 = 4 * GIB;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+impl CheckStatus {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            CheckStatus::Pass => "✓",
+            CheckStatus::Warn => "!",
+            CheckStatus::Fail => "✗",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DoctorCheck {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+    pub hint: Option<String>,
+}
+
+impl DoctorCheck {
+    fn pass(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Pass,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+
+    fn warn(name: &'static str, detail: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Warn,
+            detail: detail.into(),
+            hint: Some(hint.into()),
+        }
+    }
+
+    fn fail(name: &'static str, detail: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Fail,
+            detail: detail.into(),
+            hint: Some(hint.into()),
+        }
+    }
+}
+
+pub fn has_failures(checks: &[DoctorCheck]) -> bool {
+    checks.iter().any(|check| check.status == CheckStatus::Fail)
+}
+
+pub fn run(options: InstallOptions) -> Vec<DoctorCheck> {
+    let (compose, detected) = check_compose(options.runtime);
+    let podman = detected.or(options.runtime).map(|kind| kind.engine()) == Some("podman");
+    let engine = if podman {
+        check_podman()
+    } else {
+        check_docker()
+    };
+    let engine_found = engine.status == CheckStatus::Pass;
+    let mut checks = vec![engine];
+
+    if engine_found {
+        checks.push(compose);
+        checks.push(if podman {
+            check_podman_service()
+        } else {
+            check_daemon()
+        });
+        if options.engine_api {
+            checks.push(check_engine_api());
+        }
+        checks.push(check_platforms());
+    }
+    if cfg!(target_os = "linux") && !podman {
+        checks.push(check_docker_group());
+    }
+    checks.push(check_disk());
+    checks.push(check_memory(podman));
+    checks.push(check_data_dir());
+    checks.push(check_ports());
+    checks
+}
+
+fn check_docker() -> DoctorCheck {
+    const NAME: &str = "docker binary";
+    match utils::run_command("docker", &["--version"], COMMAND_TIMEOUT) {
+        Some(output) if output.success => DoctorCheck::pass(NAME, output.stdout.trim()),
+        Some(output) => DoctorCheck::fail(
+            NAME,
+            format!("docker --version failed: {}", output.stderr.trim()),
+            "Reinstall Docker from https://docs.docker.com/engine/install/",
+        ),
+        None => DoctorCheck::fail(
+            NAME,
+            "docker was not found on PATH",
+            "Install Docker from https://docs.docker.com/engine/install/",
+        ),
+    }
+}
+
+fn check_compose(preference: Option<RuntimeKind>) -> (DoctorCheck, Option<RuntimeKind>) {
+    const NAME: &str = "compose runtime";
+    match runtime::detect(preference) {
+        Ok((runtime, version)) => (
+            DoctorCheck::pass(
+                NAME,
+                format!("{} {}", runtime.command_line(&[], &[]), version),
+            ),
+            Some(runtime.kind()),
+        ),
+        Err(e) => (
+            DoctorCheck::fail(
+                NAME,
+                e.to_string(),
+                preference.unwrap_or(RuntimeKind::Docker).install_hint(),
+            ),
+            None,
+        ),
+    }
+}
+
+#[cfg(unix)]
+fn check_engine_api() -> DoctorCheck {
+    const NAME: &str = "engine API";
+    let socket = match engine::socket_path() {
+        Ok(socket) => socket,
+        Err(e) => {
+            return DoctorCheck::fail(
+                NAME,
+                e.to_string(),
+                "Set DOCKER_HOST to a unix:// socket, or drop --engine-api",
+            );
+        }
+    };
+    match engine::ping(&socket) {
+        Ok(()) => DoctorCheck::pass(NAME, format!("{} answers", socket.display())),
+        Err(e) => DoctorCheck::fail(
+            NAME,
+            e.to_string(),
+            "Point DOCKER_HOST at the engine socket, e.g. unix:///run/user/1000/podman/podman.sock, or drop --engine-api",
+        ),
+    }
+}
+
+#[cfg(not(unix))]
+fn check_engine_api() -> DoctorCheck {
+    DoctorCheck::fail(
+        "engine API",
+        crate::installer::ENGINE_API_UNSUPPORTED,
+        "Drop --engine-api",
+    )
+}
+
+fn check_podman() -> DoctorCheck {
+    const NAME: &str = "podman binary";
+    match utils::run_command("podman", &["--version"], COMMAND_TIMEOUT) {
+        Some(output) if output.success => DoctorCheck::pass(NAME, output.stdout.trim()),
+        _ => DoctorCheck::fail(
+            NAME,
+            "podman was not found on PATH",
+            "Install Podman from https://podman.io/docs/installation",
+        ),
+    }
+}
+
+fn check_podman_service() -> DoctorCheck {
+    const NAME: &str = "podman service";
+    match utils::run_command(
+        "podman",
+        &["info", "--format", "{{.Version.Version}}"],
+        COMMAND_TIMEOUT,
+    ) {
+        Some(output) if output.success => {
+            DoctorCheck::pass(NAME, format!("Podman {}", output.stdout.trim()))
+        }
+        Some(output) => DoctorCheck::fail(
+            NAME,
+            first_line(&output.stderr, "podman info failed"),
+            "Start the Podman machine with `podman machine start`, or check `podman system service`",
+        ),
+        None => DoctorCheck::fail(
+            NAME,
+            format!(
+                "podman info did not answer within {}s",
+                COMMAND_TIMEOUT.as_secs()
+            ),
+            "Check that Podman is responsive",
+        ),
+    }
+}
+
+fn check_daemon() -> DoctorCheck {
+    const NAME: &str = "docker daemon";
+    match utils::run_command(
+        "docker",
+        &["info", "--format", "{{.ServerVersion}}"],
+        COMMAND_TIMEOUT,
+    ) {
+        Some(output) if output.success => {
+            DoctorCheck::pass(NAME, format!("Docker Engine {}", output.stdout.trim()))
+        }
+        Some(output) if output.stderr.to_lowercase().contains("permission denied") => {
+            DoctorCheck::fail(
+                NAME,
+                "permission denied on the Docker socket",
+                "Add your user to the docker group: `sudo usermod -aG docker $USER`, then log in again",
+            )
+        }
+        Some(output) => DoctorCheck::fail(
+            NAME,
+            first_line(&output.stderr, "the daemon is not reachable"),
+            "Start Docker: `sudo systemctl start docker`, or open Docker Desktop",
+        ),
+        None => DoctorCheck::fail(
+            NAME,
+            format!(
+                "docker info did not answer within {}s",
+                COMMAND_TIMEOUT.as_secs()
+            ),
+            "Check that the Docker daemon is running and responsive",
+        ),
+    }
+}
+
+fn check_docker_group() -> DoctorCheck {
+    const NAME: &str = "docker group";
+    let Some(user) =
+        utils::run_command("id", &["-un"], COMMAND_TIMEOUT).filter(|output| output.success)
+    else {
+        return DoctorCheck::warn(
+            NAME,
+            "could not determine the current user",
+            "Make sure your user may access /var/run/docker.sock",
+        );
+    };
+    let user = user.stdout.trim().to_string();
+    if user == "root" {
+        return DoctorCheck::pass(NAME, "running as root");
+    }
+
+    let groups = utils::run_command("id", &["-Gn"], COMMAND_TIMEOUT)
+        .map(|output| output.stdout)
+        .unwrap_or_default();
+    if groups.split_whitespace().any(|group| group == "docker") {
+        DoctorCheck::pass(NAME, format!("{} is in the docker group", user))
+    } else {
+        DoctorCheck::warn(
+            NAME,
+            format!("{} is not in the docker group", user),
+            "Run `sudo usermod -aG docker $USER` and log in again, unless you use rootless Docker",
+        )
+    }
+}
+
+fn check_disk() -> DoctorCheck {
+    const NAME: &str = "disk space";
+    let root = utils::project_root();
+    let available = utils::run_command("df", &["-Pk", &root.to_string_lossy()], COMMAND_TIMEOUT)
+        .filter(|output| output.success)
+        .and_then(|output| parse_df(&output.stdout));
+
+    match available {
+        Some(bytes) if bytes < MIN_DISK_BYTES => DoctorCheck::fail(
+            NAME,
+            format!("{} free in {}", format_bytes(bytes), root.display()),
+            "Free up space, the images and the Qdrant data need at least 5 GiB",
+        ),
+        Some(bytes) if bytes < RECOMMENDED_DISK_BYTES => DoctorCheck::warn(
+            NAME,
+            format!("{} free in {}", format_bytes(bytes), root.display()),
+            "20 GiB or more is recommended, try `docker system prune`",
+        ),
+        Some(bytes) => DoctorCheck::pass(NAME, format!("{} free", format_bytes(bytes))),
+        None => DoctorCheck::warn(
+            NAME,
+            "could not read the free disk space",
+            "Make sure at least 20 GiB is free",
+        ),
+    }
+}
+
+fn check_memory(podman: bool) -> DoctorCheck {
+    const NAME: &str = "memory";
+    let (program, format, engine) = if podman {
+        ("podman", "{{.Host.MemTotal}}", "Podman")
+    } else {
+        ("docker", "{{.MemTotal}}", "Docker")
+    };
+    let engine_total = utils::run_command(program, &["info", "--format", format], COMMAND_TIMEOUT)
+        .filter(|output| output.success)
+        .and_then(|output| parse_bytes(&output.stdout));
+    let (total, scope) = match engine_total {
+        Some(bytes) => (Some(bytes), format!("available to {}", engine)),
+        None => (host_memory(), "total".to_string()),
+    };
+
+    match total {
+        Some(bytes) if bytes < MIN_MEMORY_BYTES => DoctorCheck::fail(
+            NAME,
+            format!("{} {}", format_bytes(bytes), scope),
+            "The stack needs at least 2 GiB of memory",
+        ),
+        Some(bytes) if bytes < RECOMMENDED_MEMORY_BYTES => DoctorCheck::warn(
+            NAME,
+            format!("{} {}", format_bytes(bytes), scope),
+            "4 GiB or more is recommended, close other containers or raise the Docker Desktop limit",
+        ),
+        Some(bytes) => DoctorCheck::pass(NAME, format!("{} {}", format_bytes(bytes), scope)),
+        None => DoctorCheck::warn(
+            NAME,
+            "could not read the total memory",
+            "Make sure Docker may use at least 4 GiB",
+        ),
+    }
+}
+
+fn check_data_dir() -> DoctorCheck {
+    const NAME: &str = "./data directory";
+    let path = utils::project_root().join("data");
+    if !path.exists() {
+        return DoctorCheck::warn(
+            NAME,
+            format!("{} does not exist", path.display()),
+            "Create it with `mkdir data`, otherwise Docker creates it owned by root",
+        );
+    }
+    if !path.is_dir() {
+        return DoctorCheck::fail(
+            NAME,
+            format!("{} is not a directory", path.display()),
+            "Move the file away and create a data directory",
+        );
+    }
+    if is_writable(&path) {
+        DoctorCheck::pass(NAME, format!("{} is writable", path.display()))
+    } else {
+        DoctorCheck::fail(
+            NAME,
+            format!("{} is not writable", path.display()),
+            "Fix the owner, e.g. `sudo chown -R $USER data`",
+        )
+    }
+}
+
+fn check_ports() -> DoctorCheck {
+    const NAME: &str = "host ports";
+    let Ok(services) = compose::load_services() else {
+        return DoctorCheck::warn(
+            NAME,
+            "docker-compose.yaml could not be read",
+            "Run `installer-analytics generate-compose`",
+        );
+    };
+    let env = EnvFile::load(&generate::env_path()).unwrap_or_else(generate::default_env);
+    let published = ports::published_ports(&services, &env);
+    let conflicts = ports::find_conflicts(&published);
+    if conflicts.is_empty() {
+        return DoctorCheck::pass(NAME, format!("{} port(s) free", published.len()));
+    }
+
+    let busy: Vec<String> = conflicts
+        .iter()
+        .map(|conflict| {
+            format!(
+                "{} ({})",
+                conflict.published.port, conflict.published.service
+            )
+        })
+        .collect();
+    let suggestions: Vec<String> = conflicts
+        .iter()
+        .filter_map(|conflict| {
+            Some(format!(
+                "{}={}",
+                conflict.published.env_var.as_deref()?,
+                conflict.suggestion?
+            ))
+        })
+        .collect();
+    DoctorCheck::warn(
+        NAME,
+        format!("in use: {}", busy.join(", ")),
+        if suggestions.is_empty() {
+            "Stop whatever uses these ports, it may be a previous run of the stack".to_string()
+        } else {
+            format!(
+                "Stop whatever uses them or set {} in .env",
+                suggestions.join(", ")
+            )
+        },
+    )
+}
+
+fn check_platforms() -> DoctorCheck {
+    const NAME: &str = "image platforms";
+    let Ok(services) = compose::load_services() else {
+        return DoctorCheck::warn(
+            NAME,
+            "docker-compose.yaml could not be read",
+            "Run `installer-analytics generate-compose`",
+        );
+    };
+    let env = EnvFile::load(&generate::env_path()).unwrap_or_else(generate::default_env);
+    let images = platform::inspect(&services, &env);
+    let host = platform::host_platform();
+    let names = |images: &[&platform::ImagePlatform]| {
+        images
+            .iter()
+            .map(|image| image.service.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    let missing: Vec<_> = images
+        .iter()
+        .filter(|image| image.supports(&image.platform) == Some(false))
+        .collect();
+    if let Some(first) = missing.first() {
+        let published = first.published.as_deref().unwrap_or_default().join(", ");
+        return DoctorCheck::fail(
+            NAME,
+            format!("no {} image for {}", first.platform, names(&missing)),
+            format!(
+                "Set PLATFORM in .env, or the platform of {} in {}, to one of: {}",
+                first.service,
+                compose::OVERRIDE_FILE,
+                published
+            ),
+        );
+    }
+
+    let emulated: Vec<_> = images.iter().filter(|image| image.is_emulated()).collect();
+    if let Some(first) = emulated.first() {
+        let native = emulated
+            .iter()
+            .all(|image| image.supports(host) == Some(true));
+        return DoctorCheck::warn(
+            NAME,
+            format!(
+                "{} run as {} under emulation on {}",
+                names(&emulated),
+                first.platform,
+                host
+            ),
+            if native {
+                format!("Set PLATFORM={} in .env to run them natively", host)
+            } else {
+                "Emulation is slow, register QEMU with `docker run --privileged --rm tonistiigi/binfmt --install all`".to_string()
+            },
+        );
+    }
+
+    let unknown: Vec<_> = images
+        .iter()
+        .filter(|image| image.published.is_err())
+        .collect();
+    if let Some(first) = unknown.first() {
+        return DoctorCheck::warn(
+            NAME,
+            format!(
+                "could not check {}: {}",
+                names(&unknown),
+                first.published.as_ref().err().cloned().unwrap_or_default()
+            ),
+            "Check the network connection, and `docker login ghcr.io` if the images are private",
+        );
+    }
+
+    DoctorCheck::pass(
+        NAME,
+        format!("{} image(s) run natively as {}", images.len(), host),
+    )
+}
+
+fn is_writable(path: &Path) -> bool {
+    let probe = path.join(format!(".installer-doctor.{}", std::process::id()));
+    let writable = fs::write(&probe, b"").is_ok();
+    let _ = fs::remove_file(&probe);
+    writable
+}
+
+fn first_line(text: &str, fallback: &str) -> String {
+    text.lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or(fallback)
+        .to_string()
+}
+
+fn host_memory() -> Option<u64> {
+    if cfg!(target_os = "macos") {
+        utils::run_command("sysctl", &["-n", "hw.memsize"], COMMAND_TIMEOUT)
+            .filter(|output| output.success)
+            .and_then(|output| parse_bytes(&output.stdout))
+    } else {
+        fs::read_to_string("/proc/meminfo")
+            .ok()
+            .and_then(|meminfo| parse_meminfo(&meminfo))
+    }
+}
+
+fn parse_df(output: &str) -> Option<u64> {
+    let fields: Vec<&str> = output.lines().nth(1)?.split_whitespace().collect();
+    let capacity = fields.iter().position(|field| field.ends_with('%'))?;
+    let kilobytes: u64 = fields.get(capacity.checked_sub(1)?)?.parse().ok()?;
+    Some(kilobytes * 1024)
+}
+
+fn parse_meminfo(meminfo: &str) -> Option<u64> {
+    let value = meminfo
+        .lines()
+        .find_map(|line| line.strip_prefix("MemTotal:"))?;
+    let kilobytes: u64 = value.split_whitespace().next()?.parse().ok()?;
+    Some(kilobytes * 1024)
+}
+
+fn parse_bytes(output: &str) -> Option<u64> {
+    output.trim().parse().ok().filter(|bytes| *bytes > 0)
+}
+
+fn format_bytes(bytes: u64) -> String {
+    format!("{:.1} GiB", bytes as f64 / GIB as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn df_output_cases() {
+        let cases = [
+            (
+                "Filesystem     1024-blocks      Used Available Capacity Mounted on\n\
+                 /dev/nvme0n1p2   479151816 312477068 142266956      69% /\n",
+                Some(142_266_956 * 1024),
+            ),
+            (
+                "Filesystem    1024-blocks      Used Available Capacity  Mounted on\n\
+                 map auto_home           0         0         0   100%    /System/Volumes/Data/home\n",
+                Some(0),
+            ),
+            (
+                "Filesystem 1024-blocks Used Available Capacity Mounted on\n\
+                 //server/share 1000 400 600 40% /Volumes/My Share\n",
+                Some(600 * 1024),
+            ),
+            (
+                "Filesystem 1024-blocks Used Available Capacity Mounted on\n",
+                None,
+            ),
+            ("df: /missing: No such file or directory\n", None),
+            ("", None),
+        ];
+        for (output, expected) in cases {
+            assert_eq!(parse_df(output), expected, "{}", output);
+        }
+    }
+
+    #[test]
+    fn meminfo_cases() {
+        let cases = [
+            (
+                "MemTotal:       16318412 kB\nMemFree:         1234567 kB\n",
+                Some(16_318_412 * 1024),
+            ),
+            (
+                "MemFree:         1234567 kB\nMemTotal:        2048 kB\n",
+                Some(2048 * 1024),
+            ),
+            ("MemTotal:        unknown kB\n", None),
+            ("MemFree:         1234567 kB\n", None),
+            ("", None),
+        ];
+        for (meminfo, expected) in cases {
+            assert_eq!(parse_meminfo(meminfo), expected, "{}", meminfo);
+        }
+    }
+
+    #[test]
+    fn byte_count_cases() {
+        let cases = [
+            ("8233476096\n", Some(8_233_476_096)),
+            ("17179869184", Some(17_179_869_184)),
+            ("0\n", None),
+            ("<no value>\n", None),
+            ("", None),
+        ];
+        for (output, expected) in cases {
+            assert_eq!(parse_bytes(output), expected, "{}", output);
+        }
+    }
+}
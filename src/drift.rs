@@ -0,0 +1,183 @@
+use color_eyre::Result;
+use std::fs;
+
+use crate::backup::{self, Written};
+use crate::compose;
+use crate::env_file::EnvFile;
+use crate::generate;
+use crate::templates::TemplateCatalog;
+use crate::utils;
+
+#[derive(Debug, Clone, Default)]
+pub struct EnvDrift {
+    pub missing: Vec<(String, String)>,
+    pub obsolete: Vec<String>,
+    pub unset: Vec<String>,
+}
+
+impl EnvDrift {
+    pub fn summary(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        if !self.missing.is_empty() {
+            let keys: Vec<&str> = self.missing.iter().map(|(key, _)| key.as_str()).collect();
+            lines.push(format!(
+                "{} key(s) missing from .env: {}",
+                keys.len(),
+                keys.join(", ")
+            ));
+        }
+        if !self.obsolete.is_empty() {
+            lines.push(format!(
+                "{} key(s) no longer in env_template: {}",
+                self.obsolete.len(),
+                self.obsolete.join(", ")
+            ));
+        }
+        if !self.unset.is_empty() {
+            lines.push(format!(
+                "{} variable(s) used by {} without a default and not set in .env: {}",
+                self.unset.len(),
+                compose::COMPOSE_FILE,
+                self.unset.join(", ")
+            ));
+        }
+        lines
+    }
+}
+
+pub fn load(catalog: &TemplateCatalog) -> Option<EnvDrift> {
+    let env = EnvFile::load(&generate::env_path())?;
+    let root = utils::project_root();
+    let compose = [compose::COMPOSE_FILE, compose::OVERRIDE_FILE]
+        .iter()
+        .filter_map(|file| fs::read_to_string(root.join(file)).ok())
+        .collect::<Vec<_>>()
+        .join("\n");
+    Some(check(&env, catalog, &compose))
+}
+
+pub fn check(env: &EnvFile, catalog: &TemplateCatalog, compose: &str) -> EnvDrift {
+    let defaults = generate::default_env();
+    let credentials: Vec<&str> = catalog
+        .templates
+        .iter()
+        .flat_map(|template| &template.credentials)
+        .map(|credential| credential.env_var.as_str())
+        .collect();
+
+    let missing: Vec<(String, String)> = defaults
+        .keys()
+        .filter(|key| env.get(key).is_none())
+        .map(|key| {
+            (
+                key.to_string(),
+                defaults.get(key).unwrap_or_default().to_string(),
+            )
+        })
+        .collect();
+
+    let obsolete = env
+        .keys()
+        .filter(|key| defaults.get(key).is_none() && !credentials.contains(key))
+        .map(|key| key.to_string())
+        .collect();
+
+    let unset = required_variables(compose)
+        .into_iter()
+        .filter(|name| env.get(name).is_none() && std::env::var_os(name).is_none())
+        .filter(|name| !missing.iter().any(|(key, _)| key == name))
+        .collect();
+
+    EnvDrift {
+        missing,
+        obsolete,
+        unset,
+    }
+}
+
+fn required_variables(compose: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for line in compose.lines() {
+        if line.trim_start().starts_with('#') {
+            continue;
+        }
+        let mut rest = line;
+        while let Some(start) = rest.find("${") {
+            let escaped = rest[..start].ends_with('$');
+            rest = &rest[start + 2..];
+            let Some(end) = rest.find('}') else {
+                break;
+            };
+            let expression = &rest[..end];
+            rest = &rest[end + 1..];
+
+            let split = expression
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(expression.len());
+            let (name, operator) = expression.split_at(split);
+            let required =
+                operator.is_empty() || operator.starts_with('?') || operator.starts_with(":?");
+            if !escaped && required && !name.is_empty() && !names.iter().any(|n| n == name) {
+                names.push(name.to_string());
+            }
+        }
+    }
+    names
+}
+
+pub fn add_missing(drift: &EnvDrift) -> Result<Option<Written>> {
+    let path = generate::env_path();
+    let Some(mut env) = EnvFile::load(&path) else {
+        return Ok(None);
+    };
+    if drift.missing.is_empty() {
+        return Ok(None);
+    }
+
+    env.append_section(&drift.missing);
+    backup::write_file(&path, &env.render()).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn required_compose_variables() {
+        let compose = "\
+services:
+  analytics-engine:
+    # ${COMMENTED_OUT}
+    ports:
+      - \"${ANALYTICS_ENGINE_PORT}:${ANALYTICS_ENGINE_PORT}\"
+      - \"${HOST_PORT:-3000}:3000\"
+    volumes:
+      - ${PROJECT_DIR}/data:/usr/src/app/data
+      - ${LOCAL_STORAGE-.}:/data
+    environment:
+      TOKEN: ${API_TOKEN:?set API_TOKEN}
+      OPTIONAL: ${DEBUG_FLAGS:+--debug}
+      LITERAL: $${NOT_A_VARIABLE}
+";
+        assert_eq!(
+            required_variables(compose),
+            ["ANALYTICS_ENGINE_PORT", "PROJECT_DIR", "API_TOKEN"]
+        );
+    }
+
+    #[test]
+    fn reports_unset_compose_variables() {
+        let env = EnvFile::parse("INSTALLER_TEST_SET=1\nINSTALLER_TEST_EMPTY=\n");
+        let catalog = TemplateCatalog::built_in();
+        let compose = "services:\n  app:\n    image: \"${INSTALLER_TEST_SET}${INSTALLER_TEST_EMPTY}\
+                       ${INSTALLER_TEST_UNSET}${INSTALLER_TEST_DEFAULT:-x}\"\n";
+        let drift = check(&env, &catalog, compose);
+        assert_eq!(drift.unset, ["INSTALLER_TEST_UNSET"]);
+        assert!(
+            drift
+                .summary()
+                .iter()
+                .any(|line| line.ends_with("not set in .env: INSTALLER_TEST_UNSET"))
+        );
+    }
+}
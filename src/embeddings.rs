@@ -0,0 +1,56 @@
+const EMBEDDING_DIMENSIONS: &[(&str, u64)] = &[
+    ("text-embedding-3-large", 3072),
+    ("text-embedding-3-small", 1536),
+    ("text-embedding-ada-002", 1536),
+    ("text-embedding-004", 768),
+    ("text-embedding-005", 768),
+    ("text-multilingual-embedding-002", 768),
+    ("gemini-embedding-001", 3072),
+    ("amazon.titan-embed-text-v2:0", 1024),
+    ("amazon.titan-embed-text-v1", 1536),
+    ("cohere.embed-english-v3", 1024),
+    ("cohere.embed-multilingual-v3", 1024),
+    ("nomic-embed-text", 768),
+    ("nomic-embed-text-v1.5", 768),
+    ("text-embedding-nomic-embed-text-v1.5", 768),
+    ("mxbai-embed-large", 1024),
+    ("all-minilm", 384),
+    ("bge-m3", 1024),
+    ("mistral-embed", 1024),
+];
+
+pub fn dimension(model: &str) -> Option<u64> {
+    let name = model.rsplit('/').next().unwrap_or(model);
+    let untagged = name.split_once(':').map(|(base, _)| base).unwrap_or(name);
+
+    [name, untagged].into_iter().find_map(|candidate| {
+        EMBEDDING_DIMENSIONS
+            .iter()
+            .find(|(known, _)| known.eq_ignore_ascii_case(candidate))
+            .map(|(_, dimension)| *dimension)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dimension_cases() {
+        let cases = [
+            ("text-embedding-3-large", Some(3072)),
+            ("openai/text-embedding-3-small", Some(1536)),
+            ("Text-Embedding-Ada-002", Some(1536)),
+            ("vertex_ai/text-embedding-005", Some(768)),
+            ("amazon.titan-embed-text-v2:0", Some(1024)),
+            ("bedrock/amazon.titan-embed-text-v2:0", Some(1024)),
+            ("ollama/nomic-embed-text:latest", Some(768)),
+            ("bge-m3:567m", Some(1024)),
+            ("my-finetuned-embedder", None),
+            ("", None),
+        ];
+        for (model, expected) in cases {
+            assert_eq!(dimension(model), expected, "{}", model);
+        }
+    }
+}
@@ -0,0 +1,764 @@
+use color_eyre::{Result, eyre::eyre};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::UnixStream;
+
+use crate::progress::ContainerFailure;
+
+mod project;
+
+pub use project::Project;
+
+pub const DEFAULT_SOCKET: &str = "/var/run/docker.sock";
+
+const API_VERSION: &str = "v1.41";
+const PING_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone)]
+pub struct EngineClient {
+    socket: PathBuf,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct PullMessage {
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default, rename = "progressDetail")]
+    pub progress_detail: Option<ProgressDetail>,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ProgressDetail {
+    #[serde(default)]
+    pub current: Option<u64>,
+    #[serde(default)]
+    pub total: Option<u64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ContainerSummary {
+    #[serde(rename = "Id")]
+    pub id: String,
+    #[serde(default, rename = "Names")]
+    pub names: Vec<String>,
+    #[serde(default, rename = "Labels")]
+    pub labels: HashMap<String, String>,
+    #[serde(default, rename = "State")]
+    pub state: String,
+}
+
+impl ContainerSummary {
+    pub fn service(&self) -> &str {
+        self.labels
+            .get("com.docker.compose.service")
+            .map(String::as_str)
+            .unwrap_or_else(|| self.name())
+    }
+
+    pub fn name(&self) -> &str {
+        self.names
+            .first()
+            .map(|name| name.trim_start_matches('/'))
+            .unwrap_or(&self.id)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct EngineEvent {
+    #[serde(rename = "Action")]
+    pub action: String,
+    #[serde(rename = "Actor")]
+    pub actor: EventActor,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct EventActor {
+    #[serde(default, rename = "Attributes")]
+    pub attributes: HashMap<String, String>,
+}
+
+impl EngineEvent {
+    pub fn service(&self) -> &str {
+        self.actor
+            .attributes
+            .get("com.docker.compose.service")
+            .or_else(|| self.actor.attributes.get("name"))
+            .map(String::as_str)
+            .unwrap_or_default()
+    }
+
+    pub fn attribute(&self, key: &str) -> Option<&str> {
+        self.actor.attributes.get(key).map(String::as_str)
+    }
+
+    pub fn failure(&self) -> Option<ContainerFailure> {
+        match self.action.as_str() {
+            "die" => self
+                .attribute("exitCode")
+                .and_then(|code| code.parse().ok())
+                .filter(|code| *code != 0)
+                .map(ContainerFailure::Exited),
+            "health_status: unhealthy" => Some(ContainerFailure::Unhealthy),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorMessage {
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Created {
+    #[serde(rename = "Id")]
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct VersionInfo {
+    #[serde(rename = "Version")]
+    version: String,
+    #[serde(rename = "ApiVersion")]
+    api_version: String,
+}
+
+pub struct Response {
+    pub status: u16,
+    reader: BufReader<UnixStream>,
+    body: Body,
+    pending: Vec<u8>,
+}
+
+enum Body {
+    Chunked,
+    Length(usize),
+    UntilClose,
+    Done,
+}
+
+impl EngineClient {
+    pub fn new(socket: impl Into<PathBuf>) -> Self {
+        Self {
+            socket: socket.into(),
+        }
+    }
+
+    pub fn from_env() -> Result<Self> {
+        Ok(Self::new(socket_path()?))
+    }
+
+    pub fn socket(&self) -> &Path {
+        &self.socket
+    }
+
+    pub async fn version(&self) -> Result<String> {
+        let info: VersionInfo = self.get_json("/version").await?;
+        Ok(format!("{} (API {})", info.version, info.api_version))
+    }
+
+    pub async fn pull(
+        &self,
+        image: &str,
+        platform: Option<&str>,
+        mut on_message: impl FnMut(&PullMessage),
+    ) -> Result<()> {
+        let (name, tag) = split_image(image);
+        let mut path = format!(
+            "/images/create?fromImage={}&tag={}",
+            encode(name),
+            encode(tag)
+        );
+        if let Some(platform) = platform {
+            path.push_str(&format!("&platform={}", encode(platform)));
+        }
+
+        let mut response = self
+            .send("POST", &path, None)
+            .await
+            .map_err(|e| eyre!("failed to pull {}: {}", image, e))?;
+        while let Some(line) = response.next_line().await? {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let message: PullMessage = serde_json::from_str(&line)
+                .map_err(|e| eyre!("unexpected pull progress from the engine: {}", e))?;
+            if let Some(error) = &message.error {
+                return Err(eyre!("failed to pull {}: {}", image, error));
+            }
+            on_message(&message);
+        }
+        Ok(())
+    }
+
+    pub async fn containers(&self, project: &str) -> Result<Vec<ContainerSummary>> {
+        let path = format!(
+            "/containers/json?all=true&filters={}",
+            encode(&project_filter(project))
+        );
+        self.get_json(&path).await
+    }
+
+    pub async fn create_network(&self, body: &Value) -> Result<()> {
+        let response = self
+            .exchange("POST", "/networks/create", Some(body))
+            .await?;
+        match response.status {
+            201 | 409 => Ok(()),
+            _ => Err(response.error().await),
+        }
+    }
+
+    pub async fn create_volume(&self, body: &Value) -> Result<()> {
+        self.send("POST", "/volumes/create", Some(body)).await?;
+        Ok(())
+    }
+
+    pub async fn create_container(
+        &self,
+        name: &str,
+        platform: Option<&str>,
+        body: &Value,
+    ) -> Result<String> {
+        let mut path = format!("/containers/create?name={}", encode(name));
+        if let Some(platform) = platform {
+            path.push_str(&format!("&platform={}", encode(platform)));
+        }
+        let created: Created = self.json("POST", &path, Some(body)).await?;
+        Ok(created.id)
+    }
+
+    pub async fn connect(&self, network: &str, id: &str, endpoint: &Value) -> Result<()> {
+        let path = format!("/networks/{}/connect", encode(network));
+        let body = json!({ "Container": id, "EndpointConfig": endpoint });
+        self.send("POST", &path, Some(&body)).await?;
+        Ok(())
+    }
+
+    pub async fn remove(&self, id: &str) -> Result<()> {
+        let path = format!("/containers/{}?force=true", encode(id));
+        let response = self.exchange("DELETE", &path, None).await?;
+        match response.status {
+            204 | 404 => Ok(()),
+            _ => Err(response.error().await),
+        }
+    }
+
+    pub async fn start(&self, id: &str) -> Result<()> {
+        let response = self
+            .send("POST", &format!("/containers/{}/start", encode(id)), None)
+            .await?;
+        match response.status {
+            204 | 304 => Ok(()),
+            status => Err(eyre!("starting {} returned HTTP {}", id, status)),
+        }
+    }
+
+    pub async fn events(&self, project: &str) -> Result<Response> {
+        let path = format!(
+            "/events?filters={}",
+            encode(&format!(
+                "{{\"type\":[\"container\"],\"label\":[\"com.docker.compose.project={}\"]}}",
+                project
+            ))
+        );
+        self.send("GET", &path, None).await
+    }
+
+    async fn get_json<T: for<'de> Deserialize<'de>>(&self, path: &str) -> Result<T> {
+        self.json("GET", path, None).await
+    }
+
+    async fn json<T: for<'de> Deserialize<'de>>(
+        &self,
+        method: &str,
+        path: &str,
+        body: Option<&Value>,
+    ) -> Result<T> {
+        let body = self.send(method, path, body).await?.text().await?;
+        serde_json::from_str(&body).map_err(|e| eyre!("unexpected answer to {}: {}", path, e))
+    }
+
+    async fn send(&self, method: &str, path: &str, body: Option<&Value>) -> Result<Response> {
+        let response = self.exchange(method, path, body).await?;
+        if response.status >= 400 {
+            return Err(response.error().await);
+        }
+        Ok(response)
+    }
+
+    async fn exchange(&self, method: &str, path: &str, body: Option<&Value>) -> Result<Response> {
+        let mut stream = UnixStream::connect(&self.socket)
+            .await
+            .map_err(|e| eyre!("failed to connect to {}: {}", self.socket.display(), e))?;
+        stream
+            .write_all(request(method, path, body).as_bytes())
+            .await
+            .map_err(|e| eyre!("failed to send {} {}: {}", method, path, e))?;
+
+        let mut reader = BufReader::new(stream);
+        let mut line = String::new();
+        reader.read_line(&mut line).await?;
+        let status = parse_status(&line)?;
+
+        let mut body = Body::UntilClose;
+        loop {
+            line.clear();
+            if reader.read_line(&mut line).await? == 0 || line.trim().is_empty() {
+                break;
+            }
+            let Some((name, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+            if name.eq_ignore_ascii_case("transfer-encoding")
+                && value.eq_ignore_ascii_case("chunked")
+            {
+                body = Body::Chunked;
+            } else if name.eq_ignore_ascii_case("content-length")
+                && let Ok(length) = value.parse()
+                && !matches!(body, Body::Chunked)
+            {
+                body = Body::Length(length);
+            }
+        }
+
+        Ok(Response {
+            status,
+            reader,
+            body,
+            pending: Vec::new(),
+        })
+    }
+}
+
+impl Response {
+    async fn error(self) -> color_eyre::Report {
+        let status = self.status;
+        let body = self.text().await.unwrap_or_default();
+        let message = serde_json::from_str::<ErrorMessage>(&body)
+            .map(|error| error.message)
+            .unwrap_or_else(|_| body.trim().to_string());
+        eyre!("{} (HTTP {})", message, status)
+    }
+
+    pub async fn next_line(&mut self) -> Result<Option<String>> {
+        loop {
+            if let Some(end) = self.pending.iter().position(|&byte| byte == b'\n') {
+                let line: Vec<u8> = self.pending.drain(..=end).collect();
+                return Ok(Some(String::from_utf8_lossy(&line).trim_end().to_string()));
+            }
+            match self.read_chunk().await? {
+                Some(chunk) => self.pending.extend(chunk),
+                None if self.pending.is_empty() => return Ok(None),
+                None => {
+                    let line = String::from_utf8_lossy(&self.pending).to_string();
+                    self.pending.clear();
+                    return Ok(Some(line));
+                }
+            }
+        }
+    }
+
+    pub async fn text(mut self) -> Result<String> {
+        let mut body = std::mem::take(&mut self.pending);
+        while let Some(chunk) = self.read_chunk().await? {
+            body.extend(chunk);
+        }
+        Ok(String::from_utf8_lossy(&body).to_string())
+    }
+
+    async fn read_chunk(&mut self) -> Result<Option<Vec<u8>>> {
+        match self.body {
+            Body::Done => Ok(None),
+            Body::Length(0) => {
+                self.body = Body::Done;
+                Ok(None)
+            }
+            Body::Length(remaining) => {
+                let mut chunk = vec![0; remaining.min(8192)];
+                let read = self.reader.read(&mut chunk).await?;
+                if read == 0 {
+                    return Err(eyre!("the engine closed the connection mid-response"));
+                }
+                chunk.truncate(read);
+                self.body = Body::Length(remaining - read);
+                Ok(Some(chunk))
+            }
+            Body::UntilClose => {
+                let mut chunk = vec![0; 8192];
+                let read = self.reader.read(&mut chunk).await?;
+                if read == 0 {
+                    self.body = Body::Done;
+                    return Ok(None);
+                }
+                chunk.truncate(read);
+                Ok(Some(chunk))
+            }
+            Body::Chunked => {
+                let mut line = String::new();
+                if self.reader.read_line(&mut line).await? == 0 {
+                    self.body = Body::Done;
+                    return Ok(None);
+                }
+                let size = line.trim().split(';').next().unwrap_or_default();
+                let size = usize::from_str_radix(size, 16)
+                    .map_err(|_| eyre!("invalid chunk size from the engine: {:?}", line))?;
+                if size == 0 {
+                    self.body = Body::Done;
+                    return Ok(None);
+                }
+                let mut chunk = vec![0; size + 2];
+                self.reader.read_exact(&mut chunk).await?;
+                chunk.truncate(size);
+                Ok(Some(chunk))
+            }
+        }
+    }
+}
+
+pub fn socket_path() -> Result<PathBuf> {
+    socket_for(std::env::var("DOCKER_HOST").ok().as_deref())
+}
+
+fn socket_for(host: Option<&str>) -> Result<PathBuf> {
+    match host.map(str::trim).filter(|host| !host.is_empty()) {
+        Some(host) => host.strip_prefix("unix://").map(PathBuf::from).ok_or_else(|| {
+            eyre!(
+                "DOCKER_HOST={} is not a unix socket, the engine API backend only supports unix:// hosts",
+                host
+            )
+        }),
+        None => Ok(PathBuf::from(DEFAULT_SOCKET)),
+    }
+}
+
+pub fn ping(socket: &Path) -> Result<()> {
+    let mut stream = std::os::unix::net::UnixStream::connect(socket)
+        .map_err(|e| eyre!("failed to connect to {}: {}", socket.display(), e))?;
+    stream.set_read_timeout(Some(PING_TIMEOUT))?;
+    stream.set_write_timeout(Some(PING_TIMEOUT))?;
+    stream.write_all(request("GET", "/_ping", None).as_bytes())?;
+
+    let mut answer = String::new();
+    stream
+        .read_to_string(&mut answer)
+        .map_err(|e| eyre!("no answer on {}: {}", socket.display(), e))?;
+    match parse_status(answer.lines().next().unwrap_or_default())? {
+        200 => Ok(()),
+        status => Err(eyre!("/_ping returned HTTP {}", status)),
+    }
+}
+
+fn request(method: &str, path: &str, body: Option<&Value>) -> String {
+    let (content_type, body) = match body {
+        Some(body) => ("Content-Type: application/json\r\n", body.to_string()),
+        None => ("", String::new()),
+    };
+    format!(
+        "{} /{}{} HTTP/1.1\r\nHost: docker\r\nUser-Agent: installer-analytics\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+        method,
+        API_VERSION,
+        path,
+        content_type,
+        body.len(),
+        body
+    )
+}
+
+fn parse_status(line: &str) -> Result<u16> {
+    line.split_whitespace()
+        .nth(1)
+        .and_then(|status| status.parse().ok())
+        .ok_or_else(|| eyre!("unexpected answer from the engine: {:?}", line.trim()))
+}
+
+fn project_filter(project: &str) -> String {
+    format!("{{\"label\":[\"com.docker.compose.project={}\"]}}", project)
+}
+
+fn split_image(image: &str) -> (&str, &str) {
+    if image.contains('@') {
+        return (image, "");
+    }
+    match image.rsplit_once(':') {
+        Some((name, tag)) if !tag.contains('/') => (name, tag),
+        _ => (image, "latest"),
+    }
+}
+
+fn encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::UnixListener;
+
+    fn serve(responses: Vec<String>) -> (tempfile::TempDir, EngineClient) {
+        let dir = tempfile::tempdir().unwrap();
+        let socket = dir.path().join("engine.sock");
+        let listener = UnixListener::bind(&socket).unwrap();
+        tokio::spawn(async move {
+            for response in responses {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut request = Vec::new();
+                let mut buffer = [0; 1024];
+                while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                    let read = stream.read(&mut buffer).await.unwrap();
+                    if read == 0 {
+                        break;
+                    }
+                    request.extend_from_slice(&buffer[..read]);
+                }
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+        (dir, EngineClient::new(socket))
+    }
+
+    fn with_length(status: &str, body: &str) -> String {
+        format!(
+            "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        )
+    }
+
+    fn chunked(lines: &[&str]) -> String {
+        let mut response = String::from("HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n");
+        for line in lines {
+            let data = format!("{}\n", line);
+            response.push_str(&format!("{:x}\r\n{}\r\n", data.len(), data));
+        }
+        response.push_str("0\r\n\r\n");
+        response
+    }
+
+    #[tokio::test]
+    async fn version_is_parsed() {
+        let (_dir, client) = serve(vec![with_length(
+            "200 OK",
+            r#"{"Version":"27.0.3","ApiVersion":"1.47","Os":"linux"}"#,
+        )]);
+        assert_eq!(client.version().await.unwrap(), "27.0.3 (API 1.47)");
+    }
+
+    #[tokio::test]
+    async fn chunked_pull_stream_reaches_the_callback() {
+        let (_dir, client) = serve(vec![chunked(&[
+            r#"{"status":"Pulling from qdrant/qdrant","id":"v1.11.0"}"#,
+            r#"{"status":"Downloading","progressDetail":{"current":512,"total":2048},"id":"a1b2"}"#,
+            r#"{"status":"Download complete","progressDetail":{},"id":"a1b2"}"#,
+        ])]);
+
+        let mut messages = Vec::new();
+        client
+            .pull("qdrant/qdrant:v1.11.0", Some("linux/amd64"), |message| {
+                messages.push((
+                    message.status.clone().unwrap_or_default(),
+                    message
+                        .progress_detail
+                        .as_ref()
+                        .and_then(|detail| detail.total),
+                ))
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(
+            messages,
+            vec![
+                ("Pulling from qdrant/qdrant".to_string(), None),
+                ("Downloading".to_string(), Some(2048)),
+                ("Download complete".to_string(), None),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn pull_error_line_is_an_error() {
+        let (_dir, client) = serve(vec![chunked(&[
+            r#"{"status":"Pulling from library/postgres","id":"15"}"#,
+            r#"{"errorDetail":{"message":"manifest unknown"},"error":"manifest unknown"}"#,
+        ])]);
+
+        let error = client
+            .pull("postgres:15", None, |_| {})
+            .await
+            .unwrap_err()
+            .to_string();
+        assert_eq!(error, "failed to pull postgres:15: manifest unknown");
+    }
+
+    #[tokio::test]
+    async fn http_error_body_is_an_error() {
+        let (_dir, client) = serve(vec![with_length(
+            "404 Not Found",
+            r#"{"message":"No such container: analytics-ui"}"#,
+        )]);
+
+        let error = client.start("analytics-ui").await.unwrap_err().to_string();
+        assert_eq!(error, "No such container: analytics-ui (HTTP 404)");
+    }
+
+    #[tokio::test]
+    async fn start_accepts_not_modified() {
+        let (_dir, client) = serve(vec!["HTTP/1.1 304 Not Modified\r\n\r\n".to_string()]);
+        client.start("qdrant").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn create_container_returns_the_id() {
+        let (_dir, client) = serve(vec![with_length(
+            "201 Created",
+            r#"{"Id":"e90e34656806","Warnings":[]}"#,
+        )]);
+
+        let id = client
+            .create_container(
+                "analytics-qdrant-1",
+                Some("linux/amd64"),
+                &json!({ "Image": "qdrant/qdrant:v1.11.0" }),
+            )
+            .await
+            .unwrap();
+        assert_eq!(id, "e90e34656806");
+    }
+
+    #[tokio::test]
+    async fn existing_network_and_missing_container_are_accepted() {
+        let (_dir, client) = serve(vec![
+            with_length(
+                "409 Conflict",
+                r#"{"message":"network with name analytics_default already exists"}"#,
+            ),
+            with_length(
+                "404 Not Found",
+                r#"{"message":"No such container: analytics-ui"}"#,
+            ),
+            with_length("500 Internal Server Error", r#"{"message":"disk full"}"#),
+        ]);
+
+        client
+            .create_network(&json!({ "Name": "analytics_default" }))
+            .await
+            .unwrap();
+        client.remove("analytics-ui").await.unwrap();
+        let error = client
+            .create_volume(&json!({ "Name": "analytics_data" }))
+            .await
+            .unwrap_err()
+            .to_string();
+        assert_eq!(error, "disk full (HTTP 500)");
+    }
+
+    #[test]
+    fn requests_carry_the_json_body() {
+        let body = json!({ "Name": "analytics_data" });
+        let request = request("POST", "/volumes/create", Some(&body));
+        assert!(request.starts_with("POST /v1.41/volumes/create HTTP/1.1\r\n"));
+        assert!(request.contains("Content-Type: application/json\r\nContent-Length: 25\r\n"));
+        assert!(request.ends_with("\r\n\r\n{\"Name\":\"analytics_data\"}"));
+    }
+
+    #[tokio::test]
+    async fn content_length_body_is_read_fully() {
+        let body = format!("{{\"padding\":\"{}\"}}", "x".repeat(20_000));
+        let (_dir, client) = serve(vec![with_length("200 OK", &body)]);
+
+        let text = client
+            .send("GET", "/info", None)
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        assert_eq!(text, body);
+    }
+
+    #[tokio::test]
+    async fn body_until_close_is_read_fully() {
+        let body = r#"[{"Id":"1","Names":["/analytics-qdrant-1"],"Labels":{"com.docker.compose.service":"qdrant"},"State":"created"},{"Id":"2","Names":["/analytics-analytics-ui-1"],"Labels":{},"State":"running"}]"#;
+        let (_dir, client) = serve(vec![format!("HTTP/1.1 200 OK\r\n\r\n{}", body)]);
+
+        let containers = client.containers("analytics").await.unwrap();
+        assert_eq!(containers.len(), 2);
+        assert_eq!(containers[0].service(), "qdrant");
+        assert_eq!(containers[1].service(), "analytics-analytics-ui-1");
+        assert_eq!(containers[1].state, "running");
+    }
+
+    #[test]
+    fn only_unix_hosts_are_accepted() {
+        assert_eq!(socket_for(None).unwrap(), PathBuf::from(DEFAULT_SOCKET));
+        assert_eq!(
+            socket_for(Some("unix:///run/user/1000/podman/podman.sock")).unwrap(),
+            PathBuf::from("/run/user/1000/podman/podman.sock")
+        );
+        assert!(socket_for(Some("tcp://127.0.0.1:2375")).is_err());
+    }
+
+    #[test]
+    fn image_references_are_split() {
+        assert_eq!(
+            split_image("qdrant/qdrant:v1.11.0"),
+            ("qdrant/qdrant", "v1.11.0")
+        );
+        assert_eq!(
+            split_image("localhost:5000/ui"),
+            ("localhost:5000/ui", "latest")
+        );
+        assert_eq!(encode("linux/amd64"), "linux%2Famd64");
+    }
+
+    #[test]
+    fn failing_container_events() {
+        let cases = [
+            ("die", Some("1"), Some(ContainerFailure::Exited(1))),
+            ("die", Some("137"), Some(ContainerFailure::Exited(137))),
+            ("die", Some("0"), None),
+            ("die", None, None),
+            (
+                "health_status: unhealthy",
+                None,
+                Some(ContainerFailure::Unhealthy),
+            ),
+            ("health_status: healthy", None, None),
+            ("start", None, None),
+        ];
+
+        for (action, exit_code, expected) in cases {
+            let mut attributes =
+                HashMap::from([("name".to_string(), "analytics-qdrant-1".to_string())]);
+            if let Some(code) = exit_code {
+                attributes.insert("exitCode".to_string(), code.to_string());
+            }
+            let event: EngineEvent = serde_json::from_value(json!({
+                "Action": action,
+                "Actor": { "Attributes": attributes },
+            }))
+            .unwrap();
+            assert_eq!(event.failure(), expected, "{}", action);
+        }
+    }
+}
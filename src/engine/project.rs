@@ -0,0 +1,1166 @@
+use color_eyre::{Result, eyre::eyre};
+use serde::Deserialize;
+use serde_json::{Map, Value, json};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::compose::{self, PortEntry};
+use crate::env_file::EnvFile;
+use crate::ports;
+
+const SUPPORTED_KEYS: &[&str] = &[
+    "build",
+    "command",
+    "container_name",
+    "depends_on",
+    "entrypoint",
+    "env_file",
+    "environment",
+    "expose",
+    "extra_hosts",
+    "healthcheck",
+    "hostname",
+    "image",
+    "labels",
+    "network_mode",
+    "networks",
+    "platform",
+    "ports",
+    "restart",
+    "stdin_open",
+    "tty",
+    "user",
+    "volumes",
+    "working_dir",
+];
+
+#[derive(Debug)]
+pub struct Project {
+    pub name: String,
+    pub services: Vec<ProjectService>,
+    pub networks: Vec<Resource>,
+    pub volumes: Vec<Resource>,
+}
+
+#[derive(Debug)]
+pub struct ProjectService {
+    pub name: String,
+    pub container_name: String,
+    pub image: String,
+    pub built: bool,
+    pub platform: Option<String>,
+    pub depends_on: Vec<String>,
+    pub healthcheck: bool,
+    pub networks: Vec<(String, Value)>,
+    pub ignored: Vec<String>,
+    pub body: Value,
+}
+
+#[derive(Debug)]
+pub struct Resource {
+    pub name: String,
+    pub body: Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct ConfigOutput {
+    name: Option<String>,
+    #[serde(default)]
+    services: serde_yaml::Mapping,
+    #[serde(default)]
+    networks: BTreeMap<String, Option<TopLevel>>,
+    #[serde(default)]
+    volumes: BTreeMap<String, Option<TopLevel>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct TopLevel {
+    name: Option<String>,
+    driver: Option<String>,
+    #[serde(default)]
+    external: serde_yaml::Value,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Definition {
+    image: Option<String>,
+    build: Option<serde_yaml::Value>,
+    platform: Option<String>,
+    container_name: Option<String>,
+    command: Option<Words>,
+    entrypoint: Option<Words>,
+    #[serde(default)]
+    environment: Pairs,
+    env_file: Option<serde_yaml::Value>,
+    #[serde(default)]
+    labels: Pairs,
+    #[serde(default)]
+    ports: Vec<PortEntry>,
+    #[serde(default)]
+    expose: Vec<serde_yaml::Value>,
+    #[serde(default)]
+    volumes: Vec<VolumeEntry>,
+    network_mode: Option<String>,
+    networks: Option<Networks>,
+    #[serde(default)]
+    depends_on: serde_yaml::Value,
+    healthcheck: Option<Healthcheck>,
+    restart: Option<String>,
+    working_dir: Option<String>,
+    user: Option<String>,
+    hostname: Option<String>,
+    #[serde(default)]
+    extra_hosts: Pairs,
+    #[serde(default)]
+    tty: bool,
+    #[serde(default)]
+    stdin_open: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Words {
+    Line(String),
+    List(Vec<String>),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Pairs {
+    Map(BTreeMap<String, serde_yaml::Value>),
+    List(Vec<String>),
+}
+
+impl Default for Pairs {
+    fn default() -> Self {
+        Pairs::List(Vec::new())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum VolumeEntry {
+    Short(String),
+    Long {
+        #[serde(rename = "type")]
+        kind: String,
+        source: Option<String>,
+        target: String,
+        #[serde(default)]
+        read_only: bool,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Networks {
+    List(Vec<String>),
+    Map(BTreeMap<String, Option<NetworkAttachment>>),
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct NetworkAttachment {
+    #[serde(default)]
+    aliases: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Healthcheck {
+    test: Option<Words>,
+    interval: Option<String>,
+    timeout: Option<String>,
+    retries: Option<u64>,
+    start_period: Option<String>,
+    #[serde(default)]
+    disable: bool,
+}
+
+impl Project {
+    pub fn parse(content: &str, project: &str, separator: char, root: &Path) -> Result<Self> {
+        let config: ConfigOutput = serde_yaml::from_str(content)
+            .map_err(|e| eyre!("unexpected output from compose config: {}", e))?;
+        let name = config
+            .name
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| project.to_string());
+        let network_names = resource_names(&config.networks, &name);
+        let volume_names = resource_names(&config.volumes, &name);
+
+        let mut services = Vec::new();
+        let mut used_networks = Vec::new();
+        let mut used_volumes = Vec::new();
+        for (service, definition) in config.services {
+            let Some(service) = service.as_str() else {
+                continue;
+            };
+            let ignored = definition
+                .as_mapping()
+                .map(|keys| {
+                    keys.keys()
+                        .filter_map(|key| key.as_str())
+                        .filter(|key| !SUPPORTED_KEYS.contains(key))
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default();
+            let definition: Definition = serde_yaml::from_value(definition)
+                .map_err(|e| eyre!("service {} in compose config: {}", service, e))?;
+            let context = Context {
+                project: &name,
+                service,
+                separator,
+                root,
+                networks: &network_names,
+                volumes: &volume_names,
+            };
+            let resolved = context.service(definition, ignored)?;
+            for (network, _) in &resolved.networks {
+                if !used_networks.contains(network) {
+                    used_networks.push(network.clone());
+                }
+            }
+            for volume in named_volumes(&resolved.body) {
+                if !used_volumes.contains(&volume) {
+                    used_volumes.push(volume);
+                }
+            }
+            services.push(resolved);
+        }
+
+        let networks = used_networks
+            .into_iter()
+            .filter_map(|network| {
+                let (key, definition) = lookup(&config.networks, &network_names, &network);
+                if is_external(definition) {
+                    return None;
+                }
+                let driver = definition
+                    .and_then(|definition| definition.driver.clone())
+                    .unwrap_or_else(|| "bridge".to_string());
+                Some(Resource {
+                    body: json!({
+                        "Name": network,
+                        "Driver": driver,
+                        "CheckDuplicate": true,
+                        "Labels": {
+                            "com.docker.compose.project": name,
+                            "com.docker.compose.network": key,
+                        },
+                    }),
+                    name: network,
+                })
+            })
+            .collect();
+        let volumes = used_volumes
+            .into_iter()
+            .filter_map(|volume| {
+                let (key, definition) = lookup(&config.volumes, &volume_names, &volume);
+                if is_external(definition) {
+                    return None;
+                }
+                let mut body = json!({
+                    "Name": volume,
+                    "Labels": {
+                        "com.docker.compose.project": name,
+                        "com.docker.compose.volume": key,
+                    },
+                });
+                if let Some(driver) = definition.and_then(|definition| definition.driver.as_ref()) {
+                    body["Driver"] = json!(driver);
+                }
+                Some(Resource { name: volume, body })
+            })
+            .collect();
+
+        Ok(Self {
+            name,
+            services,
+            networks,
+            volumes,
+        })
+    }
+
+    pub fn start_order(&self) -> Vec<&ProjectService> {
+        fn visit<'a>(
+            name: &str,
+            services: &'a [ProjectService],
+            order: &mut Vec<&'a ProjectService>,
+            seen: &mut Vec<String>,
+        ) {
+            if seen.iter().any(|visited| visited == name) {
+                return;
+            }
+            seen.push(name.to_string());
+            if let Some(service) = services.iter().find(|service| service.name == name) {
+                for dependency in &service.depends_on {
+                    visit(dependency, services, order, seen);
+                }
+                order.push(service);
+            }
+        }
+
+        let mut order = Vec::new();
+        let mut seen = Vec::new();
+        for service in &self.services {
+            visit(&service.name, &self.services, &mut order, &mut seen);
+        }
+        order
+    }
+}
+
+struct Context<'a> {
+    project: &'a str,
+    service: &'a str,
+    separator: char,
+    root: &'a Path,
+    networks: &'a BTreeMap<String, String>,
+    volumes: &'a BTreeMap<String, String>,
+}
+
+impl Context<'_> {
+    fn service(&self, definition: Definition, ignored: Vec<String>) -> Result<ProjectService> {
+        let service = self.service;
+        let image = definition
+            .image
+            .clone()
+            .unwrap_or_else(|| format!("{}{}{}", self.project, self.separator, service));
+        let container_name = definition.container_name.clone().unwrap_or_else(|| {
+            format!(
+                "{}{}{}{}1",
+                self.project, self.separator, service, self.separator
+            )
+        });
+
+        let mut labels = Map::new();
+        for (key, value) in pairs(definition.labels) {
+            labels.insert(key, json!(value.unwrap_or_default()));
+        }
+        for (key, value) in [
+            ("com.docker.compose.project", self.project.to_string()),
+            ("com.docker.compose.service", service.to_string()),
+            ("com.docker.compose.container-number", "1".to_string()),
+            ("com.docker.compose.oneoff", "False".to_string()),
+            (
+                "com.docker.compose.project.working_dir",
+                self.root.display().to_string(),
+            ),
+        ] {
+            labels.insert(key.to_string(), json!(value));
+        }
+
+        let mut body = Map::new();
+        body.insert("Image".to_string(), json!(image));
+        if let Some(command) = definition.command {
+            body.insert("Cmd".to_string(), json!(words(command)));
+        }
+        if let Some(entrypoint) = definition.entrypoint {
+            body.insert("Entrypoint".to_string(), json!(words(entrypoint)));
+        }
+        body.insert(
+            "Env".to_string(),
+            json!(self.environment(definition.environment, definition.env_file.as_ref())),
+        );
+        body.insert("Labels".to_string(), Value::Object(labels));
+        for (key, value) in [
+            ("WorkingDir", definition.working_dir),
+            ("User", definition.user),
+            ("Hostname", definition.hostname),
+        ] {
+            if let Some(value) = value {
+                body.insert(key.to_string(), json!(value));
+            }
+        }
+        if definition.tty {
+            body.insert("Tty".to_string(), json!(true));
+        }
+        if definition.stdin_open {
+            body.insert("OpenStdin".to_string(), json!(true));
+        }
+
+        let mut host = Map::new();
+        let mut exposed = Map::new();
+        let mut bindings: Map<String, Value> = Map::new();
+        for entry in definition.ports {
+            let spec = entry.spec();
+            for (container, binding) in port_bindings(&spec)
+                .ok_or_else(|| eyre!("service {} publishes an invalid port {}", service, spec))?
+            {
+                exposed.insert(container.clone(), json!({}));
+                if let Some(binding) = binding
+                    && let Value::Array(list) =
+                        bindings.entry(container).or_insert_with(|| json!([]))
+                {
+                    list.push(binding);
+                }
+            }
+        }
+        for port in definition.expose.iter().filter_map(text) {
+            for (container, _) in port_bindings(&port)
+                .ok_or_else(|| eyre!("service {} exposes an invalid port {}", service, port))?
+            {
+                exposed.insert(container, json!({}));
+            }
+        }
+        if !exposed.is_empty() {
+            body.insert("ExposedPorts".to_string(), Value::Object(exposed));
+        }
+        if !bindings.is_empty() {
+            host.insert("PortBindings".to_string(), Value::Object(bindings));
+        }
+
+        let (binds, mounts) = self.mounts(definition.volumes)?;
+        if !binds.is_empty() {
+            host.insert("Binds".to_string(), json!(binds));
+        }
+        if !mounts.is_empty() {
+            host.insert("Mounts".to_string(), json!(mounts));
+        }
+        if let Some(restart) = definition.restart {
+            host.insert("RestartPolicy".to_string(), restart_policy(&restart)?);
+        }
+        let extra_hosts: Vec<String> = pairs(definition.extra_hosts)
+            .into_iter()
+            .map(|(host, address)| match address {
+                Some(address) => format!("{}:{}", host, address),
+                None => host,
+            })
+            .collect();
+        if !extra_hosts.is_empty() {
+            host.insert("ExtraHosts".to_string(), json!(extra_hosts));
+        }
+
+        let networks = match definition.network_mode {
+            Some(mode) => {
+                host.insert("NetworkMode".to_string(), json!(mode));
+                Vec::new()
+            }
+            None => self.networks(definition.networks),
+        };
+        if let Some((network, endpoint)) = networks.first() {
+            host.insert("NetworkMode".to_string(), json!(network));
+            body.insert(
+                "NetworkingConfig".to_string(),
+                json!({ "EndpointsConfig": { network.as_str(): endpoint } }),
+            );
+        }
+        body.insert("HostConfig".to_string(), Value::Object(host));
+
+        let healthcheck = match definition.healthcheck {
+            Some(healthcheck) => {
+                let check = healthcheck_body(healthcheck)
+                    .map_err(|e| eyre!("service {} healthcheck: {}", service, e))?;
+                let enabled = check["Test"] != json!(["NONE"]);
+                body.insert("Healthcheck".to_string(), check);
+                enabled
+            }
+            None => false,
+        };
+
+        Ok(ProjectService {
+            name: service.to_string(),
+            container_name,
+            image,
+            built: definition.build.is_some(),
+            platform: definition.platform.filter(|platform| !platform.is_empty()),
+            depends_on: compose::dependencies(Some(&definition.depends_on)),
+            healthcheck,
+            networks,
+            ignored,
+            body: Value::Object(body),
+        })
+    }
+
+    fn environment(&self, environment: Pairs, env_file: Option<&serde_yaml::Value>) -> Vec<String> {
+        let mut env: BTreeMap<String, String> = BTreeMap::new();
+        for path in env_files(env_file) {
+            if let Some(file) = EnvFile::load(&self.root.join(path)) {
+                for key in file.keys() {
+                    env.insert(
+                        key.to_string(),
+                        file.get(key).unwrap_or_default().to_string(),
+                    );
+                }
+            }
+        }
+        for (key, value) in pairs(environment) {
+            match value.or_else(|| std::env::var(&key).ok()) {
+                Some(value) => env.insert(key, value),
+                None => env.remove(&key),
+            };
+        }
+        env.into_iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect()
+    }
+
+    fn mounts(&self, volumes: Vec<VolumeEntry>) -> Result<(Vec<String>, Vec<Value>)> {
+        let mut binds = Vec::new();
+        let mut mounts = Vec::new();
+        for volume in volumes {
+            let (kind, source, target, read_only) = match volume {
+                VolumeEntry::Long {
+                    kind,
+                    source,
+                    target,
+                    read_only,
+                } => (kind, source, target, read_only),
+                VolumeEntry::Short(spec) => {
+                    let parts: Vec<&str> = spec.split(':').collect();
+                    let (source, target, mode) = match parts.as_slice() {
+                        [target] => (None, *target, ""),
+                        [source, target] => (Some(*source), *target, ""),
+                        [source, target, mode] => (Some(*source), *target, *mode),
+                        _ => return Err(eyre!("service {} mounts {}", self.service, spec)),
+                    };
+                    let kind = match source {
+                        Some(source)
+                            if source.starts_with('.')
+                                || source.starts_with('/')
+                                || source.starts_with('~') =>
+                        {
+                            "bind"
+                        }
+                        _ => "volume",
+                    };
+                    (
+                        kind.to_string(),
+                        source.map(str::to_string),
+                        target.to_string(),
+                        mode.split(',').any(|mode| mode == "ro"),
+                    )
+                }
+            };
+
+            match (kind.as_str(), source) {
+                ("bind", Some(source)) => {
+                    let source = self.host_path(&source);
+                    binds.push(if read_only {
+                        format!("{}:{}:ro", source, target)
+                    } else {
+                        format!("{}:{}", source, target)
+                    });
+                }
+                ("volume", Some(source)) => mounts.push(json!({
+                    "Type": "volume",
+                    "Source": self
+                        .volumes
+                        .get(&source)
+                        .cloned()
+                        .unwrap_or_else(|| format!("{}_{}", self.project, source)),
+                    "Target": target,
+                    "ReadOnly": read_only,
+                })),
+                ("volume", None) | ("tmpfs", _) => mounts.push(json!({
+                    "Type": kind,
+                    "Target": target,
+                    "ReadOnly": read_only,
+                })),
+                (kind, _) => {
+                    return Err(eyre!(
+                        "service {} uses a {} mount, which the engine API backend does not create",
+                        self.service,
+                        kind
+                    ));
+                }
+            }
+        }
+        Ok((binds, mounts))
+    }
+
+    fn host_path(&self, source: &str) -> String {
+        let path = match source.strip_prefix("~/") {
+            Some(rest) => std::env::var("HOME")
+                .map(|home| Path::new(&home).join(rest))
+                .unwrap_or_else(|_| self.root.join(source)),
+            None => self.root.join(source),
+        };
+        let path = path.display().to_string();
+        match path.strip_suffix("/.") {
+            Some(path) => path.to_string(),
+            None => path.replace("/./", "/"),
+        }
+    }
+
+    fn networks(&self, networks: Option<Networks>) -> Vec<(String, Value)> {
+        let attachments: Vec<(String, Vec<String>)> = match networks {
+            None => vec![("default".to_string(), Vec::new())],
+            Some(Networks::List(names)) => {
+                names.into_iter().map(|name| (name, Vec::new())).collect()
+            }
+            Some(Networks::Map(names)) => names
+                .into_iter()
+                .map(|(name, attachment)| (name, attachment.unwrap_or_default().aliases))
+                .collect(),
+        };
+        attachments
+            .into_iter()
+            .map(|(key, mut aliases)| {
+                if !aliases.iter().any(|alias| alias == self.service) {
+                    aliases.insert(0, self.service.to_string());
+                }
+                let name = self
+                    .networks
+                    .get(&key)
+                    .cloned()
+                    .unwrap_or_else(|| format!("{}_{}", self.project, key));
+                (name, json!({ "Aliases": aliases }))
+            })
+            .collect()
+    }
+}
+
+fn resource_names(
+    resources: &BTreeMap<String, Option<TopLevel>>,
+    project: &str,
+) -> BTreeMap<String, String> {
+    let mut names: BTreeMap<String, String> = resources
+        .iter()
+        .map(|(key, definition)| {
+            let name = definition
+                .as_ref()
+                .and_then(|definition| definition.name.clone())
+                .unwrap_or_else(|| format!("{}_{}", project, key));
+            (key.clone(), name)
+        })
+        .collect();
+    names
+        .entry("default".to_string())
+        .or_insert_with(|| format!("{}_default", project));
+    names
+}
+
+fn lookup<'a>(
+    resources: &'a BTreeMap<String, Option<TopLevel>>,
+    names: &BTreeMap<String, String>,
+    name: &str,
+) -> (String, Option<&'a TopLevel>) {
+    let key = names
+        .iter()
+        .find(|(_, resolved)| *resolved == name)
+        .map(|(key, _)| key.clone())
+        .unwrap_or_else(|| name.to_string());
+    let definition = resources.get(&key).and_then(Option::as_ref);
+    (key, definition)
+}
+
+fn is_external(definition: Option<&TopLevel>) -> bool {
+    definition.is_some_and(|definition| {
+        definition.external.as_bool() == Some(true) || definition.external.is_mapping()
+    })
+}
+
+fn named_volumes(body: &Value) -> Vec<String> {
+    body["HostConfig"]["Mounts"]
+        .as_array()
+        .map(|mounts| {
+            mounts
+                .iter()
+                .filter(|mount| mount["Type"] == "volume")
+                .filter_map(|mount| mount["Source"].as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn pairs(pairs: Pairs) -> Vec<(String, Option<String>)> {
+    match pairs {
+        Pairs::Map(map) => map
+            .into_iter()
+            .map(|(key, value)| (key, text(&value)))
+            .collect(),
+        Pairs::List(list) => list
+            .into_iter()
+            .map(|entry| match entry.split_once(['=', ':']) {
+                Some((key, value)) => (key.to_string(), Some(value.to_string())),
+                None => (entry, None),
+            })
+            .collect(),
+    }
+}
+
+fn env_files(value: Option<&serde_yaml::Value>) -> Vec<String> {
+    match value {
+        Some(serde_yaml::Value::String(path)) => vec![path.clone()],
+        Some(serde_yaml::Value::Sequence(paths)) => paths
+            .iter()
+            .filter_map(|path| match path {
+                serde_yaml::Value::String(path) => Some(path.clone()),
+                path => path
+                    .get("path")
+                    .and_then(|path| path.as_str())
+                    .map(str::to_string),
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn text(value: &serde_yaml::Value) -> Option<String> {
+    match value {
+        serde_yaml::Value::String(text) => Some(text.clone()),
+        serde_yaml::Value::Number(number) => Some(number.to_string()),
+        serde_yaml::Value::Bool(flag) => Some(flag.to_string()),
+        _ => None,
+    }
+}
+
+fn words(words: Words) -> Vec<String> {
+    match words {
+        Words::List(list) => list,
+        Words::Line(line) => split_words(&line),
+    }
+}
+
+fn split_words(line: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut quote = None;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                in_word = true;
+            }
+            (Some(open), c) if c == open => quote = None,
+            (None | Some('"'), '\\') => {
+                if let Some(next) = chars.next() {
+                    word.push(next);
+                }
+                in_word = true;
+            }
+            (_, c) => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    words
+}
+
+fn port_bindings(spec: &str) -> Option<Vec<(String, Option<Value>)>> {
+    let (spec, protocol) = spec.split_once('/').unwrap_or((spec, "tcp"));
+    let parts = ports::split_port_spec(spec);
+    let (host_ip, host, container) = match parts.as_slice() {
+        [container] => (None, None, *container),
+        [host, container] => (None, Some(*host), *container),
+        [host_ip, host, container] => (Some(*host_ip), Some(*host), *container),
+        _ => return None,
+    };
+    let host_ip = host_ip
+        .map(|ip| ip.trim_start_matches('[').trim_end_matches(']'))
+        .unwrap_or_default();
+    let containers = port_range(container)?;
+    let hosts: Vec<String> = match host.filter(|host| !host.is_empty()) {
+        None if !host_ip.is_empty() => vec![String::new(); containers.len()],
+        None => Vec::new(),
+        Some(host) if containers.len() > 1 => {
+            let hosts = port_range(host)?;
+            if hosts.len() != containers.len() {
+                return None;
+            }
+            hosts.iter().map(u16::to_string).collect()
+        }
+        Some(host) => {
+            port_range(host)?;
+            vec![host.to_string()]
+        }
+    };
+
+    Some(
+        containers
+            .iter()
+            .enumerate()
+            .map(|(index, port)| {
+                let binding = hosts
+                    .get(index)
+                    .map(|host_port| json!({ "HostIp": host_ip, "HostPort": host_port }));
+                (format!("{}/{}", port, protocol), binding)
+            })
+            .collect(),
+    )
+}
+
+fn port_range(spec: &str) -> Option<Vec<u16>> {
+    match spec.split_once('-') {
+        Some((first, last)) => {
+            let (first, last) = (first.trim().parse().ok()?, last.trim().parse().ok()?);
+            (first <= last).then(|| (first..=last).collect())
+        }
+        None => Some(vec![spec.trim().parse().ok()?]),
+    }
+}
+
+fn restart_policy(restart: &str) -> Result<Value> {
+    let (name, retries) = restart.split_once(':').unwrap_or((restart, ""));
+    match name {
+        "no" | "always" | "unless-stopped" => Ok(json!({ "Name": name })),
+        "on-failure" => {
+            let retries: u64 = if retries.is_empty() {
+                0
+            } else {
+                retries
+                    .parse()
+                    .map_err(|_| eyre!("invalid restart policy {}", restart))?
+            };
+            Ok(json!({ "Name": name, "MaximumRetryCount": retries }))
+        }
+        _ => Err(eyre!("invalid restart policy {}", restart)),
+    }
+}
+
+fn healthcheck_body(healthcheck: Healthcheck) -> Result<Value> {
+    let test = match healthcheck.test {
+        _ if healthcheck.disable => vec!["NONE".to_string()],
+        Some(Words::Line(line)) => vec!["CMD-SHELL".to_string(), line],
+        Some(Words::List(list)) => list,
+        None => Vec::new(),
+    };
+    let mut body = Map::new();
+    if !test.is_empty() {
+        body.insert("Test".to_string(), json!(test));
+    }
+    for (key, value) in [
+        ("Interval", healthcheck.interval),
+        ("Timeout", healthcheck.timeout),
+        ("StartPeriod", healthcheck.start_period),
+    ] {
+        if let Some(value) = value {
+            body.insert(key.to_string(), json!(parse_duration(&value)?));
+        }
+    }
+    if let Some(retries) = healthcheck.retries {
+        body.insert("Retries".to_string(), json!(retries));
+    }
+    Ok(Value::Object(body))
+}
+
+fn parse_duration(value: &str) -> Result<u64> {
+    const UNITS: &[(&str, f64)] = &[
+        ("ns", 1.0),
+        ("us", 1e3),
+        ("µs", 1e3),
+        ("ms", 1e6),
+        ("s", 1e9),
+        ("m", 60e9),
+        ("h", 3600e9),
+    ];
+
+    let invalid = || eyre!("invalid duration {:?}", value);
+    let mut rest = value.trim();
+    if rest.is_empty() {
+        return Err(invalid());
+    }
+    let mut total = 0.0;
+    while !rest.is_empty() {
+        let number_end = rest
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .ok_or_else(invalid)?;
+        let number: f64 = rest[..number_end].parse().map_err(|_| invalid())?;
+        rest = &rest[number_end..];
+        let unit_end = rest
+            .find(|c: char| c.is_ascii_digit() || c == '.')
+            .unwrap_or(rest.len());
+        let (_, scale) = UNITS
+            .iter()
+            .find(|(unit, _)| *unit == &rest[..unit_end])
+            .ok_or_else(invalid)?;
+        total += number * scale;
+        rest = &rest[unit_end..];
+    }
+    Ok(total as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = concat!(
+        "name: analytics\n",
+        "services:\n",
+        "  bootstrap:\n",
+        "    build:\n",
+        "      context: /srv/analytics/bootstrap\n",
+        "    command: /bin/sh /app/init.sh\n",
+        "    volumes:\n",
+        "      - type: volume\n",
+        "        source: data\n",
+        "        target: /app/data\n",
+        "  qdrant:\n",
+        "    image: qdrant/qdrant:v1.11.0\n",
+        "    expose:\n",
+        "      - \"6333\"\n",
+        "    healthcheck:\n",
+        "      test: [\"CMD\", \"wget\", \"-qO-\", \"http://localhost:6333/readyz\"]\n",
+        "      interval: 1m30s\n",
+        "      retries: 3\n",
+        "    networks:\n",
+        "      analytics: null\n",
+        "      shared:\n",
+        "        aliases: [vectors]\n",
+        "    volumes:\n",
+        "      - type: volume\n",
+        "        source: qdrant_data\n",
+        "        target: /qdrant/storage\n",
+        "    ulimits:\n",
+        "      nofile: 65536\n",
+        "  analytics-ui:\n",
+        "    image: ghcr.io/h-pun/analytics-ui:latest\n",
+        "    platform: linux/amd64\n",
+        "    depends_on:\n",
+        "      qdrant:\n",
+        "        condition: service_healthy\n",
+        "    environment:\n",
+        "      PORT: \"3000\"\n",
+        "    ports:\n",
+        "      - target: 3000\n",
+        "        published: \"3000\"\n",
+        "        host_ip: 127.0.0.1\n",
+        "        protocol: tcp\n",
+        "    volumes:\n",
+        "      - type: bind\n",
+        "        source: /srv/analytics/config.yaml\n",
+        "        target: /app/config.yaml\n",
+        "        read_only: true\n",
+        "    networks:\n",
+        "      analytics: null\n",
+        "    restart: on-failure:3\n",
+        "networks:\n",
+        "  analytics:\n",
+        "    name: analytics_analytics\n",
+        "  shared:\n",
+        "    name: shared\n",
+        "    external: true\n",
+        "volumes:\n",
+        "  data:\n",
+        "    name: analytics_data\n",
+        "  qdrant_data:\n",
+        "    name: analytics_qdrant_data\n",
+    );
+
+    fn service<'a>(project: &'a Project, name: &str) -> &'a ProjectService {
+        project
+            .services
+            .iter()
+            .find(|service| service.name == name)
+            .unwrap()
+    }
+
+    #[test]
+    fn config_output_becomes_create_requests() {
+        let project = Project::parse(CONFIG, "ignored", '-', Path::new("/srv/analytics")).unwrap();
+        assert_eq!(project.name, "analytics");
+
+        let names = |resources: &[Resource]| -> Vec<String> {
+            resources
+                .iter()
+                .map(|resource| resource.name.clone())
+                .collect()
+        };
+        assert_eq!(
+            names(&project.networks),
+            ["analytics_default", "analytics_analytics"]
+        );
+        assert_eq!(
+            names(&project.volumes),
+            ["analytics_data", "analytics_qdrant_data"]
+        );
+        assert_eq!(
+            project.volumes[0].body["Labels"]["com.docker.compose.volume"],
+            "data"
+        );
+
+        let order: Vec<&str> = project
+            .start_order()
+            .iter()
+            .map(|service| service.name.as_str())
+            .collect();
+        assert_eq!(order, ["bootstrap", "qdrant", "analytics-ui"]);
+
+        let bootstrap = service(&project, "bootstrap");
+        assert!(bootstrap.built);
+        assert_eq!(bootstrap.image, "analytics-bootstrap");
+        assert_eq!(bootstrap.container_name, "analytics-bootstrap-1");
+        assert_eq!(bootstrap.body["Cmd"], json!(["/bin/sh", "/app/init.sh"]));
+        assert_eq!(
+            bootstrap.body["HostConfig"]["NetworkMode"],
+            "analytics_default"
+        );
+
+        let qdrant = service(&project, "qdrant");
+        assert!(qdrant.healthcheck);
+        assert_eq!(qdrant.ignored, ["ulimits"]);
+        assert_eq!(qdrant.body["Healthcheck"]["Interval"], 90_000_000_000u64);
+        assert_eq!(qdrant.body["ExposedPorts"], json!({ "6333/tcp": {} }));
+        assert_eq!(
+            qdrant.networks,
+            [
+                (
+                    "analytics_analytics".to_string(),
+                    json!({ "Aliases": ["qdrant"] })
+                ),
+                (
+                    "shared".to_string(),
+                    json!({ "Aliases": ["qdrant", "vectors"] })
+                ),
+            ]
+        );
+
+        let ui = service(&project, "analytics-ui");
+        assert!(!ui.built);
+        assert_eq!(ui.platform.as_deref(), Some("linux/amd64"));
+        assert_eq!(ui.depends_on, ["qdrant"]);
+        assert_eq!(ui.body["Env"], json!(["PORT=3000"]));
+        assert_eq!(
+            ui.body["HostConfig"]["PortBindings"],
+            json!({ "3000/tcp": [{ "HostIp": "127.0.0.1", "HostPort": "3000" }] })
+        );
+        assert_eq!(
+            ui.body["HostConfig"]["Binds"],
+            json!(["/srv/analytics/config.yaml:/app/config.yaml:ro"])
+        );
+        assert_eq!(
+            ui.body["HostConfig"]["RestartPolicy"],
+            json!({ "Name": "on-failure", "MaximumRetryCount": 3 })
+        );
+        assert_eq!(
+            ui.body["Labels"]["com.docker.compose.service"],
+            "analytics-ui"
+        );
+    }
+
+    #[test]
+    fn short_syntax_definitions() {
+        let cases = [
+            (
+                "short ports",
+                "ports: [\"8080:80\", \"127.0.0.1::53/udp\", 9000]",
+                "/HostConfig/PortBindings",
+                json!({
+                    "80/tcp": [{ "HostIp": "", "HostPort": "8080" }],
+                    "53/udp": [{ "HostIp": "127.0.0.1", "HostPort": "" }],
+                }),
+            ),
+            (
+                "port range",
+                "ports: [\"7000-7001:8000-8001\"]",
+                "/HostConfig/PortBindings",
+                json!({
+                    "8000/tcp": [{ "HostIp": "", "HostPort": "7000" }],
+                    "8001/tcp": [{ "HostIp": "", "HostPort": "7001" }],
+                }),
+            ),
+            (
+                "relative bind and named volume",
+                "volumes: [\"./data:/app/data:ro\", \"cache:/cache\", \"/scratch\"]",
+                "/HostConfig",
+                json!({
+                    "Binds": ["/srv/analytics/data:/app/data:ro"],
+                    "Mounts": [
+                        { "Type": "volume", "Source": "analytics_cache", "Target": "/cache", "ReadOnly": false },
+                        { "Type": "volume", "Target": "/scratch", "ReadOnly": false },
+                    ],
+                    "NetworkMode": "analytics_default",
+                }),
+            ),
+            (
+                "environment list",
+                "environment: [\"A=1\", \"B=x=y\"]",
+                "/Env",
+                json!(["A=1", "B=x=y"]),
+            ),
+            (
+                "quoted command",
+                "command: sh -c 'echo \"hi there\"'",
+                "/Cmd",
+                json!(["sh", "-c", "echo \"hi there\""]),
+            ),
+            (
+                "shell healthcheck",
+                "healthcheck:\n      test: curl -f http://localhost\n      timeout: 500ms",
+                "/Healthcheck",
+                json!({ "Test": ["CMD-SHELL", "curl -f http://localhost"], "Timeout": 500_000_000 }),
+            ),
+            (
+                "disabled healthcheck",
+                "healthcheck:\n      disable: true",
+                "/Healthcheck",
+                json!({ "Test": ["NONE"] }),
+            ),
+            (
+                "network mode",
+                "network_mode: host",
+                "/HostConfig/NetworkMode",
+                json!("host"),
+            ),
+            (
+                "extra hosts",
+                "extra_hosts:\n      host.docker.internal: host-gateway",
+                "/HostConfig/ExtraHosts",
+                json!(["host.docker.internal:host-gateway"]),
+            ),
+        ];
+
+        for (name, definition, pointer, expected) in cases {
+            let content = format!("services:\n  app:\n    image: app\n    {}\n", definition);
+            let project =
+                Project::parse(&content, "analytics", '_', Path::new("/srv/analytics")).unwrap();
+            let app = &project.services[0];
+            assert_eq!(app.container_name, "analytics_app_1", "{}", name);
+            assert_eq!(app.body.pointer(pointer), Some(&expected), "{}", name);
+        }
+    }
+
+    #[test]
+    fn invalid_definitions_are_errors() {
+        let cases = [
+            (
+                "ports: [\"http:80\"]",
+                "service app publishes an invalid port http:80",
+            ),
+            ("restart: sometimes", "invalid restart policy sometimes"),
+            (
+                "healthcheck:\n      interval: soon",
+                "service app healthcheck: invalid duration \"soon\"",
+            ),
+            (
+                "volumes:\n      - type: npipe\n        source: x\n        target: /x",
+                "service app uses a npipe mount, which the engine API backend does not create",
+            ),
+        ];
+
+        for (definition, expected) in cases {
+            let content = format!("services:\n  app:\n    image: app\n    {}\n", definition);
+            let error = Project::parse(&content, "analytics", '-', Path::new("/srv"))
+                .unwrap_err()
+                .to_string();
+            assert_eq!(error, expected);
+        }
+    }
+
+    #[test]
+    fn durations() {
+        for (value, expected) in [
+            ("30s", 30_000_000_000),
+            ("1m30s", 90_000_000_000),
+            ("1.5s", 1_500_000_000),
+            ("250ms", 250_000_000),
+            ("2h", 7_200_000_000_000),
+        ] {
+            assert_eq!(parse_duration(value).unwrap(), expected, "{}", value);
+        }
+        for value in ["", "10", "5 minutes", "s"] {
+            assert!(parse_duration(value).is_err(), "{}", value);
+        }
+    }
+}
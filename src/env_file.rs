@@ -0,0 +1,212 @@
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone)]
+enum EnvLine {
+    Entry { key: String, value: String },
+    Other(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct EnvFile {
+    lines: Vec<EnvLine>,
+}
+
+#[derive(Debug, Clone)]
+pub struct EnvMerge {
+    pub content: String,
+    pub changes: Vec<String>,
+}
+
+impl EnvFile {
+    pub fn load(path: &Path) -> Option<Self> {
+        fs::read_to_string(path)
+            .ok()
+            .map(|content| Self::parse(&content))
+    }
+
+    pub fn parse(content: &str) -> Self {
+        let lines = content
+            .lines()
+            .map(|line| {
+                let trimmed = line.trim_start();
+                match trimmed.split_once('=') {
+                    Some((key, value)) if !trimmed.starts_with('#') && is_key(key.trim()) => {
+                        EnvLine::Entry {
+                            key: key.trim().to_string(),
+                            value: value.to_string(),
+                        }
+                    }
+                    _ => EnvLine::Other(line.to_string()),
+                }
+            })
+            .collect();
+        Self { lines }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.lines.iter().find_map(|line| match line {
+            EnvLine::Entry {
+                key: existing,
+                value,
+            } if existing == key => Some(value.as_str()),
+            _ => None,
+        })
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.lines.iter().filter_map(|line| match line {
+            EnvLine::Entry { key, .. } => Some(key.as_str()),
+            EnvLine::Other(_) => None,
+        })
+    }
+
+    pub fn set(&mut self, key: &str, value: &str) {
+        let existing = self.lines.iter_mut().find_map(|line| match line {
+            EnvLine::Entry {
+                key: existing,
+                value,
+            } if existing == key => Some(value),
+            _ => None,
+        });
+        match existing {
+            Some(existing) => *existing = value.to_string(),
+            None => self.lines.push(EnvLine::Entry {
+                key: key.to_string(),
+                value: value.to_string(),
+            }),
+        }
+    }
+
+    pub fn append_section(&mut self, entries: &[(String, String)]) {
+        if entries.is_empty() {
+            return;
+        }
+        if self.lines.last().is_some_and(|line| !is_blank(line)) {
+            self.lines.push(EnvLine::Other(String::new()));
+        }
+        for (key, value) in entries {
+            self.set(key, value);
+        }
+    }
+
+    pub fn render(&self) -> String {
+        let mut content: String = self
+            .lines
+            .iter()
+            .map(|line| match line {
+                EnvLine::Entry { key, value } => format!("{}={}\n", key, value),
+                EnvLine::Other(text) => format!("{}\n", text),
+            })
+            .collect();
+        if content.is_empty() {
+            content.push('\n');
+        }
+        content
+    }
+
+    pub fn merge(&self, generated: &EnvFile, owned: &[String]) -> EnvMerge {
+        let mut merged = self.clone();
+        let mut changes = Vec::new();
+        let mut missing = Vec::new();
+
+        for key in generated.keys() {
+            let value = generated.get(key).unwrap_or_default();
+            match self.get(key) {
+                None => {
+                    missing.push((key.to_string(), value.to_string()));
+                    changes.push(format!("added {}", key));
+                }
+                Some(current) if current != value => {
+                    let is_owned = owned.iter().any(|owned| owned == key);
+                    if is_owned && !value.trim().is_empty() {
+                        merged.set(key, value);
+                        changes.push(format!("updated {}", key));
+                    }
+                }
+                Some(_) => {}
+            }
+        }
+
+        merged.append_section(&missing);
+
+        let custom: Vec<&str> = self
+            .keys()
+            .filter(|key| generated.get(key).is_none())
+            .collect();
+        if !custom.is_empty() {
+            changes.push(format!(
+                "kept {} custom key(s): {}",
+                custom.len(),
+                custom.join(", ")
+            ));
+        }
+
+        EnvMerge {
+            content: merged.render(),
+            changes,
+        }
+    }
+}
+
+fn is_blank(line: &EnvLine) -> bool {
+    matches!(line, EnvLine::Other(text) if text.trim().is_empty())
+}
+
+fn is_key(key: &str) -> bool {
+    !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn owned(keys: &[&str]) -> Vec<String> {
+        keys.iter().map(|key| key.to_string()).collect()
+    }
+
+    #[test]
+    fn merge_keeps_comments_and_unknown_keys() {
+        let existing = EnvFile::parse(
+            "# ports\nHOST_PORT=3005\n\n# mine\nMY_PROXY=http://proxy:3128\nGENERATION_MODEL=gpt-4o\n",
+        );
+        let generated = EnvFile::parse("HOST_PORT=3005\nGENERATION_MODEL=gpt-4o\n");
+
+        let merge = existing.merge(&generated, &owned(&["HOST_PORT", "GENERATION_MODEL"]));
+
+        assert_eq!(
+            merge.content,
+            "# ports\nHOST_PORT=3005\n\n# mine\nMY_PROXY=http://proxy:3128\nGENERATION_MODEL=gpt-4o\n"
+        );
+        assert_eq!(merge.changes, ["kept 1 custom key(s): MY_PROXY"]);
+    }
+
+    #[test]
+    fn merge_updates_owned_keys_only() {
+        let existing =
+            EnvFile::parse("HOST_PORT=3005\nQDRANT_HOST=my-qdrant\nOPENAI_API_KEY=sk-old\n");
+        let generated = EnvFile::parse("HOST_PORT=3010\nQDRANT_HOST=qdrant\nOPENAI_API_KEY=\n");
+
+        let merge = existing.merge(&generated, &owned(&["HOST_PORT", "OPENAI_API_KEY"]));
+
+        assert_eq!(
+            merge.content,
+            "HOST_PORT=3010\nQDRANT_HOST=my-qdrant\nOPENAI_API_KEY=sk-old\n"
+        );
+        assert_eq!(merge.changes, ["updated HOST_PORT"]);
+    }
+
+    #[test]
+    fn merge_appends_missing_keys_in_a_section() {
+        let existing = EnvFile::parse("# keep me\nHOST_PORT=3000\n");
+        let generated = EnvFile::parse("HOST_PORT=3000\nPLATFORM=linux/arm64\n");
+
+        let merge = existing.merge(&generated, &owned(&["HOST_PORT", "PLATFORM"]));
+
+        assert_eq!(
+            merge.content,
+            "# keep me\nHOST_PORT=3000\n\nPLATFORM=linux/arm64\n"
+        );
+        assert_eq!(merge.changes, ["added PLATFORM"]);
+    }
+}
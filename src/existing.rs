@@ -0,0 +1,115 @@
+use color_eyre::{Result, eyre::eyre};
+use serde::Deserialize;
+use std::fs;
+
+use crate::settings::Settings;
+use crate::templates::{Pipe, TemplateCatalog};
+use crate::validate;
+
+#[derive(Debug, Clone)]
+pub enum ConfigOrigin {
+    Template(String),
+    Custom,
+}
+
+#[derive(Debug, Clone)]
+pub struct ExistingConfig {
+    pub origin: ConfigOrigin,
+    pub placeholders: Vec<(String, String)>,
+    pub pipes: Vec<Pipe>,
+    pub settings: Option<Settings>,
+    pub settings_warnings: Vec<(String, String)>,
+}
+
+pub fn load(catalog: &TemplateCatalog) -> Result<ExistingConfig> {
+    let content = fs::read_to_string(validate::config_path())?;
+    parse(&content, catalog)
+}
+
+pub fn parse(content: &str, catalog: &TemplateCatalog) -> Result<ExistingConfig> {
+    let documents = serde_yaml::Deserializer::from_str(content)
+        .map(serde_yaml::Value::deserialize)
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|e| eyre!("config.yaml is not valid YAML: {}", e))?;
+
+    let (origin, placeholders) = catalog
+        .templates
+        .iter()
+        .find_map(|template| {
+            template
+                .match_documents(&documents)
+                .map(|captures| (ConfigOrigin::Template(template.key.clone()), captures))
+        })
+        .unwrap_or((ConfigOrigin::Custom, Vec::new()));
+
+    let pipes = documents
+        .iter()
+        .filter(|document| document.get("type").and_then(|kind| kind.as_str()) == Some("pipeline"))
+        .filter_map(|document| document.get("pipes").cloned())
+        .filter_map(|pipes| serde_yaml::from_value::<Vec<Pipe>>(pipes).ok())
+        .flatten()
+        .collect();
+
+    let (settings, settings_warnings) = documents
+        .iter()
+        .filter_map(|document| {
+            document
+                .get("settings")
+                .and_then(|settings| settings.as_mapping())
+        })
+        .map(Settings::from_config)
+        .next()
+        .map(|(settings, warnings)| (Some(settings), warnings))
+        .unwrap_or_default();
+
+    Ok(ExistingConfig {
+        origin,
+        placeholders,
+        pipes,
+        settings,
+        settings_warnings,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settings_keep_known_keys() {
+        let content = "\
+type: pipeline
+pipes:
+  - name: sql_generation
+    llm: litellm_llm.default
+---
+settings:
+  engine_timeout: 60
+  logging_level: DEBUG
+  query_cache_ttl: not-a-number
+  added_in_a_newer_release: true
+";
+        let existing = parse(content, &TemplateCatalog::built_in()).unwrap();
+        let settings = existing.settings.unwrap();
+        assert_eq!(settings.engine_timeout, 60);
+        assert_eq!(settings.logging_level, "DEBUG");
+        assert_eq!(
+            settings.query_cache_ttl,
+            Settings::default().query_cache_ttl
+        );
+        assert_eq!(
+            existing.settings_warnings,
+            [
+                (
+                    "query_cache_ttl".to_string(),
+                    "query_cache_ttl must be a whole number, using the default 3600".to_string()
+                ),
+                (
+                    "added_in_a_newer_release".to_string(),
+                    "unknown setting 'added_in_a_newer_release' is dropped".to_string()
+                ),
+            ]
+        );
+        assert_eq!(existing.pipes.len(), 1);
+    }
+}
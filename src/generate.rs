@@ -0,0 +1,171 @@
+use color_eyre::{Result, eyre::eyre};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::app::form_data::{
+    DEFAULT_AI_SERVICE_PORT, DEFAULT_GENERATION_MODEL, DEFAULT_HOST_PORT, FormData,
+};
+use crate::backup::{self, Written};
+use crate::compose;
+use crate::diff::{self, DiffLine};
+use crate::env_file::{EnvFile, EnvMerge};
+use crate::platform;
+use crate::settings::Settings;
+use crate::stack::{self, Stack, StackOptions};
+use crate::templates::{ConfigTemplate, Pipe};
+use crate::utils;
+use crate::validate;
+
+const OWNED_ENV_KEYS: &[&str] = &[
+    "ANALYTICS_AI_SERVICE_PORT",
+    "GENERATION_MODEL",
+    "HOST_PORT",
+    "AI_SERVICE_FORWARD_PORT",
+    "PLATFORM",
+];
+
+#[derive(Debug, Clone)]
+pub struct PendingFile {
+    pub path: PathBuf,
+    pub content: String,
+    pub diff: Vec<DiffLine>,
+    pub is_new: bool,
+    pub notes: Vec<String>,
+}
+
+impl PendingFile {
+    pub fn new(path: PathBuf, content: String) -> Self {
+        let (diff, is_new) = match fs::read_to_string(&path) {
+            Ok(current) => (diff::unified(&current, &content, 3), false),
+            Err(_) => (Vec::new(), true),
+        };
+        Self {
+            path,
+            content,
+            diff,
+            is_new,
+            notes: Vec::new(),
+        }
+    }
+
+    pub fn with_notes(mut self, notes: Vec<String>) -> Self {
+        self.notes = notes;
+        self
+    }
+
+    pub fn write(&self) -> Result<Written> {
+        backup::write_file(&self.path, &self.content)
+    }
+}
+
+pub fn env_path() -> PathBuf {
+    utils::project_root().join(".env")
+}
+
+pub fn generate_env_file(form_data: &FormData) -> Result<(Written, Vec<String>)> {
+    let merge = env_content(form_data);
+    let written = backup::write_file(&env_path(), &merge.content)?;
+    Ok((written, merge.changes))
+}
+
+pub fn env_content(form_data: &FormData) -> EnvMerge {
+    let generated = render_env(form_data);
+    match EnvFile::load(&env_path()) {
+        Some(existing) => existing.merge(&EnvFile::parse(&generated), &owned_env_keys(form_data)),
+        None => EnvMerge {
+            content: generated,
+            changes: Vec::new(),
+        },
+    }
+}
+
+fn owned_env_keys(form_data: &FormData) -> Vec<String> {
+    let mut keys: Vec<String> = OWNED_ENV_KEYS.iter().map(|key| key.to_string()).collect();
+    keys.extend(
+        form_data
+            .credentials
+            .iter()
+            .map(|credential| credential.env_var.clone()),
+    );
+    keys.extend(form_data.port_overrides.iter().map(|(key, _)| key.clone()));
+    keys
+}
+
+fn render_env(form_data: &FormData) -> String {
+    let vendor_keys: Vec<String> = form_data
+        .credential_pairs()
+        .map(|(env_var, value)| format!("{}={}", env_var, value))
+        .collect();
+    let content = fill_env_template(
+        &form_data.ai_service_port,
+        &vendor_keys.join("\n"),
+        &form_data.resolved_generation_model(),
+        &form_data.host_port,
+        &form_data.platform,
+    );
+    if form_data.port_overrides.is_empty() {
+        return content;
+    }
+
+    let mut env = EnvFile::parse(&content);
+    for (key, value) in &form_data.port_overrides {
+        env.set(key, value);
+    }
+    env.render()
+}
+
+pub fn default_env() -> EnvFile {
+    EnvFile::parse(&fill_env_template(
+        DEFAULT_AI_SERVICE_PORT,
+        "",
+        DEFAULT_GENERATION_MODEL,
+        DEFAULT_HOST_PORT,
+        platform::host_platform(),
+    ))
+}
+
+fn fill_env_template(
+    ai_service_port: &str,
+    vendor_keys: &str,
+    generation_model: &str,
+    host_port: &str,
+    platform: &str,
+) -> String {
+    let uuid_fragment = uuid::Uuid::new_v4()
+        .to_string()
+        .split('-')
+        .next()
+        .unwrap_or("123")
+        .to_string();
+    let user_uuid = format!("demo-user-{}", uuid_fragment);
+
+    let mut env_content = utils::ENV_TEMPLATE.to_string();
+    env_content = env_content.replace("{{ANALYTICS_AI_SERVICE_PORT}}", ai_service_port);
+    env_content = env_content.replace("{{VENDOR_KEYS}}", vendor_keys);
+    env_content = env_content.replace("{{USER_UUID}}", user_uuid.as_str());
+    env_content = env_content.replace("{{GENERATION_MODEL}}", generation_model);
+    env_content = env_content.replace("{{HOST_PORT}}", host_port);
+    env_content = env_content.replace("{{AI_SERVICE_FORWARD_PORT}}", ai_service_port);
+    env_content = env_content.replace("{{PLATFORM}}", platform);
+
+    env_content
+}
+
+pub fn compose_path() -> PathBuf {
+    utils::project_root().join(compose::COMPOSE_FILE)
+}
+
+pub fn write_compose_file(options: &StackOptions) -> Result<Written> {
+    stack::validate_image_tag(&options.qdrant_version).map_err(|e| eyre!("{}", e))?;
+    backup::write_file(&compose_path(), &Stack::new(options).render()?)
+}
+
+pub fn write_config_yaml(
+    template: &ConfigTemplate,
+    placeholders: &[(String, String)],
+    pipes: &[Pipe],
+    settings: &Settings,
+) -> Result<Written> {
+    let content = template.render(placeholders, pipes, settings)?;
+    backup::write_file(&validate::config_path(), &content)
+}
@@ -0,0 +1,748 @@
+use color_eyre::{Result, eyre::eyre};
+use std::collections::{HashMap, HashSet};
+use std::process::Stdio;
+#[cfg(unix)]
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio::task::JoinHandle;
+
+use crate::compose;
+#[cfg(unix)]
+use crate::engine::{EngineClient, EngineEvent, Project, PullMessage};
+use crate::generate;
+use crate::progress::{self, ContainerFailure, ContainerState, ProgressEvent};
+use crate::runtime::{ContainerRuntime, RuntimeKind};
+use crate::stack::StackOptions;
+#[cfg(unix)]
+use crate::utils;
+
+#[cfg(unix)]
+const SETTLE_TIMEOUT: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InstallOptions {
+    pub runtime: Option<RuntimeKind>,
+    pub engine_api: bool,
+}
+
+pub enum Backend {
+    Compose,
+    #[cfg(unix)]
+    EngineApi(EngineClient),
+}
+
+impl Backend {
+    #[cfg(unix)]
+    pub fn new(options: InstallOptions) -> Result<Self> {
+        if options.engine_api {
+            EngineClient::from_env().map(Self::EngineApi)
+        } else {
+            Ok(Self::Compose)
+        }
+    }
+
+    #[cfg(not(unix))]
+    pub fn new(options: InstallOptions) -> Result<Self> {
+        if options.engine_api {
+            Err(eyre!(ENGINE_API_UNSUPPORTED))
+        } else {
+            Ok(Self::Compose)
+        }
+    }
+}
+
+#[cfg(not(unix))]
+pub const ENGINE_API_UNSUPPORTED: &str =
+    "--engine-api is not supported on this platform, it talks to the engine over a unix socket";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum InstallEvent {
+    Log(String),
+    Service(String),
+    Progress {
+        progress: f64,
+        completed_services: usize,
+        total_services: usize,
+    },
+    ContainerFailed {
+        container: String,
+        failure: ContainerFailure,
+    },
+    Finished(Result<(), String>),
+}
+
+#[derive(Debug)]
+pub struct InstallProgress {
+    pub(crate) logs: Vec<String>,
+    pub(crate) progress: f64,
+    pub(crate) current_service: String,
+    pub(crate) total_services: usize,
+    pub(crate) completed_services: usize,
+}
+
+impl InstallProgress {
+    pub fn new() -> Self {
+        Self {
+            logs: Vec::new(),
+            progress: 0.0,
+            current_service: String::new(),
+            total_services: 0,
+            completed_services: 0,
+        }
+    }
+
+    pub fn apply(&mut self, event: &InstallEvent) {
+        match event {
+            InstallEvent::Log(message) => self.add_log(message),
+            InstallEvent::Service(service) => self.current_service = service.clone(),
+            InstallEvent::Progress {
+                progress,
+                completed_services,
+                total_services,
+            } => {
+                self.progress = *progress;
+                self.completed_services = *completed_services;
+                self.total_services = *total_services;
+            }
+            InstallEvent::ContainerFailed { container, failure } => {
+                self.add_log(&format!("❌ {} {}", container, failure))
+            }
+            InstallEvent::Finished(_) => {}
+        }
+    }
+
+    pub fn add_log(&mut self, message: &str) {
+        self.logs.push(message.to_string());
+
+        if self.logs.len() > 100 {
+            self.logs.remove(0);
+        }
+    }
+}
+
+pub fn spawn(
+    runtime: Box<dyn ContainerRuntime>,
+    backend: Backend,
+) -> (JoinHandle<()>, UnboundedReceiver<InstallEvent>) {
+    let (tx, rx) = mpsc::unbounded_channel();
+
+    let handle = tokio::spawn(async move {
+        let mut installer = Installer::new(tx.clone(), runtime);
+        let result = match backend {
+            Backend::Compose => installer.run_docker_compose().await,
+            #[cfg(unix)]
+            Backend::EngineApi(engine) => installer.run_engine_api(&engine).await,
+        }
+        .map_err(|e| e.to_string());
+        let _ = tx.send(InstallEvent::Finished(result));
+    });
+
+    (handle, rx)
+}
+
+struct Installer {
+    tx: UnboundedSender<InstallEvent>,
+    runtime: Box<dyn ContainerRuntime>,
+    services: Vec<String>,
+    project: String,
+    total_services: usize,
+    completed_services: usize,
+    tracker: ProgressTracker,
+}
+
+#[derive(Default)]
+struct ProgressTracker {
+    span: (f64, f64),
+    building: bool,
+    build_steps: HashMap<String, (HashSet<u32>, u32)>,
+    layers: HashMap<String, (u64, u64)>,
+    started: Vec<String>,
+    failures: Vec<String>,
+    progress: f64,
+}
+
+impl ProgressTracker {
+    fn new(args: &[&str], span: (f64, f64)) -> Self {
+        Self {
+            span,
+            building: args.first() == Some(&"build"),
+            progress: span.0,
+            ..Self::default()
+        }
+    }
+
+    fn fraction(&self, total_services: usize) -> f64 {
+        if self.building {
+            if self.build_steps.is_empty() {
+                return 0.0;
+            }
+            let done: f64 = self
+                .build_steps
+                .values()
+                .map(|(steps, total)| steps.len() as f64 / (*total).max(1) as f64)
+                .sum();
+            return done / self.build_steps.len() as f64;
+        }
+
+        let started = self.started.len() as f64 / total_services.max(1) as f64;
+        let (current, total) = self
+            .layers
+            .values()
+            .fold((0, 0), |(current, total), layer| {
+                (current + layer.0, total + layer.1)
+            });
+        if total > 0 {
+            0.5 * current as f64 / total as f64 + 0.5 * started
+        } else {
+            started
+        }
+    }
+}
+
+impl Installer {
+    fn new(tx: UnboundedSender<InstallEvent>, runtime: Box<dyn ContainerRuntime>) -> Self {
+        Self {
+            tx,
+            runtime,
+            services: Vec::new(),
+            project: compose::project_name(),
+            total_services: 0,
+            completed_services: 0,
+            tracker: ProgressTracker::default(),
+        }
+    }
+
+    fn prepare_files(&mut self) -> Result<Vec<String>> {
+        if !generate::compose_path().exists() {
+            let written = generate::write_compose_file(&StackOptions::default())?;
+            self.add_log(&format!(
+                "🧩 Generated {} from the default service model",
+                written.path.display()
+            ));
+        }
+
+        if let Some(ports_file) = compose::write_ports_override(self.runtime.as_ref())? {
+            self.add_log(&format!(
+                "🔧 Publishing the ports from .env via {}",
+                ports_file.display()
+            ));
+        }
+        Ok(compose::file_args())
+    }
+
+    async fn load_services(&mut self, files: &[String]) {
+        let listed = self
+            .runtime
+            .command(files, &["config", "--services"])
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()
+            .await
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| {
+                String::from_utf8_lossy(&output.stdout)
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty())
+                    .map(str::to_string)
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        self.services = if listed.is_empty() {
+            compose::service_names()
+        } else {
+            listed
+        };
+        self.total_services = self.services.len();
+        self.add_log(&format!(
+            "📋 {} services: {}",
+            self.total_services,
+            self.services.join(", ")
+        ));
+        self.set_progress(0.0);
+    }
+
+    async fn run_docker_compose(&mut self) -> Result<()> {
+        let files = self.prepare_files()?;
+        self.load_services(&files).await;
+
+        self.add_log("🔨 Step 1/2: Building images (no cache)...");
+        self.run_step(&files, &["build", "--no-cache"], (0.0, 50.0))
+            .await?;
+        self.add_log("✅ Build completed successfully!");
+        self.set_progress(50.0);
+
+        self.add_log("🚀 Step 2/2: Starting services...");
+        self.run_step(&files, &["up", "-d"], (50.0, 100.0)).await?;
+        self.add_log("✅ All services started successfully!");
+        self.set_progress(100.0);
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    async fn run_engine_api(&mut self, engine: &EngineClient) -> Result<()> {
+        let version = engine.version().await?;
+        self.add_log(&format!(
+            "🐳 Docker Engine {} via {}",
+            version,
+            engine.socket().display()
+        ));
+        let files = self.prepare_files()?;
+        self.load_services(&files).await;
+        let project = self.resolve_project(&files).await?;
+        for service in &project.services {
+            if !service.ignored.is_empty() {
+                self.add_log(&format!(
+                    "⚠️  {}: {} not applied by the engine API backend",
+                    service.name,
+                    service.ignored.join(", ")
+                ));
+            }
+        }
+
+        self.add_log("🔨 Step 1/4: Building images (no cache)...");
+        self.run_step(&files, &["build", "--no-cache"], (0.0, 20.0))
+            .await?;
+        self.add_log("✅ Build completed successfully!");
+        self.set_progress(20.0);
+
+        self.add_log("⬇️  Step 2/4: Pulling images...");
+        let pulls: Vec<_> = project
+            .services
+            .iter()
+            .filter(|service| !service.built)
+            .collect();
+        for (index, service) in pulls.iter().enumerate() {
+            self.set_service(&service.name);
+            self.add_log(&format!(
+                "⬇️  Pulling {} for {}...",
+                service.image, service.name
+            ));
+            let start = 20.0 + 40.0 * index as f64 / pulls.len() as f64;
+            let share = 40.0 / pulls.len() as f64;
+            let mut layers: HashMap<String, (u64, u64)> = HashMap::new();
+            engine
+                .pull(&service.image, service.platform.as_deref(), |message| {
+                    track_layer(&mut layers, message);
+                    let (current, total) =
+                        layers.values().fold((0, 0), |(current, total), layer| {
+                            (current + layer.0, total + layer.1)
+                        });
+                    if total > 0 {
+                        self.set_progress(start + share * current as f64 / total as f64);
+                    }
+                })
+                .await?;
+            let size: u64 = layers.values().map(|layer| layer.1).sum();
+            self.add_log(&format!(
+                "✓ Pulled {} ({:.1} MB downloaded)",
+                service.image,
+                size as f64 / 1_000_000.0
+            ));
+        }
+        self.set_progress(60.0);
+
+        self.add_log("🔨 Step 3/4: Creating containers...");
+        let containers = self.create_containers(engine, &project).await?;
+        self.set_progress(70.0);
+
+        self.add_log("🚀 Step 4/4: Starting services...");
+        let mut events = engine.events(&project.name).await?;
+        let tx = self.tx.clone();
+        let mut pending: HashMap<String, bool> = project
+            .services
+            .iter()
+            .map(|service| (service.name.clone(), service.healthcheck))
+            .collect();
+        let mut watcher = tokio::spawn(async move {
+            while !pending.is_empty() {
+                let Ok(Some(line)) = events.next_line().await else {
+                    break;
+                };
+                let Ok(event) = serde_json::from_str::<EngineEvent>(&line) else {
+                    continue;
+                };
+                if let Some(failure) = event.failure() {
+                    let container = event.attribute("name").unwrap_or(event.service());
+                    let message = format!("{} {}", container, failure);
+                    let _ = tx.send(InstallEvent::ContainerFailed {
+                        container: container.to_string(),
+                        failure,
+                    });
+                    return Err(message);
+                }
+                if let Some(message) = describe_event(&event) {
+                    let _ = tx.send(InstallEvent::Log(message));
+                }
+                let settled = match event.action.as_str() {
+                    "start" => pending.get(event.service()) == Some(&false),
+                    action => action.starts_with("health_status") || action == "die",
+                };
+                if settled {
+                    pending.remove(event.service());
+                }
+            }
+            Ok(())
+        });
+        let started = self.start_containers(engine, &containers).await;
+        let settled = if started.is_ok() {
+            if !watcher.is_finished() {
+                self.add_log("⏳ Waiting for the containers to report started or healthy...");
+            }
+            match tokio::time::timeout(SETTLE_TIMEOUT, &mut watcher).await {
+                Ok(Ok(settled)) => settled,
+                Ok(Err(_)) => Ok(()),
+                Err(_) => {
+                    self.add_log(&format!(
+                        "⚠️  Not every container reported started or healthy within {}s",
+                        SETTLE_TIMEOUT.as_secs()
+                    ));
+                    Ok(())
+                }
+            }
+        } else {
+            Ok(())
+        };
+        watcher.abort();
+        started?;
+        settled.map_err(|e| eyre!(e))?;
+
+        self.add_log("✅ All services started successfully!");
+        self.set_progress(100.0);
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    async fn resolve_project(&mut self, files: &[String]) -> Result<Project> {
+        let output = self
+            .runtime
+            .command(files, &["config"])
+            .stdin(Stdio::null())
+            .output()
+            .await
+            .map_err(|e| {
+                eyre!(
+                    "failed to run {}: {}",
+                    self.runtime.command_line(files, &["config"]),
+                    e
+                )
+            })?;
+        if !output.status.success() {
+            return Err(eyre!(
+                "{} failed: {}",
+                self.runtime.command_line(&[], &["config"]),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Project::parse(
+            &String::from_utf8_lossy(&output.stdout),
+            &self.project,
+            self.runtime.name_separator(),
+            &utils::project_root(),
+        )
+    }
+
+    #[cfg(unix)]
+    async fn create_containers(
+        &mut self,
+        engine: &EngineClient,
+        project: &Project,
+    ) -> Result<Vec<(String, String)>> {
+        for network in &project.networks {
+            engine
+                .create_network(&network.body)
+                .await
+                .map_err(|e| eyre!("failed to create network {}: {}", network.name, e))?;
+            self.add_log(&format!("🌐 Network {} ready", network.name));
+        }
+        for volume in &project.volumes {
+            engine
+                .create_volume(&volume.body)
+                .await
+                .map_err(|e| eyre!("failed to create volume {}: {}", volume.name, e))?;
+            self.add_log(&format!("💾 Volume {} ready", volume.name));
+        }
+
+        let existing = engine.containers(&project.name).await?;
+        let order = project.start_order();
+        self.total_services = order.len();
+        self.completed_services = 0;
+        let mut containers = Vec::new();
+        for service in order {
+            self.set_service(&service.name);
+            for container in existing
+                .iter()
+                .filter(|container| container.service() == service.name)
+            {
+                self.add_log(&format!(
+                    "♻️  Replacing {} container {}",
+                    container.state,
+                    container.name()
+                ));
+                engine
+                    .remove(&container.id)
+                    .await
+                    .map_err(|e| eyre!("failed to remove {}: {}", container.name(), e))?;
+            }
+
+            let id = engine
+                .create_container(
+                    &service.container_name,
+                    service.platform.as_deref(),
+                    &service.body,
+                )
+                .await
+                .map_err(|e| eyre!("failed to create {}: {}", service.container_name, e))?;
+            for (network, endpoint) in service.networks.iter().skip(1) {
+                engine.connect(network, &id, endpoint).await.map_err(|e| {
+                    eyre!(
+                        "failed to connect {} to {}: {}",
+                        service.container_name,
+                        network,
+                        e
+                    )
+                })?;
+            }
+            self.add_log(&format!("✓ Container {} created", service.container_name));
+            containers.push((service.container_name.clone(), id));
+            self.completed_services += 1;
+            self.set_progress(
+                60.0 + (self.completed_services as f64 / self.total_services.max(1) as f64) * 10.0,
+            );
+        }
+        Ok(containers)
+    }
+
+    #[cfg(unix)]
+    async fn start_containers(
+        &mut self,
+        engine: &EngineClient,
+        containers: &[(String, String)],
+    ) -> Result<()> {
+        self.completed_services = 0;
+        for (name, id) in containers {
+            self.set_service(name);
+            engine
+                .start(id)
+                .await
+                .map_err(|e| eyre!("failed to start {}: {}", name, e))?;
+            self.completed_services += 1;
+            self.set_progress(
+                70.0 + (self.completed_services as f64 / self.total_services.max(1) as f64) * 30.0,
+            );
+        }
+        Ok(())
+    }
+
+    async fn run_step(&mut self, files: &[String], args: &[&str], span: (f64, f64)) -> Result<()> {
+        self.add_log(&format!(
+            "📦 Executing: {}",
+            self.runtime.command_line(files, args)
+        ));
+        let status = self.run_compose_command(files, args, span).await?;
+        if !self.tracker.failures.is_empty() {
+            Err(eyre!("{}", self.tracker.failures.join(", ")))
+        } else if status.success() {
+            Ok(())
+        } else {
+            Err(eyre!("{} failed", self.runtime.command_line(&[], args)))
+        }
+    }
+
+    async fn run_compose_command(
+        &mut self,
+        files: &[String],
+        args: &[&str],
+        span: (f64, f64),
+    ) -> Result<std::process::ExitStatus> {
+        self.tracker = ProgressTracker::new(args, span);
+        let mut child = self
+            .runtime
+            .command(files, args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()?;
+
+        let stdout = child.stdout.take().expect("Failed to capture stdout");
+        let stderr = child.stderr.take().expect("Failed to capture stderr");
+
+        let mut stdout_reader = BufReader::new(stdout).lines();
+        let mut stderr_reader = BufReader::new(stderr).lines();
+
+        let (mut stdout_done, mut stderr_done) = (false, false);
+        while !(stdout_done && stderr_done) {
+            tokio::select! {
+                result = stdout_reader.next_line(), if !stdout_done => {
+                    match result {
+                        Ok(Some(line)) => self.handle_event(progress::parse_line(&line)),
+                        Ok(None) => stdout_done = true,
+                        Err(e) => {
+                            self.add_log(&format!("❌ Error reading stdout: {}", e));
+                            stdout_done = true;
+                        }
+                    }
+                }
+                result = stderr_reader.next_line(), if !stderr_done => {
+                    match result {
+                        Ok(Some(line)) => self.handle_event(progress::parse_line(&line)),
+                        Ok(None) => stderr_done = true,
+                        Err(e) => {
+                            self.add_log(&format!("❌ Error reading stderr: {}", e));
+                            stderr_done = true;
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(child.wait().await?)
+    }
+
+    fn handle_event(&mut self, event: ProgressEvent) {
+        match event {
+            ProgressEvent::Pulling { service } => {
+                self.set_service(&service);
+                self.add_log(&format!("⬇️  Pulling image for {}...", service));
+            }
+            ProgressEvent::Pulled { service } => {
+                self.add_log(&format!("✓ Image pulled for {}", service));
+            }
+            ProgressEvent::Layer {
+                layer,
+                current,
+                total,
+            } => {
+                self.tracker.layers.insert(layer, (current, total));
+            }
+            ProgressEvent::LayerDone { layer } => {
+                if let Some(layer) = self.tracker.layers.get_mut(&layer) {
+                    layer.0 = layer.1;
+                }
+            }
+            ProgressEvent::BuildStep {
+                service,
+                step,
+                total,
+                text,
+            } => {
+                let (steps, steps_total) =
+                    self.tracker.build_steps.entry(service.clone()).or_default();
+                *steps_total = total;
+                if steps.insert(step) {
+                    self.set_service(&service);
+                    self.add_log(&format!("🔨 [{} {}/{}] {}", service, step, total, text));
+                }
+            }
+            ProgressEvent::Container { name, state } => self.container_event(&name, state),
+            ProgressEvent::Failed { name, failure } => {
+                self.tracker.failures.push(format!("{} {}", name, failure));
+                let _ = self.tx.send(InstallEvent::ContainerFailed {
+                    container: name,
+                    failure,
+                });
+            }
+            ProgressEvent::Error(message) => self.add_log(&format!("❌ {}", message)),
+            ProgressEvent::Output(line) => {
+                if !line.is_empty() {
+                    self.add_log(&format!("ℹ️  {}", line));
+                }
+            }
+        }
+        self.update_progress();
+    }
+
+    fn container_event(&mut self, name: &str, state: ContainerState) {
+        let service = compose::container_service(&self.services, &self.project, name)
+            .unwrap_or(name)
+            .to_string();
+        if state.is_up() && !self.tracker.started.iter().any(|started| started == name) {
+            self.tracker.started.push(name.to_string());
+            self.completed_services = self.tracker.started.len();
+            self.add_log(&format!(
+                "✅ Service {} started ({}/{})",
+                service, self.completed_services, self.total_services
+            ));
+        }
+
+        match state {
+            ContainerState::Creating => {
+                self.set_service(&service);
+                self.add_log(&format!("🔨 Creating container {}...", name));
+            }
+            ContainerState::Created => self.add_log(&format!("✓ Container {} created", name)),
+            ContainerState::Starting => {
+                self.set_service(&service);
+                self.add_log(&format!("▶️  Starting service {}...", service));
+            }
+            ContainerState::Healthy => self.add_log(&format!("💚 {} is healthy", service)),
+            ContainerState::Exited => self.add_log(&format!("⏹  {} exited", service)),
+            ContainerState::Started | ContainerState::Running | ContainerState::Waiting => {}
+        }
+    }
+
+    fn update_progress(&mut self) {
+        let (start, end) = self.tracker.span;
+        let fraction = self.tracker.fraction(self.total_services).min(1.0);
+        let progress = start + (end - start) * fraction;
+        if progress > self.tracker.progress {
+            self.tracker.progress = progress;
+            self.set_progress(progress);
+        }
+    }
+
+    fn add_log(&self, message: &str) {
+        let _ = self.tx.send(InstallEvent::Log(message.to_string()));
+    }
+
+    fn set_service(&self, service: &str) {
+        let _ = self.tx.send(InstallEvent::Service(service.to_string()));
+    }
+
+    fn set_progress(&self, progress: f64) {
+        let _ = self.tx.send(InstallEvent::Progress {
+            progress,
+            completed_services: self.completed_services,
+            total_services: self.total_services,
+        });
+    }
+}
+
+#[cfg(unix)]
+fn track_layer(layers: &mut HashMap<String, (u64, u64)>, message: &PullMessage) {
+    let (Some(id), Some(status)) = (&message.id, &message.status) else {
+        return;
+    };
+    match status.as_str() {
+        "Downloading" => {
+            if let Some(detail) = &message.progress_detail
+                && let (Some(current), Some(total)) = (detail.current, detail.total)
+            {
+                layers.insert(id.clone(), (current, total));
+            }
+        }
+        "Download complete" | "Pull complete" | "Already exists" => {
+            if let Some(layer) = layers.get_mut(id) {
+                layer.0 = layer.1;
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(unix)]
+fn describe_event(event: &EngineEvent) -> Option<String> {
+    let service = event.service();
+    match event.action.as_str() {
+        "start" => Some(format!("▶️  Started {}", service)),
+        "health_status: healthy" => Some(format!("💚 {} is healthy", service)),
+        "die" => match event.attribute("exitCode") {
+            Some("0") => Some(format!("✓ {} finished", service)),
+            _ => Some(format!("⏹  {} stopped", service)),
+        },
+        _ => None,
+    }
+}
@@ -1,15 +1,51 @@
 mod app;
+mod backup;
+mod cli;
+mod compose;
+mod diff;
+mod doctor;
+mod drift;
+mod embeddings;
+#[cfg(unix)]
+mod engine;
+mod env_file;
+mod existing;
+mod generate;
+mod installer;
+mod platform;
+mod ports;
+mod progress;
+mod readiness;
+mod runtime;
+mod settings;
+mod stack;
 mod templates;
 mod ui;
 mod utils;
+mod validate;
 
 use app::App;
+use clap::Parser;
+use cli::Cli;
+use installer::InstallOptions;
+use std::process::ExitCode;
 
 #[tokio::main]
-async fn main() -> color_eyre::Result<()> {
+async fn main() -> color_eyre::Result<ExitCode> {
     color_eyre::install()?;
+    let cli = Cli::parse();
+    let options = InstallOptions {
+        runtime: cli.runtime,
+        engine_api: cli.engine_api,
+    };
+
+    if let Some(command) = cli.command {
+        return Ok(cli::run(command, options).await);
+    }
+
+    let app = App::new(options)?;
     let terminal = ratatui::init();
-    let result = App::new().run(terminal).await;
+    let result = app.run(terminal).await;
     ratatui::restore();
-    result
+    result.map(|_| ExitCode::SUCCESS)
 }
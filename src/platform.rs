@@ -0,0 +1,259 @@
+use color_eyre::{Result, eyre::eyre};
+use std::thread;
+use std::time::Duration;
+
+use crate::compose::ComposeService;
+use crate::env_file::EnvFile;
+use crate::ports;
+use crate::utils;
+
+pub const FALLBACK_PLATFORM: &str = "linux/amd64";
+
+const PLATFORM_VARIABLE: &str = "PLATFORM";
+const INSPECT_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone)]
+pub struct ImagePlatform {
+    pub service: String,
+    pub image: String,
+    pub platform: String,
+    pub published: Result<Vec<String>, String>,
+}
+
+impl ImagePlatform {
+    pub fn supports(&self, platform: &str) -> Option<bool> {
+        self.published.as_ref().ok().map(|published| {
+            published
+                .iter()
+                .any(|candidate| same_platform(candidate, platform))
+        })
+    }
+
+    pub fn is_emulated(&self) -> bool {
+        !same_platform(&self.platform, host_platform())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PlatformChoice {
+    pub platform: String,
+    pub notes: Vec<String>,
+}
+
+pub fn host_platform() -> &'static str {
+    match std::env::consts::ARCH {
+        "aarch64" => "linux/arm64",
+        "arm" => "linux/arm/v7",
+        "riscv64" => "linux/riscv64",
+        "s390x" => "linux/s390x",
+        _ => FALLBACK_PLATFORM,
+    }
+}
+
+pub fn inspect(services: &[ComposeService], env: &EnvFile) -> Vec<ImagePlatform> {
+    let images: Vec<(String, String, String)> = services
+        .iter()
+        .filter_map(|service| {
+            let image = service.image.clone()?;
+            let platform = service
+                .platform
+                .as_deref()
+                .map(|platform| ports::resolve(platform, env))
+                .filter(|platform| !platform.trim().is_empty())
+                .unwrap_or_else(|| host_platform().to_string());
+            Some((service.name.clone(), image, platform))
+        })
+        .collect();
+
+    thread::scope(|scope| {
+        let handles: Vec<_> = images
+            .iter()
+            .map(|(_, image, _)| scope.spawn(|| image_platforms(image)))
+            .collect();
+        images
+            .iter()
+            .zip(handles)
+            .map(|((service, image, platform), handle)| ImagePlatform {
+                service: service.clone(),
+                image: image.clone(),
+                platform: platform.clone(),
+                published: handle
+                    .join()
+                    .unwrap_or_else(|_| Err(eyre!("the manifest check panicked")))
+                    .map_err(|e| e.to_string()),
+            })
+            .collect()
+    })
+}
+
+pub fn select(services: &[ComposeService]) -> PlatformChoice {
+    let host = host_platform();
+    let uses_variable: Vec<ComposeService> = services
+        .iter()
+        .filter(|service| {
+            service
+                .platform
+                .as_deref()
+                .is_some_and(|platform| platform.contains(PLATFORM_VARIABLE))
+        })
+        .cloned()
+        .collect();
+    let images = inspect(&uses_variable, &EnvFile::parse(""));
+
+    let mut notes: Vec<String> = images
+        .iter()
+        .filter_map(|image| {
+            let error = image.published.as_ref().err()?;
+            Some(format!("could not check {}: {}", image.image, error))
+        })
+        .collect();
+
+    let missing: Vec<&str> = images
+        .iter()
+        .filter(|image| image.supports(host) == Some(false))
+        .map(|image| image.image.as_str())
+        .collect();
+    let candidates = [host, FALLBACK_PLATFORM];
+    let platform = match candidates.iter().find(|candidate| {
+        images
+            .iter()
+            .all(|image| image.supports(candidate) != Some(false))
+    }) {
+        Some(platform) => *platform,
+        None => {
+            notes.push(format!(
+                "{} publishes neither a {} nor a {} image",
+                missing.join(", "),
+                host,
+                FALLBACK_PLATFORM
+            ));
+            host
+        }
+    };
+
+    if platform != host {
+        notes.push(format!(
+            "{} has no {} image, so the stack runs as {} under emulation",
+            missing.join(", "),
+            host,
+            platform
+        ));
+    }
+
+    PlatformChoice {
+        platform: platform.to_string(),
+        notes,
+    }
+}
+
+pub fn image_platforms(image: &str) -> Result<Vec<String>> {
+    let output = utils::run_command(
+        "docker",
+        &["manifest", "inspect", "--verbose", image],
+        INSPECT_TIMEOUT,
+    )
+    .or_else(|| utils::run_command("podman", &["manifest", "inspect", image], INSPECT_TIMEOUT))
+    .ok_or_else(|| eyre!("docker manifest inspect did not answer"))?;
+    if !output.success {
+        let message = output
+            .stderr
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .unwrap_or("docker manifest inspect failed");
+        return Err(eyre!("{}", message));
+    }
+    parse_manifests(&output.stdout)
+}
+
+fn parse_manifests(content: &str) -> Result<Vec<String>> {
+    let value: serde_json::Value =
+        serde_json::from_str(content).map_err(|e| eyre!("failed to parse the manifest: {}", e))?;
+    let entries = match value {
+        serde_json::Value::Array(entries) => entries,
+        index if index.get("manifests").is_some() => {
+            index["manifests"].as_array().cloned().unwrap_or_default()
+        }
+        entry => vec![entry],
+    };
+
+    let mut platforms = Vec::new();
+    for entry in &entries {
+        let Some(platform) = entry.get("Descriptor").unwrap_or(entry).get("platform") else {
+            continue;
+        };
+        let field = |key: &str| {
+            platform
+                .get(key)
+                .and_then(|value| value.as_str())
+                .unwrap_or_default()
+        };
+        let (os, architecture) = (field("os"), field("architecture"));
+        if os.is_empty() || os == "unknown" || architecture.is_empty() {
+            continue;
+        }
+
+        let mut name = format!("{}/{}", os, architecture);
+        if architecture == "arm" && !field("variant").is_empty() {
+            name = format!("{}/{}", name, field("variant"));
+        }
+        if !platforms.contains(&name) {
+            platforms.push(name);
+        }
+    }
+
+    if platforms.is_empty() {
+        return Err(eyre!("the manifest does not list any platform"));
+    }
+    Ok(platforms)
+}
+
+fn same_platform(a: &str, b: &str) -> bool {
+    let normalize = |platform: &str| platform.trim().trim_end_matches("/v8").to_string();
+    normalize(a) == normalize(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifest_formats() {
+        let verbose_list = r#"[
+	{
+		"Ref": "docker.io/qdrant/qdrant:v1.11.0@sha256:aa",
+		"Descriptor": {
+			"mediaType": "application/vnd.oci.image.manifest.v1+json",
+			"platform": {"architecture": "amd64", "os": "linux"}
+		}
+	},
+	{
+		"Ref": "docker.io/qdrant/qdrant:v1.11.0@sha256:bb",
+		"Descriptor": {"platform": {"architecture": "arm64", "os": "linux", "variant": "v8"}}
+	},
+	{
+		"Ref": "docker.io/qdrant/qdrant:v1.11.0@sha256:cc",
+		"Descriptor": {"platform": {"architecture": "unknown", "os": "unknown"}}
+	}
+]"#;
+        assert_eq!(
+            parse_manifests(verbose_list).unwrap(),
+            ["linux/amd64", "linux/arm64"]
+        );
+
+        let index = r#"{"schemaVersion": 2, "manifests": [
+            {"platform": {"architecture": "arm", "os": "linux", "variant": "v7"}},
+            {"platform": {"architecture": "amd64", "os": "linux"}}
+        ]}"#;
+        assert_eq!(
+            parse_manifests(index).unwrap(),
+            ["linux/arm/v7", "linux/amd64"]
+        );
+
+        let single = r#"{"Ref": "postgres:15", "Descriptor": {"platform": {"architecture": "amd64", "os": "linux"}}}"#;
+        assert_eq!(parse_manifests(single).unwrap(), ["linux/amd64"]);
+
+        assert!(parse_manifests(r#"{"schemaVersion": 2, "config": {}}"#).is_err());
+        assert!(parse_manifests("no such manifest").is_err());
+    }
+}
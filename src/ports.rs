@@ -0,0 +1,217 @@
+use std::net::TcpListener;
+
+use crate::compose::ComposeService;
+use crate::env_file::EnvFile;
+
+const SUGGESTION_RANGE: u16 = 100;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PublishedPort {
+    pub service: String,
+    pub port: u16,
+    pub env_var: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PortConflict {
+    pub published: PublishedPort,
+    pub reason: String,
+    pub suggestion: Option<u16>,
+}
+
+pub fn published_ports(services: &[ComposeService], env: &EnvFile) -> Vec<PublishedPort> {
+    let mut ports = Vec::new();
+    for service in services {
+        for spec in &service.ports {
+            let parts = split_port_spec(spec);
+            let host = match parts.as_slice() {
+                [host, _container] | [_, host, _container] => host.trim(),
+                _ => continue,
+            };
+            let (env_var, value) = match variable(host) {
+                Some((name, default)) => {
+                    let value = env
+                        .get(name)
+                        .filter(|value| !value.trim().is_empty())
+                        .unwrap_or(default);
+                    (Some(name.to_string()), value.trim().to_string())
+                }
+                None => (None, host.to_string()),
+            };
+            let range = match value.split_once('-') {
+                Some((first, last)) => first
+                    .trim()
+                    .parse::<u16>()
+                    .ok()
+                    .zip(last.trim().parse().ok()),
+                None => value.parse::<u16>().ok().map(|port| (port, port)),
+            };
+            let Some((first, last)) = range else {
+                continue;
+            };
+            for port in first..=last {
+                ports.push(PublishedPort {
+                    service: service.name.clone(),
+                    port,
+                    env_var: env_var.clone(),
+                });
+            }
+        }
+    }
+    ports
+}
+
+pub fn resolve(spec: &str, env: &EnvFile) -> String {
+    let mut resolved = String::new();
+    let mut rest = spec;
+    while let Some(start) = rest.find("${") {
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+        resolved.push_str(&rest[..start]);
+        let expression = &rest[start..start + end + 1];
+        match variable(expression) {
+            Some((name, default)) => resolved.push_str(
+                env.get(name)
+                    .filter(|value| !value.trim().is_empty())
+                    .unwrap_or(default)
+                    .trim(),
+            ),
+            None => resolved.push_str(expression),
+        }
+        rest = &rest[start + end + 1..];
+    }
+    resolved.push_str(rest);
+    resolved
+}
+
+pub fn is_free(port: u16) -> bool {
+    TcpListener::bind(("0.0.0.0", port)).is_ok()
+}
+
+pub fn find_conflicts(ports: &[PublishedPort]) -> Vec<PortConflict> {
+    let mut reserved: Vec<u16> = ports.iter().map(|published| published.port).collect();
+    let mut conflicts = Vec::new();
+
+    for (index, published) in ports.iter().enumerate() {
+        let duplicate = ports[..index]
+            .iter()
+            .find(|other| other.port == published.port);
+        let reason = match duplicate {
+            Some(other) => format!("also published by {}", other.service),
+            None if !is_free(published.port) => "already in use".to_string(),
+            None => continue,
+        };
+
+        let suggestion = suggest_free(published.port, &reserved);
+        if let Some(port) = suggestion {
+            reserved.push(port);
+        }
+        conflicts.push(PortConflict {
+            published: published.clone(),
+            reason,
+            suggestion,
+        });
+    }
+
+    conflicts
+}
+
+pub fn suggest_free(port: u16, reserved: &[u16]) -> Option<u16> {
+    (1..=SUGGESTION_RANGE)
+        .filter_map(|offset| port.checked_add(offset))
+        .find(|candidate| !reserved.contains(candidate) && is_free(*candidate))
+}
+
+pub fn split_port_spec(spec: &str) -> Vec<&str> {
+    let spec = spec.split('/').next().unwrap_or_default();
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (index, c) in spec.char_indices() {
+        match c {
+            '{' | '[' => depth += 1,
+            '}' | ']' => depth -= 1,
+            ':' if depth == 0 => {
+                parts.push(&spec[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&spec[start..]);
+    parts
+}
+
+fn variable(value: &str) -> Option<(&str, &str)> {
+    let expression = value.strip_prefix("${")?.strip_suffix('}')?;
+    let (name, default) = match expression.split_once('-') {
+        Some((name, default)) => (name.trim_end_matches(':'), default),
+        None => (expression, ""),
+    };
+    (!name.is_empty()).then_some((name, default))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_port_specs() {
+        let cases: &[(&str, &[&str])] = &[
+            ("3000", &["3000"]),
+            ("3000:3000", &["3000", "3000"]),
+            ("${HOST_PORT:-3000}:3000", &["${HOST_PORT:-3000}", "3000"]),
+            (
+                "${AI_SERVICE_FORWARD_PORT:-5555}:${ANALYTICS_AI_SERVICE_PORT:-5555}",
+                &[
+                    "${AI_SERVICE_FORWARD_PORT:-5555}",
+                    "${ANALYTICS_AI_SERVICE_PORT:-5555}",
+                ],
+            ),
+            ("127.0.0.1:80:80", &["127.0.0.1", "80", "80"]),
+            ("[::1]:8080:80/tcp", &["[::1]", "8080", "80"]),
+            ("8000-8002:8000-8002/udp", &["8000-8002", "8000-8002"]),
+        ];
+        for (spec, expected) in cases {
+            assert_eq!(split_port_spec(spec), *expected, "{}", spec);
+        }
+    }
+
+    type Published = &'static [(u16, Option<&'static str>)];
+
+    #[test]
+    fn published_port_cases() {
+        let env = EnvFile::parse("HOST_PORT=3005\n");
+        let cases: &[(&str, Published)] = &[
+            ("${HOST_PORT:-3000}:3000", &[(3005, Some("HOST_PORT"))]),
+            ("${UNSET_PORT:-3000}:3000", &[(3000, Some("UNSET_PORT"))]),
+            ("127.0.0.1:80:80", &[(80, None)]),
+            ("[::1]:8080:80", &[(8080, None)]),
+            ("5432:5432/tcp", &[(5432, None)]),
+            (
+                "8000-8002:8000-8002",
+                &[(8000, None), (8001, None), (8002, None)],
+            ),
+            ("3000", &[]),
+            ("${UNSET_PORT}:3000", &[]),
+        ];
+
+        for (spec, expected) in cases {
+            let service = ComposeService {
+                name: "app".to_string(),
+                image: None,
+                platform: None,
+                ports: vec![spec.to_string()],
+                depends_on: Vec::new(),
+                healthcheck: false,
+            };
+            let published = published_ports(&[service], &env);
+            let found: Vec<(u16, Option<&str>)> = published
+                .iter()
+                .map(|published| (published.port, published.env_var.as_deref()))
+                .collect();
+            assert_eq!(found, *expected, "{}", spec);
+        }
+    }
+}
@@ -0,0 +1,524 @@
+use serde::Deserialize;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ContainerState {
+    Creating,
+    Created,
+    Starting,
+    Started,
+    Waiting,
+    Healthy,
+    Running,
+    Exited,
+}
+
+impl ContainerState {
+    fn parse(text: &str) -> Option<Self> {
+        match text.trim().to_lowercase().as_str() {
+            "creating" | "recreate" | "recreating" => Some(ContainerState::Creating),
+            "created" | "recreated" => Some(ContainerState::Created),
+            "starting" => Some(ContainerState::Starting),
+            "started" => Some(ContainerState::Started),
+            "waiting" => Some(ContainerState::Waiting),
+            "healthy" => Some(ContainerState::Healthy),
+            "running" => Some(ContainerState::Running),
+            "exited" => Some(ContainerState::Exited),
+            _ => None,
+        }
+    }
+
+    pub fn is_up(&self) -> bool {
+        matches!(
+            self,
+            ContainerState::Started | ContainerState::Healthy | ContainerState::Running
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContainerFailure {
+    Exited(i64),
+    Unhealthy,
+}
+
+impl fmt::Display for ContainerFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContainerFailure::Exited(code) => write!(f, "exited with code {}", code),
+            ContainerFailure::Unhealthy => write!(f, "is unhealthy"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProgressEvent {
+    Pulling {
+        service: String,
+    },
+    Pulled {
+        service: String,
+    },
+    Layer {
+        layer: String,
+        current: u64,
+        total: u64,
+    },
+    LayerDone {
+        layer: String,
+    },
+    BuildStep {
+        service: String,
+        step: u32,
+        total: u32,
+        text: String,
+    },
+    Container {
+        name: String,
+        state: ContainerState,
+    },
+    Failed {
+        name: String,
+        failure: ContainerFailure,
+    },
+    Error(String),
+    Output(String),
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonMessage {
+    #[serde(default)]
+    id: String,
+    #[serde(default)]
+    parent_id: String,
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    status: String,
+    #[serde(default)]
+    current: u64,
+    #[serde(default)]
+    total: u64,
+    #[serde(default)]
+    error: bool,
+}
+
+pub fn parse_line(line: &str) -> ProgressEvent {
+    let trimmed = line.trim();
+    if trimmed.starts_with('{')
+        && let Ok(message) = serde_json::from_str::<JsonMessage>(trimmed)
+    {
+        return parse_json(message);
+    }
+    if let Some(event) = parse_buildkit(trimmed) {
+        return event;
+    }
+    if let Some(event) = parse_plain(trimmed) {
+        return event;
+    }
+
+    let lower = trimmed.to_lowercase();
+    if lower.starts_with("error") || lower.contains("error response from daemon") {
+        ProgressEvent::Error(trimmed.to_string())
+    } else {
+        ProgressEvent::Output(trimmed.to_string())
+    }
+}
+
+fn parse_json(message: JsonMessage) -> ProgressEvent {
+    if message.error || message.text.eq_ignore_ascii_case("error") {
+        let detail = if message.status.is_empty() {
+            message.id
+        } else {
+            format!("{}: {}", message.id, message.status)
+        };
+        return ProgressEvent::Error(detail);
+    }
+    if let Some(name) = message.id.strip_prefix("Container ")
+        && let Some(state) = ContainerState::parse(&message.text)
+    {
+        return container_event(name, state, exit_code(&message.status));
+    }
+    if !message.parent_id.is_empty() {
+        return match message.text.as_str() {
+            "Downloading" if message.total > 0 => ProgressEvent::Layer {
+                layer: message.id,
+                current: message.current,
+                total: message.total,
+            },
+            "Download complete" | "Pull complete" | "Already exists" => {
+                ProgressEvent::LayerDone { layer: message.id }
+            }
+            _ => ProgressEvent::Output(String::new()),
+        };
+    }
+
+    match message.text.as_str() {
+        "Pulling" => ProgressEvent::Pulling {
+            service: message.id,
+        },
+        "Pulled" => ProgressEvent::Pulled {
+            service: message.id,
+        },
+        text => ProgressEvent::Output(
+            [message.id.as_str(), text, message.status.as_str()]
+                .iter()
+                .filter(|part| !part.is_empty())
+                .copied()
+                .collect::<Vec<_>>()
+                .join(" "),
+        ),
+    }
+}
+
+fn parse_buildkit(line: &str) -> Option<ProgressEvent> {
+    let rest = line.strip_prefix('#')?;
+    let (number, rest) = rest.split_once(' ')?;
+    if !number.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    if let Some(error) = rest.strip_prefix("ERROR: ") {
+        return Some(ProgressEvent::Error(error.to_string()));
+    }
+
+    let (stage, text) = rest.strip_prefix('[')?.split_once("] ")?;
+    let (service, steps) = stage.rsplit_once(' ').unwrap_or(("", stage));
+    let (step, total) = steps.split_once('/')?;
+    Some(ProgressEvent::BuildStep {
+        service: service.to_string(),
+        step: step.parse().ok()?,
+        total: total.parse().ok()?,
+        text: text.to_string(),
+    })
+}
+
+fn parse_plain(line: &str) -> Option<ProgressEvent> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    match words.as_slice() {
+        ["Container", name, state, rest @ ..] => Some(container_event(
+            name,
+            ContainerState::parse(state)?,
+            exit_code(&rest.join(" ")),
+        )),
+        [name, "exited", "with", "code", code] => Some(container_event(
+            name,
+            ContainerState::Exited,
+            code.parse().ok(),
+        )),
+        [.., "container", name, "exited", code] => Some(container_event(
+            name,
+            ContainerState::Exited,
+            exit_code(code),
+        )),
+        [.., "container", name, "is", "unhealthy"] => Some(ProgressEvent::Failed {
+            name: name.to_string(),
+            failure: ContainerFailure::Unhealthy,
+        }),
+        [service, "Pulling"] => Some(ProgressEvent::Pulling {
+            service: service.to_string(),
+        }),
+        [service, "Pulled"] => Some(ProgressEvent::Pulled {
+            service: service.to_string(),
+        }),
+        ["Pulling", service, ..] => Some(ProgressEvent::Pulling {
+            service: service.to_string(),
+        }),
+        [layer, "Downloading", .., sizes] => {
+            let (current, total) = sizes.split_once('/')?;
+            Some(ProgressEvent::Layer {
+                layer: layer.to_string(),
+                current: parse_size(current)?,
+                total: parse_size(total)?,
+            })
+        }
+        [layer, "Download", "complete"]
+        | [layer, "Pull", "complete"]
+        | [layer, "Already", "exists"] => Some(ProgressEvent::LayerDone {
+            layer: layer.to_string(),
+        }),
+        ["Creating" | "Recreating", name, "...", ..] => Some(ProgressEvent::Container {
+            name: name.to_string(),
+            state: ContainerState::Created,
+        }),
+        ["Starting", name, "...", ..] => Some(ProgressEvent::Container {
+            name: name.to_string(),
+            state: ContainerState::Started,
+        }),
+        _ => None,
+    }
+}
+
+fn container_event(name: &str, state: ContainerState, code: Option<i64>) -> ProgressEvent {
+    match (state, code) {
+        (ContainerState::Exited, Some(code)) if code != 0 => ProgressEvent::Failed {
+            name: name.to_string(),
+            failure: ContainerFailure::Exited(code),
+        },
+        _ => ProgressEvent::Container {
+            name: name.to_string(),
+            state,
+        },
+    }
+}
+
+fn exit_code(text: &str) -> Option<i64> {
+    text.split(|c: char| !c.is_ascii_digit())
+        .rfind(|part| !part.is_empty())
+        .and_then(|code| code.parse().ok())
+}
+
+fn parse_size(value: &str) -> Option<u64> {
+    let split = value
+        .find(|c: char| c.is_ascii_alphabetic())
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let number: f64 = number.trim().parse().ok()?;
+    let factor = match unit.to_lowercase().as_str() {
+        "" | "b" => 1.0,
+        "kb" => 1e3,
+        "mb" => 1e6,
+        "gb" => 1e9,
+        _ => return None,
+    };
+    Some((number * factor) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn container(name: &str, state: ContainerState) -> ProgressEvent {
+        ProgressEvent::Container {
+            name: name.to_string(),
+            state,
+        }
+    }
+
+    #[test]
+    fn compose_json_lines() {
+        let cases = [
+            (
+                r#"{"id":"qdrant","text":"Pulling"}"#,
+                ProgressEvent::Pulling {
+                    service: "qdrant".to_string(),
+                },
+            ),
+            (
+                r#"{"id":"9b6e5a1c2f3d","parent_id":"qdrant","text":"Downloading","status":"[==>   ]","current":1048576,"total":31457280,"percent":3}"#,
+                ProgressEvent::Layer {
+                    layer: "9b6e5a1c2f3d".to_string(),
+                    current: 1_048_576,
+                    total: 31_457_280,
+                },
+            ),
+            (
+                r#"{"id":"9b6e5a1c2f3d","parent_id":"qdrant","text":"Pull complete"}"#,
+                ProgressEvent::LayerDone {
+                    layer: "9b6e5a1c2f3d".to_string(),
+                },
+            ),
+            (
+                r#"{"id":"qdrant","text":"Pulled"}"#,
+                ProgressEvent::Pulled {
+                    service: "qdrant".to_string(),
+                },
+            ),
+            (
+                r#"{"id":"Container analytics-qdrant-1","text":"Started"}"#,
+                container("analytics-qdrant-1", ContainerState::Started),
+            ),
+            (
+                r#"{"id":"Container analytics-qdrant-1","text":"Healthy"}"#,
+                container("analytics-qdrant-1", ContainerState::Healthy),
+            ),
+            (
+                r#"{"id":"Container analytics-northwind-db-1","text":"Error","status":"port is already allocated"}"#,
+                ProgressEvent::Error(
+                    "Container analytics-northwind-db-1: port is already allocated".to_string(),
+                ),
+            ),
+            (
+                r#"{"id":"Container analytics-bootstrap-1","text":"Exited"}"#,
+                container("analytics-bootstrap-1", ContainerState::Exited),
+            ),
+            (
+                r#"{"id":"Container analytics-bootstrap-1","text":"Exited","status":"exit code 0"}"#,
+                container("analytics-bootstrap-1", ContainerState::Exited),
+            ),
+            (
+                r#"{"id":"Container analytics-bootstrap-1","text":"Exited","status":"Exited (2)"}"#,
+                ProgressEvent::Failed {
+                    name: "analytics-bootstrap-1".to_string(),
+                    failure: ContainerFailure::Exited(2),
+                },
+            ),
+            (
+                r#"{"id":"Network analytics_default","text":"Created"}"#,
+                ProgressEvent::Output("Network analytics_default Created".to_string()),
+            ),
+        ];
+
+        for (line, expected) in cases {
+            assert_eq!(parse_line(line), expected, "{}", line);
+        }
+    }
+
+    #[test]
+    fn buildkit_plain_lines() {
+        let cases = [
+            (
+                "#5 [analytics-ui 2/4] RUN npm ci --omit=dev",
+                Some(ProgressEvent::BuildStep {
+                    service: "analytics-ui".to_string(),
+                    step: 2,
+                    total: 4,
+                    text: "RUN npm ci --omit=dev".to_string(),
+                }),
+            ),
+            (
+                "#7 [3/3] COPY init.sh /init.sh",
+                Some(ProgressEvent::BuildStep {
+                    service: String::new(),
+                    step: 3,
+                    total: 3,
+                    text: "COPY init.sh /init.sh".to_string(),
+                }),
+            ),
+            (
+                "#9 ERROR: process \"/bin/sh -c npm ci\" did not complete successfully: exit code: 1",
+                Some(ProgressEvent::Error(
+                    "process \"/bin/sh -c npm ci\" did not complete successfully: exit code: 1"
+                        .to_string(),
+                )),
+            ),
+            ("#1 [internal] load build definition from Dockerfile", None),
+            ("#2 [analytics-ui internal] load .dockerignore", None),
+            ("#5 DONE 12.3s", None),
+            ("#6 CACHED", None),
+            ("# comment", None),
+        ];
+
+        for (line, expected) in cases {
+            assert_eq!(parse_buildkit(line), expected, "{}", line);
+        }
+    }
+
+    #[test]
+    fn compose_plain_and_legacy_lines() {
+        let cases = [
+            (
+                " qdrant Pulling ",
+                ProgressEvent::Pulling {
+                    service: "qdrant".to_string(),
+                },
+            ),
+            (
+                " 9b6e5a1c2f3d Downloading [=====>       ]  1.5MB/30.2MB",
+                ProgressEvent::Layer {
+                    layer: "9b6e5a1c2f3d".to_string(),
+                    current: 1_500_000,
+                    total: 30_200_000,
+                },
+            ),
+            (
+                " 9b6e5a1c2f3d Already exists ",
+                ProgressEvent::LayerDone {
+                    layer: "9b6e5a1c2f3d".to_string(),
+                },
+            ),
+            (
+                " Container analytics-qdrant-1  Started",
+                container("analytics-qdrant-1", ContainerState::Started),
+            ),
+            (
+                " Container analytics-bootstrap-1  Exited",
+                container("analytics-bootstrap-1", ContainerState::Exited),
+            ),
+            (
+                "analytics-bootstrap-1 exited with code 0",
+                container("analytics-bootstrap-1", ContainerState::Exited),
+            ),
+            (
+                "analytics-bootstrap-1 exited with code 1",
+                ProgressEvent::Failed {
+                    name: "analytics-bootstrap-1".to_string(),
+                    failure: ContainerFailure::Exited(1),
+                },
+            ),
+            (
+                "analytics_bootstrap_1 exited with code 137",
+                ProgressEvent::Failed {
+                    name: "analytics_bootstrap_1".to_string(),
+                    failure: ContainerFailure::Exited(137),
+                },
+            ),
+            (
+                "dependency failed to start: container analytics-bootstrap-1 exited (3)",
+                ProgressEvent::Failed {
+                    name: "analytics-bootstrap-1".to_string(),
+                    failure: ContainerFailure::Exited(3),
+                },
+            ),
+            (
+                "dependency failed to start: container analytics-qdrant-1 is unhealthy",
+                ProgressEvent::Failed {
+                    name: "analytics-qdrant-1".to_string(),
+                    failure: ContainerFailure::Unhealthy,
+                },
+            ),
+            (
+                "Pulling qdrant (qdrant/qdrant:v1.11.0)...",
+                ProgressEvent::Pulling {
+                    service: "qdrant".to_string(),
+                },
+            ),
+            (
+                "Creating analytics_qdrant_1 ... done",
+                container("analytics_qdrant_1", ContainerState::Created),
+            ),
+            (
+                "Starting analytics_qdrant_1 ... done",
+                container("analytics_qdrant_1", ContainerState::Started),
+            ),
+            (
+                "Error response from daemon: driver failed programming external connectivity",
+                ProgressEvent::Error(
+                    "Error response from daemon: driver failed programming external connectivity"
+                        .to_string(),
+                ),
+            ),
+            (
+                "Some other output",
+                ProgressEvent::Output("Some other output".to_string()),
+            ),
+        ];
+
+        for (line, expected) in cases {
+            assert_eq!(parse_line(line), expected, "{}", line);
+        }
+    }
+
+    #[test]
+    fn only_running_states_are_up() {
+        for (state, up) in [
+            (ContainerState::Created, false),
+            (ContainerState::Started, true),
+            (ContainerState::Healthy, true),
+            (ContainerState::Running, true),
+            (ContainerState::Exited, false),
+        ] {
+            assert_eq!(state.is_up(), up, "{:?}", state);
+        }
+    }
+
+    #[test]
+    fn sizes_are_decimal_units() {
+        assert_eq!(parse_size("512B"), Some(512));
+        assert_eq!(parse_size("1.5kB"), Some(1_500));
+        assert_eq!(parse_size("30.2MB"), Some(30_200_000));
+        assert_eq!(parse_size("1.1GB"), Some(1_100_000_000));
+        assert_eq!(parse_size("12"), Some(12));
+        assert_eq!(parse_size("3TB"), None);
+        assert_eq!(parse_size("MB"), None);
+    }
+}
@@ -0,0 +1,235 @@
+use std::fs;
+
+use crate::env_file::EnvFile;
+use crate::generate;
+use crate::templates::{self, ConfigTemplate};
+use crate::validate;
+
+const EXAMPLE_PREFIXES: &[&str] = &["your-", "your_", "<"];
+const EXAMPLE_SUFFIXES: &[&str] = &["-here", "_here", ">"];
+const EXAMPLE_VALUES: &[&str] = &["changeme", "change-me", "replace-me", "xxx", "todo"];
+
+#[derive(Debug, Clone)]
+pub struct ReadinessItem {
+    pub label: &'static str,
+    pub problems: Vec<String>,
+}
+
+impl ReadinessItem {
+    pub fn is_ready(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Readiness {
+    pub items: Vec<ReadinessItem>,
+}
+
+impl Readiness {
+    pub fn is_ready(&self) -> bool {
+        self.items.iter().all(|item| item.is_ready())
+    }
+
+    pub fn problems(&self) -> impl Iterator<Item = &str> {
+        self.items
+            .iter()
+            .flat_map(|item| item.problems.iter().map(|problem| problem.as_str()))
+    }
+}
+
+pub fn load(template: Option<&ConfigTemplate>) -> Readiness {
+    let env = EnvFile::load(&generate::env_path());
+    let config = fs::read_to_string(validate::config_path()).ok();
+    check(env.as_ref(), config.as_deref(), template)
+}
+
+pub fn check(
+    env: Option<&EnvFile>,
+    config: Option<&str>,
+    template: Option<&ConfigTemplate>,
+) -> Readiness {
+    let mut items = Vec::new();
+
+    if let Some(env) = env {
+        if let Some(template) = template {
+            items.push(ReadinessItem {
+                label: "Provider credentials set in .env",
+                problems: template
+                    .credentials
+                    .iter()
+                    .filter_map(|credential| {
+                        let value = env.get(&credential.env_var).unwrap_or_default();
+                        credential
+                            .validate(value)
+                            .err()
+                            .map(|e| format!("{}: {}", credential.env_var, e))
+                    })
+                    .collect(),
+            });
+        }
+
+        items.push(ReadinessItem {
+            label: "No example values left in .env",
+            problems: env
+                .keys()
+                .filter(|key| is_example_value(env.get(key).unwrap_or_default()))
+                .map(|key| format!("{} still has an example value", key))
+                .collect(),
+        });
+
+        items.push(ReadinessItem {
+            label: "Ports in .env are valid",
+            problems: env
+                .keys()
+                .filter(|key| key.ends_with("_PORT"))
+                .filter_map(|key| {
+                    let value = env.get(key).unwrap_or_default().trim();
+                    match value.parse::<u16>() {
+                        Ok(port) if port > 0 => None,
+                        _ => Some(format!(
+                            "{}={} is not a port between 1 and 65535",
+                            key, value
+                        )),
+                    }
+                })
+                .collect(),
+        });
+    }
+
+    if let Some(config) = config {
+        let placeholders = templates::find_placeholders(&strip_comments(config));
+        items.push(ReadinessItem {
+            label: "No placeholders left in config.yaml",
+            problems: if placeholders.is_empty() {
+                Vec::new()
+            } else {
+                vec![format!(
+                    "{} still in config.yaml",
+                    templates::format_placeholders(&placeholders)
+                )]
+            },
+        });
+    }
+
+    Readiness { items }
+}
+
+fn strip_comments(content: &str) -> String {
+    content
+        .lines()
+        .map(|line| {
+            if line.trim_start().starts_with('#') {
+                ""
+            } else {
+                line.split(" #").next().unwrap_or_default()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn is_example_value(value: &str) -> bool {
+    let value = value
+        .trim()
+        .trim_matches(|c| c == '"' || c == '\'')
+        .to_ascii_lowercase();
+    EXAMPLE_PREFIXES
+        .iter()
+        .any(|prefix| value.starts_with(prefix))
+        || EXAMPLE_SUFFIXES
+            .iter()
+            .any(|suffix| value.ends_with(suffix))
+        || EXAMPLE_VALUES.contains(&value.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::templates::TemplateCatalog;
+
+    #[test]
+    fn example_value_cases() {
+        let cases = [
+            ("your-api-key", true),
+            ("YOUR_API_KEY", true),
+            ("\"your-api-key\"", true),
+            ("sk-your-key-here", true),
+            ("<openai-api-key>", true),
+            ("changeme", true),
+            (" TODO ", true),
+            ("xxx", true),
+            ("", false),
+            ("sk-proj-4f9a2b", false),
+            ("https://my-resource.openai.azure.com", false),
+            ("todo-app", false),
+            ("3000", false),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(is_example_value(value), expected, "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn readiness_cases() {
+        let catalog = TemplateCatalog::built_in();
+        let openai = catalog.find("openai");
+        let ready_config = "type: llm\nprovider: litellm_llm\n";
+        let cases: &[(Option<&str>, Option<&str>, &[&str])] = &[
+            (None, None, &[]),
+            (
+                Some("OPENAI_API_KEY=sk-proj-4f9a2b\nHOST_PORT=3000\n"),
+                Some(ready_config),
+                &[],
+            ),
+            (
+                Some("OPENAI_API_KEY=\nHOST_PORT=3000\n"),
+                None,
+                &["OPENAI_API_KEY: OpenAI API Key is required!"],
+            ),
+            (
+                Some("HOST_PORT=3000\n"),
+                None,
+                &["OPENAI_API_KEY: OpenAI API Key is required!"],
+            ),
+            (
+                Some("OPENAI_API_KEY=sk-your-key-here\nLANGFUSE_SECRET_KEY=changeme\n"),
+                None,
+                &[
+                    "OPENAI_API_KEY still has an example value",
+                    "LANGFUSE_SECRET_KEY still has an example value",
+                ],
+            ),
+            (
+                Some("OPENAI_API_KEY=your-api-key\n"),
+                None,
+                &[
+                    "OPENAI_API_KEY: Invalid OpenAI API Key format (should start with 'sk-')",
+                    "OPENAI_API_KEY still has an example value",
+                ],
+            ),
+            (
+                Some("OPENAI_API_KEY=sk-proj-4f9a2b\nHOST_PORT=0\nUI_PORT=\nENGINE_PORT=http\n"),
+                None,
+                &[
+                    "HOST_PORT=0 is not a port between 1 and 65535",
+                    "UI_PORT= is not a port between 1 and 65535",
+                    "ENGINE_PORT=http is not a port between 1 and 65535",
+                ],
+            ),
+            (
+                None,
+                Some("api_base: <azure-api-base> # <not-this-one>\n# model: <deployment>\n"),
+                &["<azure-api-base> still in config.yaml"],
+            ),
+        ];
+
+        for (env, config, expected) in cases {
+            let env = env.map(EnvFile::parse);
+            let readiness = check(env.as_ref(), *config, openai);
+            let problems: Vec<&str> = readiness.problems().collect();
+            assert_eq!(problems, *expected, "{:?} {:?}", env, config);
+            assert_eq!(readiness.is_ready(), expected.is_empty());
+        }
+    }
+}
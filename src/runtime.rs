@@ -0,0 +1,333 @@
+use color_eyre::{Result, eyre::eyre};
+use std::time::Duration;
+use tokio::process::Command;
+
+use crate::utils;
+
+const VERSION_TIMEOUT: Duration = Duration::from_secs(10);
+const JSON_PROGRESS_VERSION: &[u32] = &[2, 30];
+const OVERRIDE_TAG_VERSION: &[u32] = &[2, 24, 4];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RuntimeKind {
+    Docker,
+    LegacyDocker,
+    Podman,
+}
+
+pub const RUNTIMES: &[RuntimeKind] = &[
+    RuntimeKind::Docker,
+    RuntimeKind::LegacyDocker,
+    RuntimeKind::Podman,
+];
+
+impl RuntimeKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            RuntimeKind::Docker => "docker",
+            RuntimeKind::LegacyDocker => "docker-compose",
+            RuntimeKind::Podman => "podman",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        RUNTIMES
+            .iter()
+            .copied()
+            .find(|kind| kind.name().eq_ignore_ascii_case(name.trim()))
+    }
+
+    pub fn engine(&self) -> &'static str {
+        match self {
+            RuntimeKind::Docker | RuntimeKind::LegacyDocker => "docker",
+            RuntimeKind::Podman => "podman",
+        }
+    }
+
+    pub fn install_hint(&self) -> &'static str {
+        match self {
+            RuntimeKind::Docker => {
+                "Install the Docker Compose v2 plugin, e.g. `sudo apt install docker-compose-plugin`"
+            }
+            RuntimeKind::LegacyDocker => {
+                "Install docker-compose from https://docs.docker.com/compose/install/standalone/"
+            }
+            RuntimeKind::Podman => {
+                "Install podman-compose, e.g. `pip install podman-compose`, or a Compose provider for `podman compose`"
+            }
+        }
+    }
+
+    pub fn runtime(&self) -> Box<dyn ContainerRuntime> {
+        match self {
+            RuntimeKind::Docker => Box::new(DockerCompose {
+                json_progress: false,
+                override_tag: false,
+            }),
+            RuntimeKind::LegacyDocker => Box::new(LegacyDockerCompose),
+            RuntimeKind::Podman => Box::new(PodmanCompose),
+        }
+    }
+
+    pub fn runtime_for(&self, version: &str) -> Box<dyn ContainerRuntime> {
+        match self {
+            RuntimeKind::Docker => Box::new(DockerCompose {
+                json_progress: at_least(version, JSON_PROGRESS_VERSION),
+                override_tag: at_least(version, OVERRIDE_TAG_VERSION),
+            }),
+            _ => self.runtime(),
+        }
+    }
+}
+
+pub fn runtime_names() -> String {
+    RUNTIMES
+        .iter()
+        .map(|kind| kind.name())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+pub trait ContainerRuntime: Send + Sync {
+    fn kind(&self) -> RuntimeKind;
+
+    fn program(&self) -> &'static str;
+
+    fn compose_args(&self) -> &'static [&'static str];
+
+    fn version(&self) -> Option<String> {
+        let mut args = self.compose_args().to_vec();
+        args.extend(["version", "--short"]);
+        let output = utils::run_command(self.program(), &args, VERSION_TIMEOUT)
+            .filter(|output| output.success)?;
+        let version = output.stdout.trim().trim_start_matches('v').to_string();
+        (!version.is_empty()).then_some(version)
+    }
+
+    fn progress_args(&self, _args: &[&str]) -> &'static [&'static str] {
+        &[]
+    }
+
+    fn supports_override_tag(&self) -> bool {
+        false
+    }
+
+    #[cfg(unix)]
+    fn name_separator(&self) -> char {
+        '_'
+    }
+
+    fn override_tag_hint(&self) -> String {
+        format!(
+            "{} does not support the `!override` tag",
+            self.command_line(&[], &[])
+        )
+    }
+
+    fn command(&self, files: &[String], args: &[&str]) -> Command {
+        let mut command = Command::new(self.program());
+        command
+            .current_dir(utils::project_root())
+            .env("BUILDKIT_PROGRESS", "plain")
+            .args(self.compose_args())
+            .args(self.progress_args(args))
+            .args(files)
+            .args(args);
+        command
+    }
+
+    fn command_line(&self, files: &[String], args: &[&str]) -> String {
+        let mut parts = vec![self.program().to_string()];
+        parts.extend(self.compose_args().iter().map(|arg| arg.to_string()));
+        parts.extend(self.progress_args(args).iter().map(|arg| arg.to_string()));
+        parts.extend(files.iter().cloned());
+        parts.extend(args.iter().map(|arg| arg.to_string()));
+        parts.join(" ")
+    }
+}
+
+pub struct DockerCompose {
+    json_progress: bool,
+    override_tag: bool,
+}
+
+impl ContainerRuntime for DockerCompose {
+    fn kind(&self) -> RuntimeKind {
+        RuntimeKind::Docker
+    }
+
+    fn program(&self) -> &'static str {
+        "docker"
+    }
+
+    fn compose_args(&self) -> &'static [&'static str] {
+        &["compose"]
+    }
+
+    fn progress_args(&self, args: &[&str]) -> &'static [&'static str] {
+        match args.first() {
+            Some(&"build") => &["--progress", "plain"],
+            Some(_) if self.json_progress => &["--progress", "json"],
+            Some(_) => &["--progress", "plain"],
+            None => &[],
+        }
+    }
+
+    fn supports_override_tag(&self) -> bool {
+        self.override_tag
+    }
+
+    #[cfg(unix)]
+    fn name_separator(&self) -> char {
+        '-'
+    }
+
+    fn override_tag_hint(&self) -> String {
+        format!(
+            "the `!override` tag needs Docker Compose {} or newer",
+            OVERRIDE_TAG_VERSION
+                .iter()
+                .map(u32::to_string)
+                .collect::<Vec<_>>()
+                .join(".")
+        )
+    }
+}
+
+pub struct LegacyDockerCompose;
+
+impl ContainerRuntime for LegacyDockerCompose {
+    fn kind(&self) -> RuntimeKind {
+        RuntimeKind::LegacyDocker
+    }
+
+    fn program(&self) -> &'static str {
+        "docker-compose"
+    }
+
+    fn compose_args(&self) -> &'static [&'static str] {
+        &[]
+    }
+}
+
+pub struct PodmanCompose;
+
+impl ContainerRuntime for PodmanCompose {
+    fn kind(&self) -> RuntimeKind {
+        RuntimeKind::Podman
+    }
+
+    fn program(&self) -> &'static str {
+        "podman"
+    }
+
+    fn compose_args(&self) -> &'static [&'static str] {
+        &["compose"]
+    }
+
+    fn version(&self) -> Option<String> {
+        let output = utils::run_command("podman", &["compose", "version"], VERSION_TIMEOUT)
+            .filter(|output| output.success)?;
+        output
+            .stdout
+            .lines()
+            .find(|line| line.to_lowercase().contains("version"))
+            .and_then(|line| line.split_whitespace().last())
+            .map(|version| version.trim_start_matches('v').to_string())
+    }
+
+    fn command(&self, files: &[String], args: &[&str]) -> Command {
+        let mut command = Command::new(self.program());
+        command
+            .current_dir(utils::project_root())
+            .env("PODMAN_COMPOSE_WARNING_LOGS", "false")
+            .env("BUILDKIT_PROGRESS", "plain")
+            .args(self.compose_args())
+            .args(files)
+            .args(args);
+        command
+    }
+}
+
+pub fn detect(preference: Option<RuntimeKind>) -> Result<(Box<dyn ContainerRuntime>, String)> {
+    let candidates = match preference {
+        Some(kind) => vec![kind],
+        None => RUNTIMES.to_vec(),
+    };
+
+    for kind in &candidates {
+        if let Some(version) = kind.runtime().version() {
+            return Ok((kind.runtime_for(&version), version));
+        }
+    }
+
+    match preference {
+        Some(kind) => Err(eyre!(
+            "{} is not available",
+            kind.runtime().command_line(&[], &[])
+        )),
+        None => Err(eyre!(
+            "no compose runtime found, tried docker compose, docker-compose and podman compose"
+        )),
+    }
+}
+
+fn at_least(version: &str, minimum: &[u32]) -> bool {
+    let mut parts = version
+        .split(|c: char| !c.is_ascii_digit())
+        .map(|part| part.parse::<u32>().unwrap_or(0));
+    let found: Vec<u32> = minimum.iter().map(|_| parts.next().unwrap_or(0)).collect();
+    found.as_slice() >= minimum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn version_gates() {
+        assert!(at_least("2.30.0", JSON_PROGRESS_VERSION));
+        assert!(!at_least("2.29.7", JSON_PROGRESS_VERSION));
+        assert!(at_least("2.24.4", OVERRIDE_TAG_VERSION));
+        assert!(at_least("2.25.0-desktop.1", OVERRIDE_TAG_VERSION));
+        assert!(!at_least("2.24.3", OVERRIDE_TAG_VERSION));
+        assert!(!at_least("2.24", OVERRIDE_TAG_VERSION));
+        assert!(!at_least("1.29.2", OVERRIDE_TAG_VERSION));
+
+        assert!(
+            RuntimeKind::Docker
+                .runtime_for("2.31.0")
+                .supports_override_tag()
+        );
+        assert!(
+            !RuntimeKind::Docker
+                .runtime_for("2.20.2")
+                .supports_override_tag()
+        );
+        assert!(
+            !RuntimeKind::Podman
+                .runtime_for("5.2.0")
+                .supports_override_tag()
+        );
+    }
+
+    #[test]
+    fn override_tag_hints_name_the_runtime() {
+        for (kind, hint) in [
+            (
+                RuntimeKind::Docker,
+                "the `!override` tag needs Docker Compose 2.24.4 or newer",
+            ),
+            (
+                RuntimeKind::LegacyDocker,
+                "docker-compose does not support the `!override` tag",
+            ),
+            (
+                RuntimeKind::Podman,
+                "podman compose does not support the `!override` tag",
+            ),
+        ] {
+            assert_eq!(kind.runtime().override_tag_hint(), hint);
+        }
+    }
+}
@@ -0,0 +1,609 @@
+use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SettingKind {
+    Toggle,
+    Integer,
+    Decimal { min: f64, max: f64 },
+    Choice(&'static [&'static str]),
+    Url,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SettingField {
+    pub key: &'static str,
+    pub kind: SettingKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SettingsPreset {
+    Production,
+    Development,
+    Debug,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Settings {
+    pub doc_endpoint: String,
+    pub is_oss: bool,
+    pub engine_timeout: u64,
+    pub column_indexing_batch_size: u64,
+    pub table_retrieval_size: u64,
+    pub table_column_retrieval_size: u64,
+    pub allow_intent_classification: bool,
+    pub allow_sql_generation_reasoning: bool,
+    pub allow_sql_functions_retrieval: bool,
+    pub enable_column_pruning: bool,
+    pub max_sql_correction_retries: u64,
+    pub query_cache_maxsize: u64,
+    pub query_cache_ttl: u64,
+    pub langfuse_host: String,
+    pub langfuse_enable: bool,
+    pub logging_level: String,
+    pub development: bool,
+    pub historical_question_retrieval_similarity_threshold: f64,
+    pub sql_pairs_similarity_threshold: f64,
+    pub sql_pairs_retrieval_max_size: u64,
+    pub instructions_similarity_threshold: f64,
+    pub instructions_top_k: u64,
+}
+
+#[derive(Serialize)]
+struct SettingsDocument<'a> {
+    settings: &'a Settings,
+}
+
+const LOGGING_LEVELS: &[&str] = &["DEBUG", "INFO", "WARNING", "ERROR", "CRITICAL"];
+const SIMILARITY: SettingKind = SettingKind::Decimal { min: 0.0, max: 1.0 };
+
+pub const PRESETS: &[SettingsPreset] = &[
+    SettingsPreset::Production,
+    SettingsPreset::Development,
+    SettingsPreset::Debug,
+];
+
+pub const SETTING_FIELDS: &[SettingField] = &[
+    SettingField {
+        key: "doc_endpoint",
+        kind: SettingKind::Url,
+    },
+    SettingField {
+        key: "is_oss",
+        kind: SettingKind::Toggle,
+    },
+    SettingField {
+        key: "engine_timeout",
+        kind: SettingKind::Integer,
+    },
+    SettingField {
+        key: "column_indexing_batch_size",
+        kind: SettingKind::Integer,
+    },
+    SettingField {
+        key: "table_retrieval_size",
+        kind: SettingKind::Integer,
+    },
+    SettingField {
+        key: "table_column_retrieval_size",
+        kind: SettingKind::Integer,
+    },
+    SettingField {
+        key: "allow_intent_classification",
+        kind: SettingKind::Toggle,
+    },
+    SettingField {
+        key: "allow_sql_generation_reasoning",
+        kind: SettingKind::Toggle,
+    },
+    SettingField {
+        key: "allow_sql_functions_retrieval",
+        kind: SettingKind::Toggle,
+    },
+    SettingField {
+        key: "enable_column_pruning",
+        kind: SettingKind::Toggle,
+    },
+    SettingField {
+        key: "max_sql_correction_retries",
+        kind: SettingKind::Integer,
+    },
+    SettingField {
+        key: "query_cache_maxsize",
+        kind: SettingKind::Integer,
+    },
+    SettingField {
+        key: "query_cache_ttl",
+        kind: SettingKind::Integer,
+    },
+    SettingField {
+        key: "langfuse_host",
+        kind: SettingKind::Url,
+    },
+    SettingField {
+        key: "langfuse_enable",
+        kind: SettingKind::Toggle,
+    },
+    SettingField {
+        key: "logging_level",
+        kind: SettingKind::Choice(LOGGING_LEVELS),
+    },
+    SettingField {
+        key: "development",
+        kind: SettingKind::Toggle,
+    },
+    SettingField {
+        key: "historical_question_retrieval_similarity_threshold",
+        kind: SIMILARITY,
+    },
+    SettingField {
+        key: "sql_pairs_similarity_threshold",
+        kind: SIMILARITY,
+    },
+    SettingField {
+        key: "sql_pairs_retrieval_max_size",
+        kind: SettingKind::Integer,
+    },
+    SettingField {
+        key: "instructions_similarity_threshold",
+        kind: SIMILARITY,
+    },
+    SettingField {
+        key: "instructions_top_k",
+        kind: SettingKind::Integer,
+    },
+];
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            doc_endpoint: "https://docs.getanalytics.ai".to_string(),
+            is_oss: true,
+            engine_timeout: 30,
+            column_indexing_batch_size: 50,
+            table_retrieval_size: 10,
+            table_column_retrieval_size: 100,
+            allow_intent_classification: true,
+            allow_sql_generation_reasoning: true,
+            allow_sql_functions_retrieval: true,
+            enable_column_pruning: false,
+            max_sql_correction_retries: 3,
+            query_cache_maxsize: 1000,
+            query_cache_ttl: 3600,
+            langfuse_host: "https://cloud.langfuse.com".to_string(),
+            langfuse_enable: false,
+            logging_level: "INFO".to_string(),
+            development: false,
+            historical_question_retrieval_similarity_threshold: 0.9,
+            sql_pairs_similarity_threshold: 0.7,
+            sql_pairs_retrieval_max_size: 10,
+            instructions_similarity_threshold: 0.7,
+            instructions_top_k: 10,
+        }
+    }
+}
+
+impl SettingsPreset {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SettingsPreset::Production => "production",
+            SettingsPreset::Development => "development",
+            SettingsPreset::Debug => "debug",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        PRESETS
+            .iter()
+            .copied()
+            .find(|preset| preset.name().eq_ignore_ascii_case(name.trim()))
+    }
+
+    pub fn settings(&self) -> Settings {
+        let mut settings = Settings::default();
+        match self {
+            SettingsPreset::Production => {}
+            SettingsPreset::Development => {
+                settings.langfuse_enable = true;
+                settings.logging_level = "DEBUG".to_string();
+                settings.development = true;
+            }
+            SettingsPreset::Debug => {
+                settings.langfuse_enable = true;
+                settings.logging_level = "DEBUG".to_string();
+            }
+        }
+        settings
+    }
+}
+
+impl Settings {
+    pub fn from_manifest(values: &Mapping) -> Result<Self, String> {
+        let mut settings = match values.get("preset") {
+            Some(preset) => {
+                let name = preset.as_str().unwrap_or_default();
+                SettingsPreset::parse(name)
+                    .ok_or_else(|| {
+                        format!("unknown preset '{}', expected {}", name, preset_names())
+                    })?
+                    .settings()
+            }
+            None => Settings::default(),
+        };
+
+        for (key, value) in values {
+            let Some(key) = key.as_str() else {
+                return Err("setting names must be strings".to_string());
+            };
+            if key != "preset" {
+                settings.set_value(key, value.clone())?;
+            }
+        }
+
+        Ok(settings)
+    }
+
+    pub fn from_config(values: &Mapping) -> (Self, Vec<(String, String)>) {
+        let mut settings = Settings::default();
+        let mut warnings = Vec::new();
+        for (key, value) in values {
+            let Some(key) = key.as_str() else {
+                continue;
+            };
+            let Ok(field) = field(key) else {
+                warnings.push((
+                    key.to_string(),
+                    format!("unknown setting '{}' is dropped", key),
+                ));
+                continue;
+            };
+            let Err(error) = check_value(field, value) else {
+                settings.store(key, value.clone()).ok();
+                continue;
+            };
+            let warning = match settings.store(key, value.clone()) {
+                Ok(()) => format!("{}, keeping {}", error, scalar(value)),
+                Err(_) => format!("{}, using the default {}", error, settings.display(key)),
+            };
+            warnings.push((key.to_string(), warning));
+        }
+        (settings, warnings)
+    }
+
+    pub fn preset(&self) -> Option<SettingsPreset> {
+        PRESETS
+            .iter()
+            .copied()
+            .find(|preset| preset.settings() == *self)
+    }
+
+    pub fn display(&self, key: &str) -> String {
+        match self.value(key) {
+            Some(Value::Bool(value)) => value.to_string(),
+            Some(Value::Number(value)) => value.to_string(),
+            Some(Value::String(value)) => value,
+            _ => String::new(),
+        }
+    }
+
+    pub fn set(&mut self, key: &str, input: &str) -> Result<(), String> {
+        let field = field(key)?;
+        let input = input.trim();
+        let value = match field.kind {
+            SettingKind::Toggle => match input.to_ascii_lowercase().as_str() {
+                "true" | "yes" | "on" => Value::Bool(true),
+                "false" | "no" | "off" => Value::Bool(false),
+                _ => return Err(format!("{} must be true or false", key)),
+            },
+            SettingKind::Integer => input
+                .parse::<u64>()
+                .map(Value::from)
+                .map_err(|_| format!("{} must be a whole number", key))?,
+            SettingKind::Decimal { .. } => input
+                .parse::<f64>()
+                .map(Value::from)
+                .map_err(|_| format!("{} must be a number", key))?,
+            SettingKind::Choice(options) => options
+                .iter()
+                .find(|option| option.eq_ignore_ascii_case(input))
+                .map(|option| Value::from(*option))
+                .ok_or_else(|| format!("{} must be one of {}", key, options.join(", ")))?,
+            SettingKind::Url => Value::from(input),
+        };
+        self.set_value(key, value)
+    }
+
+    pub fn cycle(&mut self, key: &str, forward: bool) {
+        let Ok(field) = field(key) else {
+            return;
+        };
+        let next = match field.kind {
+            SettingKind::Toggle => Value::Bool(self.display(key) != "true"),
+            SettingKind::Choice(options) => {
+                let current = self.display(key);
+                let next = match options.iter().position(|option| *option == current) {
+                    Some(index) if forward => (index + 1) % options.len(),
+                    Some(index) => index.checked_sub(1).unwrap_or(options.len() - 1),
+                    None => 0,
+                };
+                Value::from(options[next])
+            }
+            _ => return,
+        };
+        let _ = self.set_value(key, next);
+    }
+
+    pub fn changed_keys(&self, other: &Settings) -> Vec<&'static str> {
+        SETTING_FIELDS
+            .iter()
+            .filter(|field| self.value(field.key) != other.value(field.key))
+            .map(|field| field.key)
+            .collect()
+    }
+
+    pub fn to_document(&self) -> Result<Value, serde_yaml::Error> {
+        serde_yaml::to_value(SettingsDocument { settings: self })
+    }
+
+    fn value(&self, key: &str) -> Option<Value> {
+        serde_yaml::to_value(self).ok()?.get(key).cloned()
+    }
+
+    fn set_value(&mut self, key: &str, value: Value) -> Result<(), String> {
+        check_value(field(key)?, &value)?;
+        self.store(key, value)
+    }
+
+    fn store(&mut self, key: &str, value: Value) -> Result<(), String> {
+        let mut values = serde_yaml::to_value(&*self).map_err(|e| e.to_string())?;
+        if let Some(mapping) = values.as_mapping_mut() {
+            mapping.insert(Value::from(key), value);
+        }
+        *self = serde_yaml::from_value(values).map_err(|e| format!("{}: {}", key, e))?;
+        Ok(())
+    }
+}
+
+fn field(key: &str) -> Result<&'static SettingField, String> {
+    SETTING_FIELDS
+        .iter()
+        .find(|field| field.key == key)
+        .ok_or_else(|| format!("unknown setting '{}'", key))
+}
+
+fn check_value(field: &SettingField, value: &Value) -> Result<(), String> {
+    let key = field.key;
+    match field.kind {
+        SettingKind::Toggle if !value.is_bool() => Err(format!("{} must be true or false", key)),
+        SettingKind::Integer if !value.is_u64() => Err(format!("{} must be a whole number", key)),
+        SettingKind::Decimal { min, max } => match value.as_f64() {
+            Some(number) if (min..=max).contains(&number) => Ok(()),
+            _ => Err(format!("{} must be a number from {} to {}", key, min, max)),
+        },
+        SettingKind::Choice(options) => match value.as_str() {
+            Some(choice) if options.contains(&choice) => Ok(()),
+            _ => Err(format!("{} must be one of {}", key, options.join(", "))),
+        },
+        SettingKind::Url => match value.as_str() {
+            Some(url) if url.starts_with("http://") || url.starts_with("https://") => Ok(()),
+            _ => Err(format!("{} must be an http:// or https:// URL", key)),
+        },
+        _ => Ok(()),
+    }
+}
+
+pub fn check_values(values: &Mapping) -> Vec<String> {
+    SETTING_FIELDS
+        .iter()
+        .filter_map(|field| {
+            let value = values.get(field.key)?;
+            check_value(field, value).err()
+        })
+        .collect()
+}
+
+fn scalar(value: &Value) -> String {
+    serde_yaml::to_string(value)
+        .map(|text| text.trim().to_string())
+        .unwrap_or_default()
+}
+
+pub fn preset_names() -> String {
+    PRESETS
+        .iter()
+        .map(|preset| preset.name())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASELINE_SETTINGS: &str = "\
+settings:
+  doc_endpoint: https://docs.getanalytics.ai
+  is_oss: true
+  engine_timeout: 30
+  column_indexing_batch_size: 50
+  table_retrieval_size: 10
+  table_column_retrieval_size: 100
+  allow_intent_classification: true
+  allow_sql_generation_reasoning: true
+  allow_sql_functions_retrieval: true
+  enable_column_pruning: false
+  max_sql_correction_retries: 3
+  query_cache_maxsize: 1000
+  query_cache_ttl: 3600
+  langfuse_host: https://cloud.langfuse.com
+  langfuse_enable: {{LANGFUSE_ENABLE}}
+  logging_level: {{LOGGING_LEVEL}}
+  development: {{DEVELOPMENT}}
+  historical_question_retrieval_similarity_threshold: 0.9
+  sql_pairs_similarity_threshold: 0.7
+  sql_pairs_retrieval_max_size: 10
+  instructions_similarity_threshold: 0.7
+  instructions_top_k: 10
+";
+
+    fn mapping(content: &str) -> Mapping {
+        serde_yaml::from_str(content).unwrap()
+    }
+
+    #[test]
+    fn presets_match_the_old_constants() {
+        let cases = [
+            (SettingsPreset::Production, "false", "INFO", "false"),
+            (SettingsPreset::Debug, "true", "DEBUG", "false"),
+            (SettingsPreset::Development, "true", "DEBUG", "true"),
+        ];
+        for (preset, langfuse_enable, logging_level, development) in cases {
+            let expected: Value = serde_yaml::from_str(
+                &BASELINE_SETTINGS
+                    .replace("{{LANGFUSE_ENABLE}}", langfuse_enable)
+                    .replace("{{LOGGING_LEVEL}}", logging_level)
+                    .replace("{{DEVELOPMENT}}", development),
+            )
+            .unwrap();
+            assert_eq!(
+                preset.settings().to_document().unwrap(),
+                expected,
+                "{}",
+                preset.name()
+            );
+            assert_eq!(preset.settings().preset(), Some(preset));
+        }
+    }
+
+    #[test]
+    fn set_cases() {
+        let cases = [
+            ("engine_timeout", "120", Ok("120")),
+            ("engine_timeout", " 0 ", Ok("0")),
+            (
+                "engine_timeout",
+                "-1",
+                Err("engine_timeout must be a whole number"),
+            ),
+            (
+                "engine_timeout",
+                "1.5",
+                Err("engine_timeout must be a whole number"),
+            ),
+            ("sql_pairs_similarity_threshold", "0", Ok("0.0")),
+            ("sql_pairs_similarity_threshold", "1", Ok("1.0")),
+            (
+                "sql_pairs_similarity_threshold",
+                "1.01",
+                Err("sql_pairs_similarity_threshold must be a number from 0 to 1"),
+            ),
+            (
+                "sql_pairs_similarity_threshold",
+                "high",
+                Err("sql_pairs_similarity_threshold must be a number"),
+            ),
+            ("development", "yes", Ok("true")),
+            (
+                "development",
+                "maybe",
+                Err("development must be true or false"),
+            ),
+            ("logging_level", "warning", Ok("WARNING")),
+            (
+                "logging_level",
+                "TRACE",
+                Err("logging_level must be one of DEBUG, INFO, WARNING, ERROR, CRITICAL"),
+            ),
+            (
+                "langfuse_host",
+                "http://langfuse:3000",
+                Ok("http://langfuse:3000"),
+            ),
+            (
+                "langfuse_host",
+                "langfuse:3000",
+                Err("langfuse_host must be an http:// or https:// URL"),
+            ),
+            (
+                "added_in_a_newer_release",
+                "1",
+                Err("unknown setting 'added_in_a_newer_release'"),
+            ),
+        ];
+
+        for (key, input, expected) in cases {
+            let mut settings = Settings::default();
+            let result = settings.set(key, input).map(|()| settings.display(key));
+            assert_eq!(
+                result,
+                expected.map(str::to_string).map_err(str::to_string),
+                "{} = {}",
+                key,
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn unknown_fields_are_rejected() {
+        let mut values = serde_yaml::to_value(Settings::default()).unwrap();
+        values
+            .as_mapping_mut()
+            .unwrap()
+            .insert("added_in_a_newer_release".into(), true.into());
+        let error = serde_yaml::from_value::<Settings>(values).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("unknown field `added_in_a_newer_release`"),
+            "{}",
+            error
+        );
+
+        assert_eq!(
+            Settings::from_manifest(&mapping("preset: debug\nengine_timout: 60\n")),
+            Err("unknown setting 'engine_timout'".to_string())
+        );
+        assert_eq!(
+            Settings::from_manifest(&mapping("preset: staging\n")),
+            Err("unknown preset 'staging', expected production, development, debug".to_string())
+        );
+    }
+
+    #[test]
+    fn from_config_keeps_what_it_can() {
+        let (settings, warnings) = Settings::from_config(&mapping(
+            "\
+engine_timeout: 900
+sql_pairs_similarity_threshold: 1.5
+logging_level: TRACE
+langfuse_host: langfuse:3000
+query_cache_ttl: an hour
+development: true
+added_in_a_newer_release: true
+",
+        ));
+
+        assert_eq!(settings.engine_timeout, 900);
+        assert_eq!(settings.sql_pairs_similarity_threshold, 1.5);
+        assert_eq!(settings.logging_level, "TRACE");
+        assert_eq!(settings.langfuse_host, "langfuse:3000");
+        assert_eq!(settings.query_cache_ttl, 3600);
+        assert!(settings.development);
+        let warnings: Vec<&str> = warnings
+            .iter()
+            .map(|(_, warning)| warning.as_str())
+            .collect();
+        assert_eq!(
+            warnings,
+            [
+                "sql_pairs_similarity_threshold must be a number from 0 to 1, keeping 1.5",
+                "logging_level must be one of DEBUG, INFO, WARNING, ERROR, CRITICAL, keeping TRACE",
+                "langfuse_host must be an http:// or https:// URL, keeping langfuse:3000",
+                "query_cache_ttl must be a whole number, using the default 3600",
+                "unknown setting 'added_in_a_newer_release' is dropped",
+            ]
+        );
+    }
+}
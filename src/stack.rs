@@ -0,0 +1,335 @@
+use color_eyre::{Result, eyre::eyre};
+use serde::{Serialize, Serializer};
+use serde_yaml::{Mapping, Value};
+
+pub const DEFAULT_QDRANT_VERSION: &str = "v1.11.0";
+
+const NETWORK: &str = "analytics";
+const PLATFORM: &str = "${PLATFORM}";
+const HEADER: &str = "# Generated by installer-analytics generate-compose.\n\
+                      # Change the generate-compose options instead of editing this file.\n";
+
+#[derive(Debug, Clone)]
+pub struct StackOptions {
+    pub include_northwind: bool,
+    pub include_ibis: bool,
+    pub qdrant_version: String,
+    pub platform: Option<String>,
+}
+
+impl Default for StackOptions {
+    fn default() -> Self {
+        Self {
+            include_northwind: true,
+            include_ibis: true,
+            qdrant_version: DEFAULT_QDRANT_VERSION.to_string(),
+            platform: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Build {
+    pub context: &'static str,
+    pub dockerfile: &'static str,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Service {
+    #[serde(skip)]
+    pub name: &'static str,
+    #[serde(skip)]
+    pub comment: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub build: Option<Build>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    pub restart: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub platform: Option<String>,
+    #[serde(
+        skip_serializing_if = "Vec::is_empty",
+        serialize_with = "serialize_environment"
+    )]
+    pub environment: Vec<(&'static str, &'static str)>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub env_file: Vec<&'static str>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub ports: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub expose: Vec<u16>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub volumes: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub networks: Vec<&'static str>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<&'static str>,
+}
+
+fn serialize_environment<S: Serializer>(
+    environment: &[(&'static str, &'static str)],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_map(environment.iter().copied())
+}
+
+impl Service {
+    fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            comment: None,
+            build: None,
+            image: None,
+            restart: "on-failure",
+            platform: None,
+            env_file: vec![".env"],
+            environment: Vec::new(),
+            ports: Vec::new(),
+            expose: Vec::new(),
+            volumes: Vec::new(),
+            networks: vec![NETWORK],
+            depends_on: Vec::new(),
+            command: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Stack {
+    pub services: Vec<Service>,
+    pub volumes: Vec<&'static str>,
+}
+
+impl Stack {
+    pub fn new(options: &StackOptions) -> Self {
+        let platform = options
+            .platform
+            .clone()
+            .unwrap_or_else(|| PLATFORM.to_string());
+        let mut services = vec![
+            Service {
+                build: Some(Build {
+                    context: "./bootstrap",
+                    dockerfile: "Dockerfile",
+                }),
+                platform: Some(platform.clone()),
+                env_file: Vec::new(),
+                environment: vec![("DATA_PATH", "/app/data")],
+                volumes: vec!["data:/app/data".to_string()],
+                networks: Vec::new(),
+                command: Some("/bin/sh /app/init.sh"),
+                ..Service::new("bootstrap")
+            },
+            Service {
+                image: Some("ghcr.io/idhamtrycode/analytics-engine:latest".to_string()),
+                platform: Some(platform.clone()),
+                ports: vec!["${ANALYTICS_ENGINE_PORT}:${ANALYTICS_ENGINE_PORT}".to_string()],
+                volumes: vec![
+                    "data:/usr/src/app/etc".to_string(),
+                    "${PROJECT_DIR}/data:/usr/src/app/data".to_string(),
+                ],
+                depends_on: vec!["bootstrap"],
+                ..Service::new("analytics-engine")
+            },
+        ];
+
+        if options.include_ibis {
+            services.push(Service {
+                image: Some("ghcr.io/idhamtrycode/analytics-engine-ibis:latest".to_string()),
+                platform: Some(platform.clone()),
+                ports: vec!["${IBIS_SERVER_PORT}:${IBIS_SERVER_PORT}".to_string()],
+                volumes: vec!["${LOCAL_STORAGE:-.}:/usr/src/app/data".to_string()],
+                depends_on: vec!["analytics-engine"],
+                ..Service::new("ibis-server")
+            });
+        }
+
+        services.push(Service {
+            image: Some("ghcr.io/h-pun/analytics-service:latest".to_string()),
+            platform: Some(platform.clone()),
+            ports: vec![
+                "${AI_SERVICE_FORWARD_PORT:-5555}:${ANALYTICS_AI_SERVICE_PORT:-5555}".to_string(),
+            ],
+            volumes: vec![
+                "./config.yaml:/app/config.yaml:ro".to_string(),
+                "./data:/app/data:ro".to_string(),
+            ],
+            depends_on: vec!["qdrant"],
+            ..Service::new("analytics-service")
+        });
+
+        services.push(Service {
+            image: Some(format!("qdrant/qdrant:{}", options.qdrant_version)),
+            env_file: Vec::new(),
+            expose: vec![6333, 6334],
+            volumes: vec!["qdrant_data:/qdrant/storage".to_string()],
+            ..Service::new("qdrant")
+        });
+
+        if options.include_northwind {
+            services.push(Service {
+                comment: Some("Northwind PostgreSQL Demo Database"),
+                image: Some("postgres:15".to_string()),
+                restart: "unless-stopped",
+                ports: vec!["${NORTHWIND_DB_PORT:-5432}:5432".to_string()],
+                volumes: vec![
+                    "./northwind.sql:/docker-entrypoint-initdb.d/northwind.sql:ro".to_string(),
+                    "northwind_data:/var/lib/postgresql/data".to_string(),
+                ],
+                ..Service::new("northwind-db")
+            });
+        }
+
+        let mut ui_depends_on = vec!["analytics-service", "analytics-engine"];
+        if options.include_ibis {
+            ui_depends_on.push("ibis-server");
+        }
+        services.push(Service {
+            image: Some("ghcr.io/h-pun/analytics-ui:latest".to_string()),
+            platform: Some(platform),
+            ports: vec!["${HOST_PORT:-3000}:3000".to_string()],
+            volumes: vec!["data:/app/data".to_string()],
+            depends_on: ui_depends_on,
+            ..Service::new("analytics-ui")
+        });
+
+        let mut volumes = vec!["data", "qdrant_data"];
+        if options.include_northwind {
+            volumes.push("northwind_data");
+        }
+
+        Self { services, volumes }
+    }
+
+    pub fn render(&self) -> Result<String> {
+        let volumes: Mapping = self
+            .volumes
+            .iter()
+            .map(|volume| (Value::from(*volume), Value::Mapping(Mapping::new())))
+            .collect();
+        let mut bridge = Mapping::new();
+        bridge.insert("driver".into(), "bridge".into());
+        let mut networks = Mapping::new();
+        networks.insert(NETWORK.into(), Value::Mapping(bridge));
+
+        let mut content = HEADER.to_string();
+        content.push('\n');
+        content.push_str(&to_yaml(&Mapping::from_iter([(
+            "volumes".into(),
+            Value::Mapping(volumes),
+        )]))?);
+        content.push('\n');
+        content.push_str(&to_yaml(&Mapping::from_iter([(
+            "networks".into(),
+            Value::Mapping(networks),
+        )]))?);
+        content.push_str("\nservices:\n");
+
+        for (index, service) in self.services.iter().enumerate() {
+            if index > 0 {
+                content.push('\n');
+            }
+            if let Some(comment) = service.comment {
+                content.push_str(&format!("  # {}\n", comment));
+            }
+            let entry = Mapping::from_iter([(
+                Value::from(service.name),
+                serde_yaml::to_value(service)
+                    .map_err(|e| eyre!("Failed to serialize service {}: {}", service.name, e))?,
+            )]);
+            for line in to_yaml(&entry)?.lines() {
+                content.push_str(&format!("  {}\n", line));
+            }
+        }
+        Ok(content)
+    }
+}
+
+fn to_yaml(value: &Mapping) -> Result<String> {
+    serde_yaml::to_string(value).map_err(|e| eyre!("Failed to render docker-compose.yaml: {}", e))
+}
+
+pub fn validate_image_tag(tag: &str) -> Result<String, String> {
+    let valid = tag.len() <= 128
+        && tag
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_')
+        && tag
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'));
+    if valid {
+        Ok(tag.to_string())
+    } else {
+        Err(format!(
+            "'{}' is not a valid image tag: use letters, digits, '_', '.' and '-', \
+             starting with a letter, digit or '_', at most 128 characters",
+            tag
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compose;
+
+    #[test]
+    fn rendered_stack_parses_back() {
+        let options = StackOptions {
+            include_ibis: false,
+            qdrant_version: "v1.12.0".to_string(),
+            ..StackOptions::default()
+        };
+        let content = Stack::new(&options).render().unwrap();
+        assert!(content.contains("  # Northwind PostgreSQL Demo Database\n  northwind-db:\n"));
+
+        let services = compose::parse_services(&content).unwrap();
+        let names: Vec<&str> = services.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "bootstrap",
+                "analytics-engine",
+                "analytics-service",
+                "qdrant",
+                "northwind-db",
+                "analytics-ui"
+            ]
+        );
+        let ui = services.iter().find(|s| s.name == "analytics-ui").unwrap();
+        assert_eq!(ui.ports, ["${HOST_PORT:-3000}:3000"]);
+        assert_eq!(ui.depends_on, ["analytics-service", "analytics-engine"]);
+        let qdrant = services.iter().find(|s| s.name == "qdrant").unwrap();
+        assert_eq!(qdrant.image.as_deref(), Some("qdrant/qdrant:v1.12.0"));
+
+        let document: Value = serde_yaml::from_str(&content).unwrap();
+        assert_eq!(
+            document["services"]["bootstrap"]["environment"]["DATA_PATH"],
+            Value::from("/app/data")
+        );
+        assert!(document["volumes"]["northwind_data"].is_mapping());
+    }
+
+    #[test]
+    fn image_tags() {
+        for tag in ["v1.11.0", "latest", "_dev", "1.12-unprivileged"] {
+            assert!(validate_image_tag(tag).is_ok(), "{}", tag);
+        }
+        let too_long = "a".repeat(129);
+        for tag in [
+            "",
+            "-v1",
+            ".v1",
+            "v1 rm",
+            "v1;rm",
+            "v1:latest",
+            "v1/x",
+            too_long.as_str(),
+        ] {
+            assert!(validate_image_tag(tag).is_err(), "{}", tag);
+        }
+    }
+}
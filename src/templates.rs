@@ -1,274 +1,1543 @@
+use color_eyre::{Result, eyre::eyre};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use crate::embeddings;
+use crate::settings::Settings;
+use crate::utils;
+
+#[derive(Debug, Clone)]
 pub struct ConfigTemplate {
-    pub key: &'static str,
-    pub name: &'static str,
-    pub description: &'static str,
-    template: &'static str,
-    pipeline_overrides: &'static [(&'static str, &'static str)],
-    settings: TemplateSettings,
+    pub key: String,
+    pub name: String,
+    pub description: String,
+    template: String,
+    pub credentials: Vec<Credential>,
+    pipeline_overrides: Vec<(String, String)>,
+    settings: Settings,
+    pub source: TemplateSource,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemplateSource {
+    BuiltIn,
+    User(PathBuf),
+    Project(PathBuf),
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum CredentialKind {
+    Secret {
+        #[serde(default)]
+        prefix: String,
+    },
+    Url,
+    Text,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Credential {
+    pub env_var: String,
+    pub label: String,
+    #[serde(flatten)]
+    pub kind: CredentialKind,
+    #[serde(default)]
+    pub default: String,
+    #[serde(default)]
+    pub placeholder: Option<String>,
 }
 
-#[derive(Clone, Copy)]
-pub struct TemplateSettings {
-    pub langfuse_enable: bool,
-    pub logging_level: &'static str,
-    pub development: bool,
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TemplateManifest {
+    key: String,
+    name: String,
+    #[serde(default)]
+    description: String,
+    provider: Option<String>,
+    provider_file: Option<PathBuf>,
+    #[serde(default)]
+    credentials: Vec<Credential>,
+    #[serde(default)]
+    pipeline_overrides: BTreeMap<String, String>,
+    #[serde(default)]
+    settings: serde_yaml::Mapping,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Pipe {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub llm: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedder: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub engine: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub document_store: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PipelineDocument {
+    #[serde(rename = "type")]
+    kind: String,
+    pipes: Vec<Pipe>,
+}
+
+enum TemplateDocument {
+    Yaml(serde_yaml::Value),
+    Section(&'static str),
+}
+
+enum PatternSegment<'a> {
+    Literal(&'a str),
+    Placeholder(&'a str),
+}
+
+#[derive(Debug)]
+pub struct TemplateCatalog {
+    pub templates: Vec<ConfigTemplate>,
+    pub warnings: Vec<String>,
 }
 
 const ENGINE_SECTION: &str = include_str!("../config_templates/common/engine.yaml");
 const PIPELINE_TEMPLATE: &str = include_str!("../config_templates/common/pipeline.yaml");
-const SETTINGS_TEMPLATE: &str = include_str!("../config_templates/common/settings.yaml");
-
-const PIPELINE_DEFAULT_BINDINGS: &[(&str, &str)] = &[
-    ("{{LLM_SQL_ANSWER}}", "litellm_llm.default"),
-    ("{{LLM_DATA_ASSISTANCE}}", "litellm_llm.default"),
-    ("{{LLM_CHART_GENERATION}}", "litellm_llm.default"),
-    ("{{LLM_CHART_ADJUSTMENT}}", "litellm_llm.default"),
-    ("{{LLM_SQL_GENERATION_REASONING}}", "litellm_llm.default"),
+
+const ENGINE_MARKER: &str = "{{ENGINE_SECTION}}";
+const PIPELINE_MARKER: &str = "{{PIPELINE_SECTION}}";
+const SETTINGS_MARKER: &str = "{{SETTINGS_SECTION}}";
+const SECTIONS: &[&str] = &[ENGINE_MARKER, PIPELINE_MARKER, SETTINGS_MARKER];
+
+const MANIFEST_SUFFIXES: &[&str] = &[".template.yaml", ".template.yml"];
+
+const BUILT_IN_TEMPLATES: &[(&str, &str)] = &[
     (
-        "{{LLM_FOLLOWUP_SQL_GENERATION_REASONING}}",
-        "litellm_llm.default",
+        include_str!("../config_templates/providers/openai.template.yaml"),
+        include_str!("../config_templates/providers/openai.yaml"),
     ),
-];
-
-const NO_OVERRIDES: &[(&str, &str)] = &[];
-const OVERRIDES_DEEPSEEK: &[(&str, &str)] = &[
-    ("{{LLM_SQL_ANSWER}}", "litellm_llm.deepseek/deepseek-chat"),
     (
-        "{{LLM_DATA_ASSISTANCE}}",
-        "litellm_llm.deepseek/deepseek-chat",
+        include_str!("../config_templates/providers/anthropic.template.yaml"),
+        include_str!("../config_templates/providers/anthropic.yaml"),
     ),
     (
-        "{{LLM_SQL_GENERATION_REASONING}}",
-        "litellm_llm.deepseek/deepseek-reasoner",
+        include_str!("../config_templates/providers/azure.template.yaml"),
+        include_str!("../config_templates/providers/azure.yaml"),
     ),
     (
-        "{{LLM_FOLLOWUP_SQL_GENERATION_REASONING}}",
-        "litellm_llm.deepseek/deepseek-reasoner",
+        include_str!("../config_templates/providers/bedrock.template.yaml"),
+        include_str!("../config_templates/providers/bedrock.yaml"),
     ),
-];
-const OVERRIDES_GEMINI_CHART: &[(&str, &str)] = &[
     (
-        "{{LLM_CHART_GENERATION}}",
-        "litellm_llm.gemini-llm-for-chart",
+        include_str!("../config_templates/providers/deepseek.template.yaml"),
+        include_str!("../config_templates/providers/deepseek.yaml"),
     ),
     (
-        "{{LLM_CHART_ADJUSTMENT}}",
-        "litellm_llm.gemini-llm-for-chart",
+        include_str!("../config_templates/providers/google_ai_studio.template.yaml"),
+        include_str!("../config_templates/providers/google_ai_studio.yaml"),
     ),
-];
-const OVERRIDES_QWEN3: &[(&str, &str)] = &[
-    ("{{LLM_SQL_ANSWER}}", "litellm_llm.qwen3-fast"),
-    ("{{LLM_DATA_ASSISTANCE}}", "litellm_llm.qwen3-fast"),
     (
-        "{{LLM_SQL_GENERATION_REASONING}}",
-        "litellm_llm.qwen3-thinking",
+        include_str!("../config_templates/providers/google_vertexai.template.yaml"),
+        include_str!("../config_templates/providers/google_vertexai.yaml"),
     ),
     (
-        "{{LLM_FOLLOWUP_SQL_GENERATION_REASONING}}",
-        "litellm_llm.qwen3-thinking",
+        include_str!("../config_templates/providers/grok.template.yaml"),
+        include_str!("../config_templates/providers/grok.yaml"),
     ),
-];
-const OVERRIDES_ZHIPU: &[(&str, &str)] = &[
-    ("{{LLM_SQL_ANSWER}}", "litellm_llm.glm45-fast"),
-    ("{{LLM_DATA_ASSISTANCE}}", "litellm_llm.glm45-fast"),
     (
-        "{{LLM_SQL_GENERATION_REASONING}}",
-        "litellm_llm.glm45-thinking",
+        include_str!("../config_templates/providers/groq.template.yaml"),
+        include_str!("../config_templates/providers/groq.yaml"),
     ),
     (
-        "{{LLM_FOLLOWUP_SQL_GENERATION_REASONING}}",
-        "litellm_llm.glm45-thinking",
+        include_str!("../config_templates/providers/lm_studio.template.yaml"),
+        include_str!("../config_templates/providers/lm_studio.yaml"),
+    ),
+    (
+        include_str!("../config_templates/providers/ollama.template.yaml"),
+        include_str!("../config_templates/providers/ollama.yaml"),
+    ),
+    (
+        include_str!("../config_templates/providers/open_router.template.yaml"),
+        include_str!("../config_templates/providers/open_router.yaml"),
+    ),
+    (
+        include_str!("../config_templates/providers/qwen3.template.yaml"),
+        include_str!("../config_templates/providers/qwen3.yaml"),
+    ),
+    (
+        include_str!("../config_templates/providers/zhipu.template.yaml"),
+        include_str!("../config_templates/providers/zhipu.yaml"),
     ),
 ];
 
-const SETTINGS_OPENAI: TemplateSettings = TemplateSettings {
-    langfuse_enable: false,
-    logging_level: "INFO",
-    development: false,
-};
-const SETTINGS_DEBUG_FALSE: TemplateSettings = TemplateSettings {
-    langfuse_enable: true,
-    logging_level: "DEBUG",
-    development: false,
-};
-const SETTINGS_DEBUG_TRUE: TemplateSettings = TemplateSettings {
-    langfuse_enable: true,
-    logging_level: "DEBUG",
-    development: true,
-};
-const SETTINGS_OPEN_ROUTER: TemplateSettings = TemplateSettings {
-    langfuse_enable: true,
-    logging_level: "DEBUG",
-    development: false,
-};
-
-const CONFIG_OPENAI: ConfigTemplate = ConfigTemplate {
-    key: "openai",
-    name: "OpenAI (GPT-4o mini)",
-    description: "Use OpenAI gpt-4o-mini with text-embedding-3-large",
-    template: include_str!("../config_templates/providers/openai.yaml"),
-    pipeline_overrides: NO_OVERRIDES,
-    settings: SETTINGS_OPENAI,
-};
-const CONFIG_ANTHROPIC: ConfigTemplate = ConfigTemplate {
-    key: "anthropic",
-    name: "Anthropic Claude 3.7 Sonnet",
-    description: "Anthropic Claude via api.anthropic.com",
-    template: include_str!("../config_templates/providers/anthropic.yaml"),
-    pipeline_overrides: NO_OVERRIDES,
-    settings: SETTINGS_DEBUG_FALSE,
-};
-const CONFIG_AZURE: ConfigTemplate = ConfigTemplate {
-    key: "azure",
-    name: "Azure OpenAI",
-    description: "Azure OpenAI deployment using gpt-4",
-    template: include_str!("../config_templates/providers/azure.yaml"),
-    pipeline_overrides: NO_OVERRIDES,
-    settings: SETTINGS_DEBUG_FALSE,
-};
-const CONFIG_BEDROCK: ConfigTemplate = ConfigTemplate {
-    key: "bedrock",
-    name: "AWS Bedrock",
-    description: "Amazon Bedrock Claude Sonnet + Titan embeddings",
-    template: include_str!("../config_templates/providers/bedrock.yaml"),
-    pipeline_overrides: NO_OVERRIDES,
-    settings: SETTINGS_DEBUG_FALSE,
-};
-const CONFIG_DEEPSEEK: ConfigTemplate = ConfigTemplate {
-    key: "deepseek",
-    name: "DeepSeek",
-    description: "DeepSeek reasoning and chat models via api.deepseek.com",
-    template: include_str!("../config_templates/providers/deepseek.yaml"),
-    pipeline_overrides: OVERRIDES_DEEPSEEK,
-    settings: SETTINGS_DEBUG_TRUE,
-};
-const CONFIG_GOOGLE_AI: ConfigTemplate = ConfigTemplate {
-    key: "google_ai_studio",
-    name: "Google Gemini (AI Studio)",
-    description: "Gemini 2.0 Flash via Google AI Studio",
-    template: include_str!("../config_templates/providers/google_ai_studio.yaml"),
-    pipeline_overrides: OVERRIDES_GEMINI_CHART,
-    settings: SETTINGS_DEBUG_TRUE,
-};
-const CONFIG_GOOGLE_VERTEX: ConfigTemplate = ConfigTemplate {
-    key: "google_vertexai",
-    name: "Google Gemini (Vertex AI)",
-    description: "Gemini 2.5 Flash via Vertex AI",
-    template: include_str!("../config_templates/providers/google_vertexai.yaml"),
-    pipeline_overrides: OVERRIDES_GEMINI_CHART,
-    settings: SETTINGS_DEBUG_TRUE,
-};
-const CONFIG_GROK: ConfigTemplate = ConfigTemplate {
-    key: "grok",
-    name: "xAI Grok",
-    description: "xAI Grok 3 via api.x.ai",
-    template: include_str!("../config_templates/providers/grok.yaml"),
-    pipeline_overrides: NO_OVERRIDES,
-    settings: SETTINGS_DEBUG_TRUE,
-};
-const CONFIG_GROQ: ConfigTemplate = ConfigTemplate {
-    key: "groq",
-    name: "Groq Llama 3.3",
-    description: "Groq API with Llama 3.3 70B specdec",
-    template: include_str!("../config_templates/providers/groq.yaml"),
-    pipeline_overrides: NO_OVERRIDES,
-    settings: SETTINGS_DEBUG_TRUE,
-};
-const CONFIG_LM_STUDIO: ConfigTemplate = ConfigTemplate {
-    key: "lm_studio",
-    name: "LM Studio",
-    description: "Local LM Studio endpoint (phi-4 + nomic embeddings)",
-    template: include_str!("../config_templates/providers/lm_studio.yaml"),
-    pipeline_overrides: NO_OVERRIDES,
-    settings: SETTINGS_DEBUG_TRUE,
-};
-const CONFIG_OLLAMA: ConfigTemplate = ConfigTemplate {
-    key: "ollama",
-    name: "Ollama",
-    description: "Local Ollama with phi4:14b",
-    template: include_str!("../config_templates/providers/ollama.yaml"),
-    pipeline_overrides: NO_OVERRIDES,
-    settings: SETTINGS_DEBUG_TRUE,
-};
-const CONFIG_OPEN_ROUTER: ConfigTemplate = ConfigTemplate {
-    key: "open_router",
-    name: "OpenRouter",
-    description: "OpenRouter Claude 3.7 Sonnet",
-    template: include_str!("../config_templates/providers/open_router.yaml"),
-    pipeline_overrides: NO_OVERRIDES,
-    settings: SETTINGS_OPEN_ROUTER,
-};
-const CONFIG_QWEN3: ConfigTemplate = ConfigTemplate {
-    key: "qwen3",
-    name: "Qwen3",
-    description: "Qwen3 via OpenRouter with thinking and fast modes",
-    template: include_str!("../config_templates/providers/qwen3.yaml"),
-    pipeline_overrides: OVERRIDES_QWEN3,
-    settings: SETTINGS_DEBUG_TRUE,
-};
-const CONFIG_ZHIPU: ConfigTemplate = ConfigTemplate {
-    key: "zhipu",
-    name: "Zhipu GLM-4.5",
-    description: "Zhipu AI GLM-4.5 with thinking/fast variants",
-    template: include_str!("../config_templates/providers/zhipu.yaml"),
-    pipeline_overrides: OVERRIDES_ZHIPU,
-    settings: SETTINGS_DEBUG_TRUE,
-};
-
-pub const CONFIG_TEMPLATES: &[ConfigTemplate] = &[
-    CONFIG_OPENAI,
-    CONFIG_ANTHROPIC,
-    CONFIG_AZURE,
-    CONFIG_BEDROCK,
-    CONFIG_DEEPSEEK,
-    CONFIG_GOOGLE_AI,
-    CONFIG_GOOGLE_VERTEX,
-    CONFIG_GROK,
-    CONFIG_GROQ,
-    CONFIG_LM_STUDIO,
-    CONFIG_OLLAMA,
-    CONFIG_OPEN_ROUTER,
-    CONFIG_QWEN3,
-    CONFIG_ZHIPU,
-];
+impl TemplateSource {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TemplateSource::BuiltIn => "built-in",
+            TemplateSource::User(_) => "user",
+            TemplateSource::Project(_) => "project",
+        }
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            TemplateSource::BuiltIn => None,
+            TemplateSource::User(path) | TemplateSource::Project(path) => Some(path),
+        }
+    }
+}
+
+impl TemplateCatalog {
+    pub fn load() -> Self {
+        Self::load_from(
+            user_template_dir().as_deref(),
+            &utils::project_root().join("templates"),
+        )
+    }
+
+    fn load_from(user_dir: Option<&Path>, project_dir: &Path) -> Self {
+        let mut catalog = Self::built_in();
+
+        if let Some(dir) = user_dir {
+            catalog.load_dir(dir, TemplateSource::User);
+        }
+        catalog.load_dir(project_dir, TemplateSource::Project);
+
+        catalog
+    }
+
+    pub fn built_in() -> Self {
+        let mut catalog = Self {
+            templates: Vec::new(),
+            warnings: Vec::new(),
+        };
+
+        for (manifest, provider) in BUILT_IN_TEMPLATES {
+            let result = serde_yaml::from_str::<TemplateManifest>(manifest)
+                .map_err(|e| eyre!(e))
+                .and_then(|manifest| {
+                    manifest.into_template(provider.to_string(), TemplateSource::BuiltIn)
+                });
+            match result {
+                Ok(template) => catalog.insert(template),
+                Err(e) => catalog
+                    .warnings
+                    .push(format!("Skipped built-in template: {}", e)),
+            }
+        }
+
+        catalog
+    }
+
+    pub fn find(&self, key: &str) -> Option<&ConfigTemplate> {
+        self.templates.iter().find(|template| template.key == key)
+    }
+
+    fn load_dir(&mut self, dir: &Path, source: fn(PathBuf) -> TemplateSource) {
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == ErrorKind::NotFound => return,
+            Err(e) => {
+                self.warnings
+                    .push(format!("Could not read {}: {}", dir.display(), e));
+                return;
+            }
+        };
+
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| is_manifest(path))
+            .collect();
+        paths.sort();
+
+        for path in paths {
+            match load_manifest(&path, source(path.clone())) {
+                Ok(template) => self.insert(template),
+                Err(e) => self
+                    .warnings
+                    .push(format!("Skipped {}: {}", path.display(), e)),
+            }
+        }
+    }
+
+    fn insert(&mut self, template: ConfigTemplate) {
+        match self
+            .templates
+            .iter_mut()
+            .find(|existing| existing.key == template.key)
+        {
+            Some(existing) => *existing = template,
+            None => self.templates.push(template),
+        }
+    }
+}
+
+impl TemplateManifest {
+    fn into_template(self, provider: String, source: TemplateSource) -> Result<ConfigTemplate> {
+        let key_is_valid = !self.key.is_empty()
+            && self
+                .key
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+        if !key_is_valid {
+            return Err(eyre!(
+                "key '{}' must only use lowercase letters, digits, '-' and '_'",
+                self.key
+            ));
+        }
+
+        if self.name.trim().is_empty() {
+            return Err(eyre!("template '{}' has an empty name", self.key));
+        }
+
+        let pipeline_overrides: Vec<(String, String)> =
+            self.pipeline_overrides.into_iter().collect();
+        default_pipes(&pipeline_overrides)?;
+        split_documents(&provider).map_err(|e| eyre!("provider YAML: {}", e))?;
+        let settings =
+            Settings::from_manifest(&self.settings).map_err(|e| eyre!("settings: {}", e))?;
+
+        let placeholders = find_placeholders(&provider);
+        for credential in &self.credentials {
+            if let Some(placeholder) = &credential.placeholder
+                && !placeholders.contains(placeholder)
+            {
+                return Err(eyre!(
+                    "{} fills <{}>, which does not appear in the provider YAML",
+                    credential.env_var,
+                    placeholder
+                ));
+            }
+        }
+
+        Ok(ConfigTemplate {
+            key: self.key,
+            name: self.name,
+            description: self.description,
+            template: provider,
+            credentials: self.credentials,
+            pipeline_overrides,
+            settings,
+            source,
+        })
+    }
+}
+
+fn load_manifest(path: &Path, source: TemplateSource) -> Result<ConfigTemplate> {
+    let content = fs::read_to_string(path)?;
+    let manifest: TemplateManifest = serde_yaml::from_str(&content)?;
+
+    let provider = match (&manifest.provider, &manifest.provider_file) {
+        (Some(provider), None) => provider.clone(),
+        (None, Some(file)) => {
+            let provider_path = path.parent().unwrap_or(Path::new(".")).join(file);
+            fs::read_to_string(&provider_path)
+                .map_err(|e| eyre!("failed to read {}: {}", provider_path.display(), e))?
+        }
+        (Some(_), Some(_)) => return Err(eyre!("set either provider or provider_file, not both")),
+        (None, None) => return Err(eyre!("missing provider or provider_file")),
+    };
+
+    manifest.into_template(provider, source)
+}
+
+fn is_manifest(path: &Path) -> bool {
+    let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+        return false;
+    };
+    path.is_file()
+        && MANIFEST_SUFFIXES
+            .iter()
+            .any(|suffix| file_name.ends_with(suffix))
+}
+
+pub fn user_template_dir() -> Option<PathBuf> {
+    let config_home = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+
+    Some(config_home.join("installer-analytics").join("templates"))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelSummary {
+    pub kind: String,
+    pub provider: String,
+    pub alias: String,
+    pub model: String,
+}
+
+impl ModelSummary {
+    pub fn binding(&self) -> String {
+        format!("{}.{}", self.provider, self.alias)
+    }
+}
+
+impl Credential {
+    pub fn validate(&self, value: &str) -> Result<(), String> {
+        let value = value.trim();
+
+        if value.is_empty() {
+            return Err(format!("{} is required!", self.label));
+        }
+
+        match &self.kind {
+            CredentialKind::Secret { prefix } => {
+                if !value.starts_with(prefix.as_str()) {
+                    return Err(format!(
+                        "Invalid {} format (should start with '{}')",
+                        self.label, prefix
+                    ));
+                }
+            }
+            CredentialKind::Url => {
+                if !value.starts_with("http://") && !value.starts_with("https://") {
+                    return Err(format!(
+                        "Invalid {} (should start with 'http://' or 'https://')",
+                        self.label
+                    ));
+                }
+            }
+            CredentialKind::Text => {}
+        }
+
+        Ok(())
+    }
+}
 
 impl ConfigTemplate {
-    pub fn render(&self) -> String {
-        let mut content = self.template.replace("{{ENGINE_SECTION}}", ENGINE_SECTION);
+    pub fn render(
+        &self,
+        values: &[(String, String)],
+        pipes: &[Pipe],
+        settings: &Settings,
+    ) -> Result<String> {
+        let mut documents = self.documents(values, pipes, settings)?;
+        apply_embedding_dimensions(&mut documents, pipes)?;
+
+        let content = documents
+            .iter()
+            .map(serde_yaml::to_string)
+            .collect::<std::result::Result<Vec<_>, _>>()?
+            .join("---\n");
+
+        let markers = find_markers(&content);
+        if !markers.is_empty() {
+            return Err(eyre!(
+                "config.yaml still contains template markers: {}",
+                markers.join(", ")
+            ));
+        }
+
+        let unresolved = find_placeholders(&content);
+        if !unresolved.is_empty() {
+            return Err(eyre!(
+                "config.yaml still contains unresolved placeholders: {}",
+                format_placeholders(&unresolved)
+            ));
+        }
+
+        let reparsed = serde_yaml::Deserializer::from_str(&content)
+            .map(serde_yaml::Value::deserialize)
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| eyre!("rendered config.yaml is not valid YAML: {}", e))?;
+        if reparsed != documents {
+            return Err(eyre!(
+                "rendered config.yaml does not round-trip to the same documents"
+            ));
+        }
+
+        Ok(content)
+    }
+
+    pub fn embedding_warnings(&self, values: &[(String, String)], pipes: &[Pipe]) -> Vec<String> {
+        let mut documents = match self.documents(values, pipes, &self.settings) {
+            Ok(documents) => documents,
+            Err(e) => return vec![e.to_string()],
+        };
+        apply_embedding_dimensions(&mut documents, pipes).unwrap_or_else(|e| vec![e.to_string()])
+    }
+
+    fn documents(
+        &self,
+        values: &[(String, String)],
+        pipes: &[Pipe],
+        settings: &Settings,
+    ) -> Result<Vec<serde_yaml::Value>> {
+        let mut documents = Vec::new();
+        let mut sections = Vec::new();
+
+        for document in split_documents(&self.template)? {
+            match document {
+                TemplateDocument::Yaml(value) => documents.push(value),
+                TemplateDocument::Section(section) => {
+                    documents.extend(render_section(section, pipes, settings)?);
+                    sections.push(section);
+                }
+            }
+        }
+        for section in SECTIONS {
+            if !sections.contains(section) {
+                documents.extend(render_section(section, pipes, settings)?);
+            }
+        }
+
+        for document in &mut documents {
+            fill_placeholders(document, values);
+        }
+
+        Ok(documents)
+    }
+
+    pub fn placeholders(&self) -> Vec<String> {
+        find_placeholders(&self.template)
+    }
+
+    pub fn missing_placeholders(&self, values: &[(String, String)]) -> Vec<String> {
+        self.placeholders()
+            .into_iter()
+            .filter(|placeholder| {
+                !values
+                    .iter()
+                    .any(|(name, value)| name == placeholder && !value.trim().is_empty())
+            })
+            .collect()
+    }
+
+    pub fn pipes(&self) -> Vec<Pipe> {
+        default_pipes(&self.pipeline_overrides).unwrap_or_default()
+    }
+
+    pub fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
+    pub fn match_documents(
+        &self,
+        documents: &[serde_yaml::Value],
+    ) -> Option<Vec<(String, String)>> {
+        let is_model_document = |document: &serde_yaml::Value| {
+            matches!(
+                document.get("type").and_then(|kind| kind.as_str()),
+                Some("llm" | "embedder")
+            )
+        };
+        let expected: Vec<serde_yaml::Value> = split_documents(&self.template)
+            .ok()?
+            .into_iter()
+            .filter_map(|document| match document {
+                TemplateDocument::Yaml(value) => Some(value),
+                TemplateDocument::Section(_) => None,
+            })
+            .filter(is_model_document)
+            .collect();
+        let actual_count = documents
+            .iter()
+            .filter(|document| is_model_document(document))
+            .count();
+        if expected.is_empty() || expected.len() != actual_count {
+            return None;
+        }
+
+        let mut captures = Vec::new();
+        for document in &expected {
+            let actual = documents.iter().find(|actual| {
+                actual.get("type") == document.get("type")
+                    && actual.get("provider") == document.get("provider")
+            })?;
+            if !match_value(document, actual, &mut captures) {
+                return None;
+            }
+        }
+
+        Some(captures)
+    }
 
-        let pipeline = render_pipeline(self.pipeline_overrides);
-        content = content.replace("{{PIPELINE_SECTION}}", &pipeline);
+    pub fn generation_model(&self) -> Option<String> {
+        let models: Vec<ModelSummary> = self
+            .models()
+            .into_iter()
+            .filter(|model| model.kind == "llm")
+            .collect();
+        models
+            .iter()
+            .find(|model| model.alias == "default")
+            .or_else(|| models.first())
+            .map(|model| model.model.clone())
+    }
+
+    pub fn providers(&self, kind: &str) -> Vec<String> {
+        let documents = self.documents(&[], &[], &self.settings).unwrap_or_default();
+        let mut providers: Vec<String> = Vec::new();
+        for document in &documents {
+            if document.get("type").and_then(|value| value.as_str()) != Some(kind) {
+                continue;
+            }
+            if let Some(provider) = document.get("provider").and_then(|value| value.as_str())
+                && !providers.iter().any(|known| known == provider)
+            {
+                providers.push(provider.to_string());
+            }
+        }
+        providers
+    }
 
-        let settings = render_settings(&self.settings);
-        content.replace("{{SETTINGS_SECTION}}", &settings)
+    pub fn models(&self) -> Vec<ModelSummary> {
+        let mut models = Vec::new();
+
+        let values = split_documents(&self.template)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|document| match document {
+                TemplateDocument::Yaml(value) => Some(value),
+                TemplateDocument::Section(_) => None,
+            });
+        for value in values {
+            let Some(kind) = value.get("type").and_then(|kind| kind.as_str()) else {
+                continue;
+            };
+            if kind != "llm" && kind != "embedder" {
+                continue;
+            }
+            let provider = value
+                .get("provider")
+                .and_then(|provider| provider.as_str())
+                .unwrap_or_default();
+
+            let entries = value
+                .get("models")
+                .and_then(|models| models.as_sequence())
+                .into_iter()
+                .flatten();
+            for entry in entries {
+                let Some(model) = entry.get("model").and_then(|model| model.as_str()) else {
+                    continue;
+                };
+                let alias = entry
+                    .get("alias")
+                    .and_then(|alias| alias.as_str())
+                    .unwrap_or(model);
+                models.push(ModelSummary {
+                    kind: kind.to_string(),
+                    provider: provider.to_string(),
+                    alias: alias.to_string(),
+                    model: model.to_string(),
+                });
+            }
+        }
+
+        models
     }
 }
 
-fn render_pipeline(overrides: &[(&'static str, &'static str)]) -> String {
-    let mut rendered = PIPELINE_TEMPLATE.to_string();
+fn apply_embedding_dimensions(
+    documents: &mut [serde_yaml::Value],
+    pipes: &[Pipe],
+) -> Result<Vec<String>> {
+    let mut embedders = Vec::new();
+    for document in documents.iter() {
+        if document.get("type").and_then(|kind| kind.as_str()) != Some("embedder") {
+            continue;
+        }
+        let provider = document
+            .get("provider")
+            .and_then(|provider| provider.as_str())
+            .unwrap_or_default();
+        let entries = document
+            .get("models")
+            .and_then(|models| models.as_sequence())
+            .into_iter()
+            .flatten();
+        for entry in entries {
+            let Some(model) = entry.get("model").and_then(|model| model.as_str()) else {
+                continue;
+            };
+            let alias = entry
+                .get("alias")
+                .and_then(|alias| alias.as_str())
+                .unwrap_or(model);
+            embedders.push((format!("{}.{}", provider, alias), model.to_string()));
+        }
+    }
 
-    for (placeholder, default_value) in PIPELINE_DEFAULT_BINDINGS {
-        let value = overrides
+    let mut known: Vec<(String, u64)> = Vec::new();
+    let mut unknown: Vec<String> = Vec::new();
+    for binding in pipes.iter().filter_map(|pipe| pipe.embedder.as_ref()) {
+        let Some((_, model)) = embedders.iter().find(|(name, _)| name == binding) else {
+            continue;
+        };
+        match embeddings::dimension(model) {
+            Some(dimension) => {
+                if !known.iter().any(|(name, _)| name == model) {
+                    known.push((model.clone(), dimension));
+                }
+            }
+            None => {
+                if !unknown.contains(model) {
+                    unknown.push(model.clone());
+                }
+            }
+        }
+    }
+
+    if known.is_empty() && unknown.is_empty() {
+        return Ok(Vec::new());
+    }
+    if known.iter().any(|(_, dimension)| *dimension != known[0].1) {
+        let models: Vec<String> = known
             .iter()
-            .find(|(key, _)| key == placeholder)
-            .map(|(_, value)| *value)
-            .unwrap_or(*default_value);
-        rendered = rendered.replace(placeholder, value);
+            .map(|(model, dimension)| format!("{} ({})", model, dimension))
+            .collect();
+        return Err(eyre!(
+            "pipes use embedders with different dimensions: {}; the document store needs a single embedding_model_dim",
+            models.join(", ")
+        ));
     }
 
-    rendered
+    if let Some((model, _)) = known.first()
+        && !unknown.is_empty()
+    {
+        return Err(eyre!(
+            "pipes mix catalogued embedder {} with unknown embedders {}; their dimensions cannot be checked",
+            model,
+            unknown.join(", ")
+        ));
+    }
+
+    let mut warnings: Vec<String> = unknown
+        .iter()
+        .map(|model| {
+            format!(
+                "embedding model {} is not in the dimension catalog; embedding_model_dim is taken from the template",
+                model
+            )
+        })
+        .collect();
+
+    for document in documents.iter_mut() {
+        if document.get("type").and_then(|kind| kind.as_str()) != Some("document_store") {
+            continue;
+        }
+        let current = document
+            .get("embedding_model_dim")
+            .and_then(|dimension| dimension.as_u64());
+        let Some((model, dimension)) = known.first() else {
+            if current.is_none() {
+                return Err(eyre!(
+                    "embedding model {} is not in the dimension catalog and the document store has no embedding_model_dim",
+                    unknown.join(", ")
+                ));
+            }
+            continue;
+        };
+        if current == Some(*dimension) {
+            continue;
+        }
+        if let Some(current) = current {
+            warnings.push(format!(
+                "embedding_model_dim changed from {} to {} to match {}",
+                current, dimension, model
+            ));
+        }
+        if let Some(mapping) = document.as_mapping_mut() {
+            mapping.insert(
+                serde_yaml::Value::from("embedding_model_dim"),
+                serde_yaml::Value::from(*dimension),
+            );
+        }
+    }
+
+    Ok(warnings)
 }
 
-fn render_settings(settings: &TemplateSettings) -> String {
-    SETTINGS_TEMPLATE
-        .replace(
-            "{{LANGFUSE_ENABLE}}",
-            bool_literal(settings.langfuse_enable),
-        )
-        .replace("{{LOGGING_LEVEL}}", settings.logging_level)
-        .replace("{{DEVELOPMENT}}", bool_literal(settings.development))
+fn split_documents(template: &str) -> Result<Vec<TemplateDocument>> {
+    let mut chunks = vec![String::new()];
+    for line in template.lines() {
+        if line.trim_end() == "---" {
+            chunks.push(String::new());
+        } else if let Some(chunk) = chunks.last_mut() {
+            chunk.push_str(line);
+            chunk.push('\n');
+        }
+    }
+
+    let mut documents = Vec::new();
+    for (index, chunk) in chunks.iter().enumerate() {
+        let trimmed = chunk.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        if let Some(section) = SECTIONS.iter().find(|section| **section == trimmed) {
+            documents.push(TemplateDocument::Section(section));
+            continue;
+        }
+        if trimmed.starts_with("{{") {
+            return Err(eyre!(
+                "document {} is an unknown section marker {}. Expected one of: {}",
+                index + 1,
+                trimmed,
+                SECTIONS.join(", ")
+            ));
+        }
+
+        let value: serde_yaml::Value = serde_yaml::from_str(chunk)
+            .map_err(|e| eyre!("document {} is not valid YAML: {}", index + 1, e))?;
+        documents.push(TemplateDocument::Yaml(value));
+    }
+
+    Ok(documents)
+}
+
+fn fill_placeholders(value: &mut serde_yaml::Value, values: &[(String, String)]) {
+    match value {
+        serde_yaml::Value::String(content) => {
+            if let Some((_, replacement)) = values
+                .iter()
+                .find(|(name, _)| *content == format!("<{}>", name))
+            {
+                *value = match serde_yaml::from_str::<serde_yaml::Value>(replacement) {
+                    Ok(
+                        scalar @ (serde_yaml::Value::Bool(_)
+                        | serde_yaml::Value::Number(_)
+                        | serde_yaml::Value::String(_)),
+                    ) => scalar,
+                    _ => serde_yaml::Value::String(replacement.clone()),
+                };
+                return;
+            }
+
+            for (name, replacement) in values {
+                *content = content.replace(&format!("<{}>", name), replacement);
+            }
+        }
+        serde_yaml::Value::Sequence(items) => {
+            for item in items {
+                fill_placeholders(item, values);
+            }
+        }
+        serde_yaml::Value::Mapping(mapping) => {
+            for (_, item) in mapping.iter_mut() {
+                fill_placeholders(item, values);
+            }
+        }
+        serde_yaml::Value::Tagged(tagged) => fill_placeholders(&mut tagged.value, values),
+        _ => {}
+    }
+}
+
+fn match_value(
+    expected: &serde_yaml::Value,
+    actual: &serde_yaml::Value,
+    captures: &mut Vec<(String, String)>,
+) -> bool {
+    match (expected, actual) {
+        (serde_yaml::Value::Mapping(expected), serde_yaml::Value::Mapping(actual)) => {
+            expected.len() == actual.len()
+                && expected.iter().all(|(key, item)| {
+                    actual
+                        .get(key)
+                        .is_some_and(|other| match_value(item, other, captures))
+                })
+        }
+        (serde_yaml::Value::Sequence(expected), serde_yaml::Value::Sequence(actual)) => {
+            expected.len() == actual.len()
+                && expected
+                    .iter()
+                    .zip(actual)
+                    .all(|(item, other)| match_value(item, other, captures))
+        }
+        (serde_yaml::Value::String(pattern), actual) if !find_placeholders(pattern).is_empty() => {
+            let text = match actual {
+                serde_yaml::Value::String(text) => text.clone(),
+                serde_yaml::Value::Bool(value) => value.to_string(),
+                serde_yaml::Value::Number(value) => value.to_string(),
+                _ => return false,
+            };
+            let mut attempt = captures.clone();
+            if match_segments(&pattern_segments(pattern), &text, &mut attempt) {
+                *captures = attempt;
+                true
+            } else {
+                false
+            }
+        }
+        _ => expected == actual,
+    }
+}
+
+fn pattern_segments(pattern: &str) -> Vec<PatternSegment<'_>> {
+    let mut segments = Vec::new();
+    let mut literal_start = 0;
+    let mut search_from = 0;
+
+    while let Some(offset) = pattern[search_from..].find('<') {
+        let start = search_from + offset;
+        let Some(length) = pattern[start + 1..].find('>') else {
+            break;
+        };
+        let name = &pattern[start + 1..start + 1 + length];
+        let is_placeholder = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+        if is_placeholder {
+            if start > literal_start {
+                segments.push(PatternSegment::Literal(&pattern[literal_start..start]));
+            }
+            segments.push(PatternSegment::Placeholder(name));
+            literal_start = start + length + 2;
+            search_from = literal_start;
+        } else {
+            search_from = start + 1;
+        }
+    }
+    if literal_start < pattern.len() {
+        segments.push(PatternSegment::Literal(&pattern[literal_start..]));
+    }
+
+    segments
+}
+
+fn match_segments(
+    segments: &[PatternSegment<'_>],
+    text: &str,
+    captures: &mut Vec<(String, String)>,
+) -> bool {
+    match segments.split_first() {
+        None => text.is_empty(),
+        Some((PatternSegment::Literal(literal), rest)) => text
+            .strip_prefix(literal)
+            .is_some_and(|remaining| match_segments(rest, remaining, captures)),
+        Some((PatternSegment::Placeholder(name), rest)) => {
+            if let Some((_, value)) = captures.iter().find(|(captured, _)| captured == name) {
+                let value = value.clone();
+                return text
+                    .strip_prefix(value.as_str())
+                    .is_some_and(|remaining| match_segments(rest, remaining, captures));
+            }
+
+            for end in (1..=text.len()).filter(|end| text.is_char_boundary(*end)) {
+                let mut attempt = captures.clone();
+                attempt.push((name.to_string(), text[..end].to_string()));
+                if match_segments(rest, &text[end..], &mut attempt) {
+                    *captures = attempt;
+                    return true;
+                }
+            }
+            false
+        }
+    }
+}
+
+fn find_markers(content: &str) -> Vec<String> {
+    let mut markers: Vec<String> = Vec::new();
+    let mut rest = content;
+
+    while let Some(start) = rest.find("{{") {
+        rest = &rest[start..];
+        let Some(end) = rest.find("}}") else {
+            break;
+        };
+
+        let marker = &rest[..end + 2];
+        if !markers.iter().any(|existing| existing == marker) {
+            markers.push(marker.to_string());
+        }
+        rest = &rest[end + 2..];
+    }
+
+    markers
+}
+
+pub fn find_placeholders(content: &str) -> Vec<String> {
+    let mut placeholders: Vec<String> = Vec::new();
+    let mut rest = content;
+
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start + 1..];
+        let Some(end) = rest.find('>') else {
+            break;
+        };
+
+        let name = &rest[..end];
+        let is_placeholder = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+        if is_placeholder {
+            if !placeholders.iter().any(|existing| existing == name) {
+                placeholders.push(name.to_string());
+            }
+            rest = &rest[end + 1..];
+        }
+    }
+
+    placeholders
+}
+
+pub fn format_placeholders(placeholders: &[String]) -> String {
+    placeholders
+        .iter()
+        .map(|placeholder| format!("<{}>", placeholder))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn default_pipes(overrides: &[(String, String)]) -> Result<Vec<Pipe>> {
+    let document: PipelineDocument = serde_yaml::from_str(PIPELINE_TEMPLATE)?;
+    let mut pipes = document.pipes;
+
+    for (name, binding) in overrides {
+        let pipe_name = name
+            .strip_prefix("LLM_")
+            .map(|pipe| pipe.to_lowercase())
+            .unwrap_or_default();
+        let Some(llm) = pipes
+            .iter_mut()
+            .find(|pipe| pipe.name == pipe_name)
+            .and_then(|pipe| pipe.llm.as_mut())
+        else {
+            return Err(eyre!(
+                "pipeline override '{}' does not match a pipe with an llm (expected LLM_<PIPE_NAME>)",
+                name
+            ));
+        };
+        *llm = binding.clone();
+    }
+
+    Ok(pipes)
+}
+
+fn render_section(
+    section: &str,
+    pipes: &[Pipe],
+    settings: &Settings,
+) -> Result<Vec<serde_yaml::Value>> {
+    match section {
+        ENGINE_MARKER => serde_yaml::Deserializer::from_str(ENGINE_SECTION)
+            .map(serde_yaml::Value::deserialize)
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| eyre!("engine section is not valid YAML: {}", e)),
+        PIPELINE_MARKER => {
+            let document = PipelineDocument {
+                kind: "pipeline".to_string(),
+                pipes: pipes.to_vec(),
+            };
+            Ok(vec![serde_yaml::to_value(document)?])
+        }
+        _ => Ok(vec![settings.to_document()?]),
+    }
 }
 
-fn bool_literal(value: bool) -> &'static str {
-    if value { "true" } else { "false" }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(provider: &str) -> ConfigTemplate {
+        serde_yaml::from_str::<TemplateManifest>("key: test\nname: Test\n")
+            .unwrap()
+            .into_template(provider.to_string(), TemplateSource::BuiltIn)
+            .unwrap()
+    }
+
+    fn yaml(content: &str) -> serde_yaml::Value {
+        serde_yaml::from_str(content).unwrap()
+    }
+
+    const MANIFEST: &str = "\
+key: openai
+name: {name}
+provider: |
+  type: llm
+  provider: litellm_llm
+  models:
+    - model: gpt-4o
+";
+
+    fn write_manifest(dir: &Path, file: &str, name: &str) {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(dir.join(file), MANIFEST.replace("{name}", name)).unwrap();
+    }
+
+    #[test]
+    fn catalog_sources_take_precedence() {
+        let dir = tempfile::tempdir().unwrap();
+        let user = dir.path().join("user");
+        let project = dir.path().join("project");
+        let built_in = TemplateCatalog::built_in();
+
+        let catalog = TemplateCatalog::load_from(Some(&user), &project);
+        assert_eq!(catalog.templates.len(), built_in.templates.len());
+        assert!(catalog.warnings.is_empty(), "{:?}", catalog.warnings);
+
+        write_manifest(&user, "openai.template.yaml", "User OpenAI");
+        let catalog = TemplateCatalog::load_from(Some(&user), &project);
+        let openai = catalog.find("openai").unwrap();
+        assert_eq!(openai.name, "User OpenAI");
+        assert_eq!(
+            openai.source,
+            TemplateSource::User(user.join("openai.template.yaml"))
+        );
+        assert_eq!(catalog.templates.len(), built_in.templates.len());
+        assert_eq!(catalog.templates[0].key, built_in.templates[0].key);
+
+        write_manifest(&project, "openai.template.yml", "Project OpenAI");
+        let catalog = TemplateCatalog::load_from(Some(&user), &project);
+        let openai = catalog.find("openai").unwrap();
+        assert_eq!(openai.name, "Project OpenAI");
+        assert_eq!(openai.source.label(), "project");
+        assert_eq!(catalog.templates.len(), built_in.templates.len());
+    }
+
+    #[test]
+    fn malformed_manifests_are_skipped_with_a_warning() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().join("templates");
+        write_manifest(&project, "openai.template.yaml", "Project OpenAI");
+        fs::write(project.join("notes.yaml"), "not: a manifest").unwrap();
+        let cases = [
+            ("broken.template.yaml", "key: broken\nname: [unclosed\n"),
+            (
+                "unknown.template.yaml",
+                "key: unknown\nname: Unknown\nprovder: x\n",
+            ),
+            ("empty.template.yaml", "key: empty\nname: Empty\n"),
+            (
+                "both.template.yaml",
+                "key: both\nname: Both\nprovider: x\nprovider_file: x.yaml\n",
+            ),
+        ];
+        for (file, content) in cases {
+            fs::write(project.join(file), content).unwrap();
+        }
+
+        let catalog = TemplateCatalog::load_from(None, &project);
+        for (file, _) in cases {
+            let path = project.join(file);
+            assert!(
+                catalog
+                    .warnings
+                    .iter()
+                    .any(|warning| warning.starts_with(&format!("Skipped {}: ", path.display()))),
+                "{}: {:?}",
+                file,
+                catalog.warnings
+            );
+        }
+        assert_eq!(catalog.warnings.len(), cases.len());
+        assert_eq!(catalog.find("openai").unwrap().name, "Project OpenAI");
+    }
+
+    fn embedder_documents(model: &str, stores: &[Option<u64>]) -> Vec<serde_yaml::Value> {
+        let mut documents = vec![yaml(&format!(
+            "type: embedder\nprovider: litellm_embedder\nmodels:\n  - model: {}\n    alias: default\n",
+            model
+        ))];
+        for store in stores {
+            let mut document = yaml("type: document_store\nprovider: qdrant\n");
+            if let (Some(dimension), Some(mapping)) = (store, document.as_mapping_mut()) {
+                mapping.insert("embedding_model_dim".into(), (*dimension).into());
+            }
+            documents.push(document);
+        }
+        documents
+    }
+
+    fn store_dimensions(documents: &[serde_yaml::Value]) -> Vec<Option<u64>> {
+        documents
+            .iter()
+            .filter(|document| {
+                document.get("type").and_then(|kind| kind.as_str()) == Some("document_store")
+            })
+            .map(|document| {
+                document
+                    .get("embedding_model_dim")
+                    .and_then(|dimension| dimension.as_u64())
+            })
+            .collect()
+    }
+
+    type Dimensions<'a> = &'a [Option<u64>];
+
+    #[test]
+    fn embedding_dimension_cases() {
+        let pipe = |embedder: Option<&str>| Pipe {
+            name: "indexing".to_string(),
+            llm: None,
+            embedder: embedder.map(str::to_string),
+            engine: None,
+            document_store: None,
+        };
+        let used = [pipe(Some("litellm_embedder.default"))];
+        let cases: &[(&str, &[Pipe], Dimensions, Dimensions, usize)] = &[
+            (
+                "text-embedding-3-large",
+                &used,
+                &[Some(3072)],
+                &[Some(3072)],
+                0,
+            ),
+            (
+                "text-embedding-3-small",
+                &used,
+                &[Some(3072)],
+                &[Some(1536)],
+                1,
+            ),
+            ("text-embedding-3-small", &used, &[None], &[Some(1536)], 0),
+            (
+                "text-embedding-3-small",
+                &used,
+                &[Some(3072), Some(768), Some(1536)],
+                &[Some(1536), Some(1536), Some(1536)],
+                2,
+            ),
+            (
+                "my-finetuned-embedder",
+                &used,
+                &[Some(4096)],
+                &[Some(4096)],
+                1,
+            ),
+            (
+                "my-finetuned-embedder",
+                &used,
+                &[Some(4096), Some(1024)],
+                &[Some(4096), Some(1024)],
+                1,
+            ),
+            (
+                "text-embedding-3-small",
+                &[],
+                &[Some(3072)],
+                &[Some(3072)],
+                0,
+            ),
+        ];
+
+        for (model, pipes, stores, expected, warnings) in cases {
+            let mut documents = embedder_documents(model, stores);
+            let found = apply_embedding_dimensions(&mut documents, pipes).unwrap();
+            assert_eq!(
+                store_dimensions(&documents),
+                *expected,
+                "{} {:?}",
+                model,
+                stores
+            );
+            assert_eq!(
+                found.len(),
+                *warnings,
+                "{} {:?}: {:?}",
+                model,
+                stores,
+                found
+            );
+        }
+    }
+
+    #[test]
+    fn embedding_dimension_errors() {
+        let pipe = |embedder: &str| Pipe {
+            name: embedder.to_string(),
+            llm: None,
+            embedder: Some(embedder.to_string()),
+            engine: None,
+            document_store: None,
+        };
+
+        let mut documents = embedder_documents("my-finetuned-embedder", &[None]);
+        let error = apply_embedding_dimensions(&mut documents, &[pipe("litellm_embedder.default")])
+            .unwrap_err();
+        assert!(
+            error.to_string().contains("no embedding_model_dim"),
+            "{}",
+            error
+        );
+
+        let mut documents = embedder_documents("text-embedding-3-large", &[Some(3072)]);
+        documents.push(yaml(
+            "type: embedder\nprovider: ollama_embedder\nmodels:\n  - model: nomic-embed-text\n    alias: default\n",
+        ));
+        let pipes = [
+            pipe("litellm_embedder.default"),
+            pipe("ollama_embedder.default"),
+        ];
+        let error = apply_embedding_dimensions(&mut documents, &pipes).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .starts_with("pipes use embedders with different dimensions"),
+            "{}",
+            error
+        );
+    }
+
+    fn rendered(template: &ConfigTemplate, values: &[(String, String)]) -> Vec<serde_yaml::Value> {
+        let content = template
+            .render(values, &template.pipes(), template.settings())
+            .unwrap();
+        serde_yaml::Deserializer::from_str(&content)
+            .map(serde_yaml::Value::deserialize)
+            .collect::<std::result::Result<_, _>>()
+            .unwrap()
+    }
+
+    fn set_llm_field(documents: &mut [serde_yaml::Value], field: &str, value: &str) {
+        let entry = documents
+            .iter_mut()
+            .find(|document| document.get("type").and_then(|kind| kind.as_str()) == Some("llm"))
+            .and_then(|document| document.get_mut("models"))
+            .and_then(|models| models.get_mut(0))
+            .and_then(|entry| entry.as_mapping_mut())
+            .unwrap();
+        entry.insert(field.into(), value.into());
+    }
+
+    #[test]
+    fn template_detection_cases() {
+        let catalog = TemplateCatalog::built_in();
+        let pairs = |values: &[(&str, &str)]| -> Vec<(String, String)> {
+            values
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect()
+        };
+        let bedrock = pairs(&[
+            ("region-name", "eu-west-1"),
+            ("iam-role-arn", "arn:aws:iam::123:role/analytics"),
+        ]);
+        let azure = pairs(&[
+            ("chat-deployment-name", "gpt-4o"),
+            ("azure-api-base", "https://example.openai.azure.com"),
+            ("embedding-deployment-name", "embeddings"),
+        ]);
+        type Change = fn(&mut Vec<serde_yaml::Value>);
+        let unchanged: Change = |_| {};
+        let reordered: Change = |documents| documents.reverse();
+        let other_model: Change = |documents| set_llm_field(documents, "model", "gpt-3.5-turbo");
+        let other_region: Change = |documents| {
+            set_llm_field(
+                documents,
+                "api_base",
+                "https://bedrock-runtime.us-east-1.amazonaws.com",
+            )
+        };
+        let no_models: Change = |documents| {
+            documents.retain(|document| {
+                !matches!(
+                    document.get("type").and_then(|kind| kind.as_str()),
+                    Some("llm" | "embedder")
+                )
+            })
+        };
+
+        let cases = [
+            ("openai", Vec::new(), unchanged, Some("openai")),
+            ("bedrock", bedrock.clone(), unchanged, Some("bedrock")),
+            ("azure", azure.clone(), unchanged, Some("azure")),
+            ("azure", azure.clone(), reordered, Some("azure")),
+            ("openai", Vec::new(), other_model, None),
+            ("bedrock", bedrock.clone(), other_region, None),
+            ("openai", Vec::new(), no_models, None),
+        ];
+
+        for (key, values, change, expected) in cases {
+            let mut documents = rendered(catalog.find(key).unwrap(), &values);
+            change(&mut documents);
+            let detected = catalog.templates.iter().find_map(|template| {
+                template
+                    .match_documents(&documents)
+                    .map(|captures| (template.key.as_str(), captures))
+            });
+            match expected {
+                Some(expected) => {
+                    let (found, mut captures) = detected.unwrap_or_else(|| panic!("{}", key));
+                    assert_eq!(found, expected);
+                    let mut values = values.clone();
+                    captures.sort();
+                    values.sort();
+                    assert_eq!(captures, values, "{}", key);
+                }
+                None => assert!(detected.is_none(), "{}: {:?}", key, detected),
+            }
+        }
+    }
+
+    #[test]
+    fn segment_matching_cases() {
+        let cases: &[(&str, &str, Option<Values>)] = &[
+            (
+                "azure/<deployment>",
+                "azure/gpt-4o",
+                Some(&[("deployment", "gpt-4o")]),
+            ),
+            ("azure/<deployment>", "openai/gpt-4o", None),
+            ("azure/<deployment>", "azure/", None),
+            (
+                "https://<host>.<region>.aws",
+                "https://runtime.eu.west.aws",
+                Some(&[("host", "runtime"), ("region", "eu.west")]),
+            ),
+            (
+                "<region>/<region>",
+                "eu-west-1/eu-west-1",
+                Some(&[("region", "eu-west-1")]),
+            ),
+            ("<region>/<region>", "eu-west-1/us-east-1", None),
+            (
+                "https://api.example.com",
+                "https://api.example.com",
+                Some(&[]),
+            ),
+        ];
+
+        for (pattern, text, expected) in cases {
+            let mut captures = Vec::new();
+            let matched = match_segments(&pattern_segments(pattern), text, &mut captures);
+            let expected = expected.map(|values| {
+                values
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), value.to_string()))
+                    .collect::<Vec<_>>()
+            });
+            assert_eq!(
+                matched.then_some(captures),
+                expected,
+                "{} ~ {}",
+                pattern,
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn built_in_templates_render_and_round_trip() {
+        let catalog = TemplateCatalog::built_in();
+        assert!(catalog.warnings.is_empty(), "{:?}", catalog.warnings);
+
+        for template in &catalog.templates {
+            let values: Vec<(String, String)> = template
+                .placeholders()
+                .into_iter()
+                .map(|placeholder| (placeholder, "https://example.com".to_string()))
+                .collect();
+            let pipes = template.pipes();
+            let content = template
+                .render(&values, &pipes, template.settings())
+                .unwrap_or_else(|e| panic!("{}: {}", template.key, e));
+
+            let reparsed: Vec<serde_yaml::Value> = serde_yaml::Deserializer::from_str(&content)
+                .map(serde_yaml::Value::deserialize)
+                .collect::<std::result::Result<_, _>>()
+                .unwrap();
+            let kinds: Vec<&str> = reparsed
+                .iter()
+                .filter_map(|document| document.get("type").and_then(|kind| kind.as_str()))
+                .collect();
+            for kind in ["llm", "embedder", "engine", "pipeline"] {
+                assert!(kinds.contains(&kind), "{} has no {}", template.key, kind);
+            }
+            assert!(
+                reparsed
+                    .iter()
+                    .any(|document| document.get("settings").is_some()),
+                "{} has no settings",
+                template.key
+            );
+        }
+    }
+
+    #[test]
+    fn render_rejects_leftovers() {
+        let cases = [
+            (
+                "type: llm\nprovider: litellm_llm\nmodel: \"{{MODEL}}\"\n",
+                Vec::new(),
+                "config.yaml still contains template markers: {{MODEL}}",
+            ),
+            (
+                "type: llm\nprovider: litellm_llm\napi_base: <api_base>\n",
+                Vec::new(),
+                "config.yaml still contains unresolved placeholders: <api_base>",
+            ),
+            (
+                "type: llm\nprovider: litellm_llm\napi_base: <api_base>/<api_version>\n",
+                vec![("api_base".to_string(), "https://example.com".to_string())],
+                "config.yaml still contains unresolved placeholders: <api_version>",
+            ),
+        ];
+
+        for (provider, values, expected) in cases {
+            let template = template(provider);
+            let error = template
+                .render(&values, &template.pipes(), template.settings())
+                .unwrap_err();
+            assert_eq!(error.to_string(), expected, "{}", provider);
+        }
+    }
+
+    #[test]
+    fn unknown_section_markers_are_errors() {
+        let error = split_documents("type: llm\n---\n{{MODELS_SECTION}}\n")
+            .err()
+            .unwrap();
+        assert!(
+            error
+                .to_string()
+                .starts_with("document 2 is an unknown section marker {{MODELS_SECTION}}"),
+            "{}",
+            error
+        );
+    }
+
+    type Values = &'static [(&'static str, &'static str)];
+
+    #[test]
+    fn fill_placeholder_cases() {
+        let cases: &[(&str, Values, &str)] = &[
+            ("timeout: 120", &[("api_base", "x")], "timeout: 120"),
+            (
+                "api_base: <api_base>",
+                &[("api_version", "2024-02-01")],
+                "api_base: <api_base>",
+            ),
+            (
+                "api_base: <api_base>",
+                &[("api_base", "https://example.com")],
+                "api_base: https://example.com",
+            ),
+            (
+                "url: <host>:<port>/<host>\nalias: <host>",
+                &[("host", "qdrant"), ("port", "6333")],
+                "url: qdrant:6333/qdrant\nalias: qdrant",
+            ),
+            (
+                "models: [{port: <port>, enabled: <enabled>}]",
+                &[("port", "8080"), ("enabled", "true")],
+                "models: [{port: 8080, enabled: true}]",
+            ),
+            (
+                "kwargs: {headers: <headers>}",
+                &[("headers", "[a, b]")],
+                "kwargs: {headers: '[a, b]'}",
+            ),
+        ];
+
+        for (content, values, expected) in cases {
+            let values: Vec<(String, String)> = values
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect();
+            let mut value = yaml(content);
+            fill_placeholders(&mut value, &values);
+            assert_eq!(value, yaml(expected), "{}", content);
+        }
+    }
 }
@@ -0,0 +1,43 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+
+pub fn render_checking(frame: &mut Frame, message: &str) {
+    let area = frame.area();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(5),
+            Constraint::Length(2),
+        ])
+        .split(area);
+
+    let title = Paragraph::new("⏳ Please wait")
+        .style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+        .block(Block::default().borders(Borders::ALL))
+        .centered();
+    frame.render_widget(title, chunks[0]);
+
+    let content = Paragraph::new(vec![Line::from(""), Line::from(message)])
+        .style(Style::default().fg(Color::Gray))
+        .block(Block::default().borders(Borders::ALL))
+        .wrap(Wrap { trim: false })
+        .centered();
+    frame.render_widget(content, chunks[1]);
+
+    let help = Paragraph::new("Ctrl+C to quit")
+        .style(Style::default().fg(Color::DarkGray))
+        .centered();
+    frame.render_widget(help, chunks[2]);
+}
@@ -6,10 +6,11 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, Wrap},
 };
 
-use crate::templates::ConfigTemplate;
+use crate::templates::{ConfigTemplate, TemplateSource};
 
 pub struct ConfigSelectionView<'a> {
     pub templates: &'a [ConfigTemplate],
+    pub warnings: &'a [String],
     pub selected_index: usize,
 }
 
@@ -22,11 +23,11 @@ pub fn render_config_selection(frame: &mut Frame, view: &ConfigSelectionView<'_>
         .constraints([
             Constraint::Length(3),
             Constraint::Min(12),
-            Constraint::Length(5),
+            Constraint::Length(8 + view.warnings.len().min(4) as u16),
         ])
         .split(area);
 
-    let title = Paragraph::new("🧩 Choose a configuration template")
+    let title = Paragraph::new("🧩 Step 1/4 · Choose a model provider")
         .style(
             Style::default()
                 .fg(Color::Cyan)
@@ -63,14 +64,16 @@ pub fn render_config_selection(frame: &mut Frame, view: &ConfigSelectionView<'_>
             list_lines.push(Line::from(vec![
                 Span::styled(selector, name_style),
                 Span::raw(" "),
-                Span::styled(template.name, name_style),
+                Span::styled(template.name.as_str(), name_style),
                 Span::raw("  "),
                 Span::styled(format!("({})", template.key), description_style),
+                Span::raw("  "),
+                source_badge(&template.source),
             ]));
 
             list_lines.push(Line::from(vec![
                 Span::raw("    "),
-                Span::styled(template.description, description_style),
+                Span::styled(template.description.as_str(), description_style),
             ]));
 
             list_lines.push(Line::from(""));
@@ -86,32 +89,54 @@ pub fn render_config_selection(frame: &mut Frame, view: &ConfigSelectionView<'_>
         .wrap(Wrap { trim: true });
     frame.render_widget(list, chunks[1]);
 
-    let detail_lines = if let Some(template) = view.templates.get(
+    let mut detail_lines = Vec::new();
+
+    if let Some(template) = view.templates.get(
         view.selected_index
             .min(view.templates.len().saturating_sub(1)),
     ) {
-        vec![
-            Line::from(vec![
-                Span::styled("Selected: ", Style::default().fg(Color::Yellow)),
-                Span::styled(template.name, Style::default().fg(Color::White)),
-            ]),
-            Line::from(vec![
-                Span::styled("Key: ", Style::default().fg(Color::Yellow)),
-                Span::raw(template.key),
-            ]),
-            Line::from(""),
-            Line::from("Use ↑ ↓ to navigate, Enter to generate config.yaml"),
-            Line::from("Press Esc to go back, Ctrl+C to exit"),
-        ]
-    } else {
-        vec![
-            Line::from("Use ↑ ↓ to navigate, Enter to generate config.yaml"),
-            Line::from("Press Esc to go back, Ctrl+C to exit"),
-        ]
-    };
+        detail_lines.push(Line::from(vec![
+            Span::styled("Selected: ", Style::default().fg(Color::Yellow)),
+            Span::styled(template.name.as_str(), Style::default().fg(Color::White)),
+        ]));
+        detail_lines.push(Line::from(vec![
+            Span::styled("Key: ", Style::default().fg(Color::Yellow)),
+            Span::raw(template.key.as_str()),
+        ]));
+
+        let source = match template.source.path() {
+            Some(path) => format!("{} ({})", template.source.label(), path.display()),
+            None => template.source.label().to_string(),
+        };
+        detail_lines.push(Line::from(vec![
+            Span::styled("Source: ", Style::default().fg(Color::Yellow)),
+            Span::raw(source),
+        ]));
+    }
+
+    for warning in view.warnings {
+        detail_lines.push(Line::from(Span::styled(
+            format!("⚠ {}", warning),
+            Style::default().fg(Color::Red),
+        )));
+    }
+
+    detail_lines.push(Line::from(""));
+    detail_lines.push(Line::from("Use ↑ ↓ to navigate, Enter to continue"));
+    detail_lines.push(Line::from("Press Esc to go back, Ctrl+C to exit"));
 
     let details = Paragraph::new(detail_lines)
         .block(Block::default().borders(Borders::ALL).title("Details"))
         .wrap(Wrap { trim: true });
     frame.render_widget(details, chunks[2]);
 }
+
+fn source_badge(source: &TemplateSource) -> Span<'static> {
+    let color = match source {
+        TemplateSource::BuiltIn => Color::DarkGray,
+        TemplateSource::User(_) => Color::Magenta,
+        TemplateSource::Project(_) => Color::Green,
+    };
+
+    Span::styled(format!("[{}]", source.label()), Style::default().fg(color))
+}
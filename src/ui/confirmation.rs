@@ -3,14 +3,21 @@ use ratatui::{
     layout::{Constraint, Direction, Layout},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Paragraph, Wrap},
 };
 
 use crate::app::MenuSelection;
+use crate::drift::EnvDrift;
+use crate::readiness::Readiness;
 
 pub struct ConfirmationView<'a> {
     pub env_exists: bool,
     pub config_exists: bool,
+    pub config_origin: Option<String>,
+    pub env_drift: Option<&'a EnvDrift>,
+    pub readiness: &'a Readiness,
+    pub services: &'a [String],
+    pub menu_items: &'a [MenuSelection],
     pub menu_selection: &'a MenuSelection,
 }
 
@@ -23,7 +30,7 @@ pub fn render_confirmation(frame: &mut Frame, view: &ConfirmationView<'_>) {
         .constraints([
             Constraint::Length(3),
             Constraint::Min(10),
-            Constraint::Length(5),
+            Constraint::Length(8),
             Constraint::Length(2),
         ])
         .split(area);
@@ -39,12 +46,13 @@ pub fn render_confirmation(frame: &mut Frame, view: &ConfirmationView<'_>) {
     frame.render_widget(title, chunks[0]);
 
     let all_files_exist = view.env_exists && view.config_exists;
+    let all_files_ready = all_files_exist && view.readiness.is_ready();
 
     let mut content_lines = vec![
         Line::from(""),
         Line::from(Span::styled(
             "Configuration Files:",
-            Style::default().fg(if all_files_exist {
+            Style::default().fg(if all_files_ready {
                 Color::Green
             } else {
                 Color::Yellow
@@ -82,16 +90,46 @@ pub fn render_confirmation(frame: &mut Frame, view: &ConfirmationView<'_>) {
             }),
         ),
         Span::raw(" config.yaml"),
-        if !view.config_exists {
-            Span::styled(" (missing)", Style::default().fg(Color::Red))
-        } else {
-            Span::raw("")
+        match (&view.config_origin, view.config_exists) {
+            (_, false) => Span::styled(" (missing)", Style::default().fg(Color::Red)),
+            (Some(origin), true) => {
+                Span::styled(format!(" ({})", origin), Style::default().fg(Color::Gray))
+            }
+            (None, true) => Span::raw(""),
         },
     ]));
 
+    for item in &view.readiness.items {
+        let (mark, color) = if item.is_ready() {
+            ("✓", Color::Green)
+        } else {
+            ("✗", Color::Red)
+        };
+        content_lines.push(Line::from(vec![
+            Span::raw("  "),
+            Span::styled(mark, Style::default().fg(color)),
+            Span::raw(format!(" {}", item.label)),
+        ]));
+        for problem in &item.problems {
+            content_lines.push(Line::from(Span::styled(
+                format!("    {}", problem),
+                Style::default().fg(Color::Red),
+            )));
+        }
+    }
+
+    if let Some(drift) = view.env_drift {
+        for line in drift.summary() {
+            content_lines.push(Line::from(Span::styled(
+                format!("  ⚠ {}", line),
+                Style::default().fg(Color::Yellow),
+            )));
+        }
+    }
+
     content_lines.push(Line::from(""));
 
-    if all_files_exist {
+    if all_files_ready {
         content_lines.push(Line::from(Span::styled(
             "✅ All configuration files ready!",
             Style::default()
@@ -100,10 +138,29 @@ pub fn render_confirmation(frame: &mut Frame, view: &ConfirmationView<'_>) {
         )));
         content_lines.push(Line::from(""));
         content_lines.push(Line::from("Services to be started:"));
-        content_lines.push(Line::from("  • analytics-service"));
-        content_lines.push(Line::from("  • qdrant"));
-        content_lines.push(Line::from("  • northwind-db (PostgreSQL demo)"));
-        content_lines.push(Line::from("  • analytics-ui"));
+        for service in view.services {
+            content_lines.push(Line::from(format!("  • {}", service)));
+        }
+    } else if all_files_exist {
+        content_lines.push(Line::from(Span::styled(
+            "⚠️  Some configuration files are not ready!",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )));
+        content_lines.push(Line::from(
+            "Fix the items marked ✗ by setting up .env again, or edit or regenerate config.yaml.",
+        ));
+    } else if view.config_exists {
+        content_lines.push(Line::from(Span::styled(
+            "⚠️  .env is missing!",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )));
+        content_lines.push(Line::from(
+            "Keep config.yaml and only set up .env, or regenerate both from a template.",
+        ));
     } else {
         content_lines.push(Line::from(Span::styled(
             "⚠️  Some configuration files are missing!",
@@ -112,66 +169,35 @@ pub fn render_confirmation(frame: &mut Frame, view: &ConfirmationView<'_>) {
                 .add_modifier(Modifier::BOLD),
         )));
         content_lines.push(Line::from(
-            "Please generate the missing files before proceeding.",
+            "Run the setup wizard to generate them before proceeding.",
         ));
     }
 
     let content = Paragraph::new(content_lines)
         .block(Block::default().borders(Borders::ALL).title("Status"))
+        .wrap(Wrap { trim: false })
         .centered();
     frame.render_widget(content, chunks[1]);
 
     let mut menu_lines = vec![Line::from("")];
 
-    if !view.env_exists {
-        let style = if *view.menu_selection == MenuSelection::GenerateEnv {
-            Style::default()
-                .fg(Color::Black)
-                .bg(Color::Cyan)
-                .add_modifier(Modifier::BOLD)
-        } else {
-            Style::default().fg(Color::Cyan)
+    for item in view.menu_items {
+        let color = match item {
+            MenuSelection::Proceed => Color::Green,
+            MenuSelection::Cancel => Color::Red,
+            _ => Color::Cyan,
         };
-        menu_lines.push(Line::from(Span::styled("[ Generate .env ]", style)));
-    }
-
-    if !view.config_exists {
-        let style = if *view.menu_selection == MenuSelection::GenerateConfig {
+        let style = if item == view.menu_selection {
             Style::default()
                 .fg(Color::Black)
-                .bg(Color::Cyan)
+                .bg(color)
                 .add_modifier(Modifier::BOLD)
         } else {
-            Style::default().fg(Color::Cyan)
+            Style::default().fg(color)
         };
-        menu_lines.push(Line::from(Span::styled("[ Generate config.yaml ]", style)));
+        menu_lines.push(Line::from(Span::styled(item.label(), style)));
     }
 
-    if all_files_exist {
-        let style = if *view.menu_selection == MenuSelection::Proceed {
-            Style::default()
-                .fg(Color::Black)
-                .bg(Color::Green)
-                .add_modifier(Modifier::BOLD)
-        } else {
-            Style::default().fg(Color::Green)
-        };
-        menu_lines.push(Line::from(Span::styled(
-            "[ Proceed with Installation ]",
-            style,
-        )));
-    }
-
-    let cancel_style = if *view.menu_selection == MenuSelection::Cancel {
-        Style::default()
-            .fg(Color::Black)
-            .bg(Color::Red)
-            .add_modifier(Modifier::BOLD)
-    } else {
-        Style::default().fg(Color::Red)
-    };
-    menu_lines.push(Line::from(Span::styled("[ Cancel ]", cancel_style)));
-
     let menu = Paragraph::new(menu_lines)
         .block(Block::default().borders(Borders::ALL).title("Menu"))
         .centered();
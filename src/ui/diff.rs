@@ -0,0 +1,106 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::diff::DiffKind;
+use crate::generate::PendingFile;
+
+pub struct DiffView<'a> {
+    pub files: &'a [PendingFile],
+    pub scroll: usize,
+}
+
+pub fn render_diff(frame: &mut Frame, view: &DiffView<'_>) {
+    let area = frame.area();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(10),
+            Constraint::Length(2),
+        ])
+        .split(area);
+
+    let title = Paragraph::new("🔍 Review changes to existing files")
+        .style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+        .block(Block::default().borders(Borders::ALL))
+        .centered();
+    frame.render_widget(title, chunks[0]);
+
+    let lines = diff_lines(view.files);
+    let visible_rows = chunks[1].height.saturating_sub(2) as usize;
+    let scroll = view.scroll.min(lines.len().saturating_sub(visible_rows)) as u16;
+
+    let diff = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Existing files are backed up to backups/ before they are replaced"),
+        )
+        .scroll((scroll, 0));
+    frame.render_widget(diff, chunks[1]);
+
+    let help = Paragraph::new("↑↓ PgUp/PgDn to scroll, Enter to write and install, Esc to go back")
+        .style(Style::default().fg(Color::DarkGray))
+        .centered();
+    frame.render_widget(help, chunks[2]);
+}
+
+pub fn diff_line_count(files: &[PendingFile]) -> usize {
+    diff_lines(files).len()
+}
+
+fn diff_lines(files: &[PendingFile]) -> Vec<Line<'_>> {
+    let heading_style = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    let detail_style = Style::default().fg(Color::Gray);
+    let mut lines = Vec::new();
+
+    for file in files {
+        let summary = if file.is_new {
+            "new file".to_string()
+        } else if file.diff.is_empty() {
+            "unchanged".to_string()
+        } else {
+            let added = file
+                .diff
+                .iter()
+                .filter(|line| line.kind == DiffKind::Added)
+                .count();
+            let removed = file
+                .diff
+                .iter()
+                .filter(|line| line.kind == DiffKind::Removed)
+                .count();
+            format!("+{} -{}", added, removed)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(file.path.display().to_string(), heading_style),
+            Span::styled(format!("  {}", summary), detail_style),
+        ]));
+
+        for line in &file.diff {
+            let style = match line.kind {
+                DiffKind::Hunk => Style::default().fg(Color::Cyan),
+                DiffKind::Context => detail_style,
+                DiffKind::Added => Style::default().fg(Color::Green),
+                DiffKind::Removed => Style::default().fg(Color::Red),
+            };
+            lines.push(Line::from(Span::styled(line.text.as_str(), style)));
+        }
+        lines.push(Line::from(""));
+    }
+
+    lines
+}
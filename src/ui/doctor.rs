@@ -0,0 +1,102 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+
+use crate::doctor::{self, CheckStatus, DoctorCheck};
+
+pub struct DoctorView<'a> {
+    pub checks: &'a [DoctorCheck],
+    pub confirm_install: bool,
+}
+
+pub fn render_doctor(frame: &mut Frame, view: &DoctorView<'_>) {
+    let area = frame.area();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(10),
+            Constraint::Length(2),
+        ])
+        .split(area);
+
+    let title = Paragraph::new("🩺 Host checks")
+        .style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+        .block(Block::default().borders(Borders::ALL))
+        .centered();
+    frame.render_widget(title, chunks[0]);
+
+    let mut lines = vec![Line::from("")];
+    for check in view.checks {
+        let color = match check.status {
+            CheckStatus::Pass => Color::Green,
+            CheckStatus::Warn => Color::Yellow,
+            CheckStatus::Fail => Color::Red,
+        };
+        lines.push(Line::from(vec![
+            Span::raw("  "),
+            Span::styled(check.status.symbol(), Style::default().fg(color)),
+            Span::styled(
+                format!(" {:<18}", check.name),
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(check.detail.as_str(), Style::default().fg(Color::Gray)),
+        ]));
+        if let Some(hint) = &check.hint {
+            lines.push(Line::from(Span::styled(
+                format!("      fix: {}", hint),
+                Style::default().fg(color),
+            )));
+        }
+    }
+
+    let failed = doctor::has_failures(view.checks);
+    lines.push(Line::from(""));
+    lines.push(Line::from(if view.confirm_install {
+        Span::styled(
+            "Press i again to install although checks failed, any other key to cancel.",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )
+    } else if failed {
+        Span::styled(
+            "Some checks failed, the installation is likely to fail.",
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        )
+    } else {
+        Span::styled(
+            "The host is ready for the installation.",
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        )
+    }));
+
+    let content = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title("Doctor"))
+        .wrap(Wrap { trim: false });
+    frame.render_widget(content, chunks[1]);
+
+    let help_text = if failed {
+        "r to run the checks again, i to install anyway, Esc to go back"
+    } else {
+        "Enter to install, r to run the checks again, Esc to go back"
+    };
+    let help = Paragraph::new(help_text)
+        .style(Style::default().fg(Color::DarkGray))
+        .centered();
+    frame.render_widget(help, chunks[2]);
+}
@@ -6,10 +6,13 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph},
 };
 
-use crate::app::FormData;
+use crate::app::{FormData, FormSection};
+use crate::templates::ConfigTemplate;
 
 pub struct EnvSetupView<'a> {
+    pub template: &'a ConfigTemplate,
     pub form_data: &'a FormData,
+    pub section: FormSection,
 }
 
 pub fn render_env_setup(frame: &mut Frame, view: &EnvSetupView<'_>) {
@@ -25,7 +28,11 @@ pub fn render_env_setup(frame: &mut Frame, view: &EnvSetupView<'_>) {
         ])
         .split(area);
 
-    let title = Paragraph::new("🔧 Generate .env File")
+    let title_text = match view.section {
+        FormSection::Credentials => "🔑 Step 2/4 · Provider credentials",
+        FormSection::Options => "🔧 Step 3/4 · Ports and options",
+    };
+    let title = Paragraph::new(title_text)
         .style(
             Style::default()
                 .fg(Color::Cyan)
@@ -38,101 +45,98 @@ pub fn render_env_setup(frame: &mut Frame, view: &EnvSetupView<'_>) {
     let data = view.form_data;
 
     let mut form_lines = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Provider: ", Style::default().fg(Color::Yellow)),
+            Span::styled(
+                view.template.name.as_str(),
+                Style::default().fg(Color::White),
+            ),
+        ]),
         Line::from(""),
         Line::from("Please provide the following information:"),
         Line::from(""),
     ];
 
-    let field0_style = if data.current_field == 0 {
-        if data.editing {
-            Style::default()
-                .fg(Color::Green)
-                .add_modifier(Modifier::BOLD)
-        } else {
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD)
-        }
-    } else {
-        Style::default().fg(Color::White)
-    };
-
-    let key_display = if data.openai_api_key.is_empty() {
-        "_".repeat(40)
-    } else {
-        format!(
-            "{}{}",
-            &data.openai_api_key,
-            "_".repeat(40 - data.openai_api_key.len().min(40))
-        )
-    };
-
-    form_lines.push(Line::from(vec![
-        Span::styled("OpenAI API Key: ", field0_style),
-        Span::styled(&key_display[..40.min(key_display.len())], field0_style),
-        Span::styled(" *", Style::default().fg(Color::Red)),
-    ]));
-    form_lines.push(Line::from(""));
-
-    let field1_style = if data.current_field == 1 {
-        if data.editing {
-            Style::default()
-                .fg(Color::Green)
-                .add_modifier(Modifier::BOLD)
-        } else {
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD)
-        }
-    } else {
-        Style::default().fg(Color::White)
-    };
-
-    form_lines.push(Line::from(vec![
-        Span::styled("Generation Model: ", field1_style),
-        Span::styled(&data.generation_model, field1_style),
-    ]));
-    form_lines.push(Line::from(""));
-
-    let field2_style = if data.current_field == 2 {
-        if data.editing {
-            Style::default()
-                .fg(Color::Green)
-                .add_modifier(Modifier::BOLD)
-        } else {
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD)
+    match view.section {
+        FormSection::Credentials => {
+            for (index, credential) in data.credentials.iter().enumerate() {
+                let style = field_style(data, index);
+                let value = &data.credential_values[index];
+                let value_display = if value.is_empty() {
+                    "_".repeat(40)
+                } else {
+                    format!(
+                        "{}{}",
+                        value,
+                        "_".repeat(40 - value.chars().count().min(40))
+                    )
+                };
+
+                form_lines.push(Line::from(vec![
+                    Span::styled(format!("{}: ", credential.label), style),
+                    Span::styled(value_display.chars().take(40).collect::<String>(), style),
+                    Span::styled(" *", Style::default().fg(Color::Red)),
+                ]));
+                form_lines.push(Line::from(Span::styled(
+                    format!("  {}", credential.env_var),
+                    Style::default().fg(Color::DarkGray),
+                )));
+                form_lines.push(Line::from(""));
+            }
+
+            if !data.placeholders.is_empty() {
+                form_lines.push(Line::from(Span::styled(
+                    "Template values",
+                    Style::default().fg(Color::Yellow),
+                )));
+                form_lines.push(Line::from(""));
+            }
+
+            let offset = data.credentials.len();
+            for (index, placeholder) in data.placeholders.iter().enumerate() {
+                let style = field_style(data, offset + index);
+                form_lines.push(Line::from(vec![
+                    Span::styled(format!("<{}>: ", placeholder), style),
+                    Span::styled(data.placeholder_values[index].as_str(), style),
+                    Span::styled(" *", Style::default().fg(Color::Red)),
+                ]));
+                form_lines.push(Line::from(""));
+            }
+
+            if data.credentials.is_empty() && data.placeholders.is_empty() {
+                form_lines.push(Line::from(Span::styled(
+                    "This provider does not need any credentials.",
+                    Style::default().fg(Color::DarkGray),
+                )));
+                form_lines.push(Line::from(""));
+            }
         }
-    } else {
-        Style::default().fg(Color::White)
-    };
-
-    form_lines.push(Line::from(vec![
-        Span::styled("UI Port: ", field2_style),
-        Span::styled(&data.host_port, field2_style),
-    ]));
-    form_lines.push(Line::from(""));
-
-    let field3_style = if data.current_field == 3 {
-        if data.editing {
-            Style::default()
-                .fg(Color::Green)
-                .add_modifier(Modifier::BOLD)
-        } else {
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD)
+        FormSection::Options => {
+            let offset = data.section_fields(FormSection::Options).start;
+            let settings = [
+                ("Generation Model: ", &data.generation_model),
+                ("UI Port: ", &data.host_port),
+                ("AI Service Port: ", &data.ai_service_port),
+            ];
+
+            for (index, (label, value)) in settings.into_iter().enumerate() {
+                let style = field_style(data, offset + index);
+                form_lines.push(Line::from(vec![
+                    Span::styled(label, style),
+                    Span::styled(value.as_str(), style),
+                ]));
+                form_lines.push(Line::from(""));
+            }
+
+            for (key, value) in &data.port_overrides {
+                form_lines.push(Line::from(Span::styled(
+                    format!("{}={} (free port picked during the port check)", key, value),
+                    Style::default().fg(Color::DarkGray),
+                )));
+            }
         }
-    } else {
-        Style::default().fg(Color::White)
-    };
-
-    form_lines.push(Line::from(vec![
-        Span::styled("AI Service Port: ", field3_style),
-        Span::styled(&data.ai_service_port, field3_style),
-    ]));
-    form_lines.push(Line::from(""));
+    }
 
     if !data.error_message.is_empty() {
         form_lines.push(Line::from(""));
@@ -158,7 +162,7 @@ pub fn render_env_setup(frame: &mut Frame, view: &EnvSetupView<'_>) {
     let help_text = if data.editing {
         "Type to edit, Enter to finish, Esc to cancel"
     } else {
-        "↑↓ to navigate, Enter to edit, Ctrl+S to save, Esc to cancel"
+        "↑↓ to navigate, Enter to edit, Ctrl+S to continue, Esc to go back"
     };
 
     let help = Paragraph::new(help_text)
@@ -166,3 +170,19 @@ pub fn render_env_setup(frame: &mut Frame, view: &EnvSetupView<'_>) {
         .centered();
     frame.render_widget(help, chunks[2]);
 }
+
+fn field_style(data: &FormData, index: usize) -> Style {
+    if data.current_field == index {
+        if data.editing {
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD)
+        }
+    } else {
+        Style::default().fg(Color::White)
+    }
+}
@@ -41,7 +41,7 @@ pub fn render_installing(frame: &mut Frame, view: &InstallingView<'_>) {
 
     let progress_width = (chunks[1].width as f64 - 10.0).max(0.0) * (view.progress / 100.0);
     let filled = "█".repeat(progress_width as usize);
-    let empty = "░".repeat((chunks[1].width as usize - 10 - progress_width as usize).max(0));
+    let empty = "░".repeat((chunks[1].width as usize).saturating_sub(10 + progress_width as usize));
 
     let progress_text = format!("[{}{}] {:.0}%", filled, empty, view.progress);
     let progress_widget = Paragraph::new(progress_text)
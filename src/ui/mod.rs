@@ -1,13 +1,27 @@
+mod checking;
 mod config_selection;
 mod confirmation;
+mod diff;
+mod doctor;
 mod env_setup;
 mod error;
 mod installing;
+mod pipes;
+mod ports;
+mod review;
+mod settings;
 mod success;
 
+pub use checking::render_checking;
 pub use config_selection::{ConfigSelectionView, render_config_selection};
 pub use confirmation::{ConfirmationView, render_confirmation};
+pub use diff::{DiffView, diff_line_count, render_diff};
+pub use doctor::{DoctorView, render_doctor};
 pub use env_setup::{EnvSetupView, render_env_setup};
 pub use error::{ErrorView, render_error};
 pub use installing::{InstallingView, render_installing};
+pub use pipes::{PipesView, pipe_summary, render_pipes};
+pub use ports::{PortsView, render_ports};
+pub use review::{ReviewView, render_review};
+pub use settings::{SettingsView, render_settings, settings_summary};
 pub use success::{SuccessView, render_success};
@@ -0,0 +1,154 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+
+use crate::app::{PipeEditor, PipeField};
+use crate::templates::ConfigTemplate;
+
+pub struct PipesView<'a> {
+    pub template: &'a ConfigTemplate,
+    pub editor: &'a PipeEditor,
+}
+
+pub fn render_pipes(frame: &mut Frame, view: &PipesView<'_>) {
+    let area = frame.area();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(10),
+            Constraint::Length(6),
+        ])
+        .split(area);
+
+    let title = Paragraph::new("🔀 Pipe bindings")
+        .style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+        .block(Block::default().borders(Borders::ALL))
+        .centered();
+    frame.render_widget(title, chunks[0]);
+
+    let editor = view.editor;
+    let header_style = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    let detail_style = Style::default().fg(Color::Gray);
+    let highlight_style = Style::default()
+        .fg(Color::Black)
+        .bg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+
+    let mut lines = vec![Line::from(Span::styled(
+        format!(
+            "  {:<44}{:<26}{:<16}{:<17}{}",
+            "pipe", "llm", "embedder", "engine", "store"
+        ),
+        header_style,
+    ))];
+
+    for (index, pipe) in editor.pipes.iter().enumerate() {
+        let is_selected = index == editor.selected;
+        let marker = if editor.is_customized(index) {
+            Span::styled("* ", Style::default().fg(Color::Yellow))
+        } else {
+            Span::raw("  ")
+        };
+        let row_style = if is_selected {
+            Style::default().fg(Color::Cyan)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        let cell_style = |field: PipeField| {
+            if is_selected && editor.field == field {
+                highlight_style
+            } else {
+                row_style
+            }
+        };
+
+        lines.push(Line::from(vec![
+            marker,
+            Span::styled(format!("{:<44}", pipe.name), row_style),
+            Span::styled(
+                format!("{:<24}", binding_label(pipe.llm.as_deref())),
+                cell_style(PipeField::Llm),
+            ),
+            Span::raw("  "),
+            Span::styled(
+                format!("{:<14}", binding_label(pipe.embedder.as_deref())),
+                cell_style(PipeField::Embedder),
+            ),
+            Span::raw("  "),
+            Span::styled(
+                format!("{:<15}", pipe.engine.as_deref().unwrap_or("—")),
+                cell_style(PipeField::Engine),
+            ),
+            Span::raw("  "),
+            Span::styled(
+                pipe.document_store.as_deref().unwrap_or("—").to_string(),
+                cell_style(PipeField::DocumentStore),
+            ),
+        ]));
+    }
+
+    let visible_rows = chunks[1].height.saturating_sub(3) as usize;
+    let scroll = (editor.selected + 1).saturating_sub(visible_rows) as u16;
+
+    let table = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title(format!(
+            "{} · {}",
+            view.template.name,
+            pipe_summary(editor)
+        )))
+        .scroll((scroll, 0));
+    frame.render_widget(table, chunks[1]);
+
+    let help_lines = vec![
+        Line::from(vec![
+            Span::styled("LLMs: ", Style::default().fg(Color::Yellow)),
+            Span::styled(editor.llm_bindings.join(", "), detail_style),
+        ]),
+        Line::from(vec![
+            Span::styled("Embedders: ", Style::default().fg(Color::Yellow)),
+            Span::styled(editor.embedder_bindings.join(", "), detail_style),
+        ]),
+        Line::from(vec![
+            Span::styled("Engines: ", Style::default().fg(Color::Yellow)),
+            Span::styled(editor.engines.join(", "), detail_style),
+            Span::styled("  Stores: ", Style::default().fg(Color::Yellow)),
+            Span::styled(editor.document_stores.join(", "), detail_style),
+        ]),
+        Line::from(Span::styled(
+            "↑↓ select pipe · ←→ column · Enter/Space next option · Backspace previous (— unsets) · r reset · Esc back to review",
+            Style::default().fg(Color::DarkGray),
+        )),
+    ];
+    let help = Paragraph::new(help_lines).wrap(Wrap { trim: true });
+    frame.render_widget(help, chunks[2]);
+}
+
+pub fn pipe_summary(editor: &PipeEditor) -> String {
+    match editor.customized_count() {
+        0 => format!("{} pipes, template defaults", editor.pipes.len()),
+        count => format!("{} pipes, {} customized", editor.pipes.len(), count),
+    }
+}
+
+fn binding_label(binding: Option<&str>) -> &str {
+    match binding {
+        Some(binding) => binding
+            .split_once('.')
+            .map(|(_, alias)| alias)
+            .unwrap_or(binding),
+        None => "—",
+    }
+}
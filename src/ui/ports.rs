@@ -0,0 +1,92 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+
+use crate::ports::PortConflict;
+
+pub struct PortsView<'a> {
+    pub conflicts: &'a [PortConflict],
+}
+
+pub fn render_ports(frame: &mut Frame, view: &PortsView<'_>) {
+    let area = frame.area();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(10),
+            Constraint::Length(2),
+        ])
+        .split(area);
+
+    let title = Paragraph::new("🔌 Host port conflicts")
+        .style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+        .block(Block::default().borders(Borders::ALL))
+        .centered();
+    frame.render_widget(title, chunks[0]);
+
+    let detail_style = Style::default().fg(Color::Gray);
+    let mut lines = vec![
+        Line::from(""),
+        Line::from("These host ports cannot be published by the stack:"),
+        Line::from(""),
+    ];
+
+    for conflict in view.conflicts {
+        let published = &conflict.published;
+        let mut spans = vec![
+            Span::raw("  "),
+            Span::styled("✗", Style::default().fg(Color::Red)),
+            Span::styled(
+                format!(" {} ", published.port),
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("{} ({})", published.service, conflict.reason),
+                detail_style,
+            ),
+        ];
+        match (conflict.suggestion, &published.env_var) {
+            (Some(port), Some(env_var)) => spans.push(Span::styled(
+                format!("  → {}={}", env_var, port),
+                Style::default().fg(Color::Green),
+            )),
+            (Some(port), None) => spans.push(Span::styled(
+                format!(
+                    "  → {} is free, but the port is fixed in docker-compose.yaml",
+                    port
+                ),
+                Style::default().fg(Color::Yellow),
+            )),
+            (None, _) => spans.push(Span::styled(
+                "  → no free port found nearby",
+                Style::default().fg(Color::Yellow),
+            )),
+        }
+        lines.push(Line::from(spans));
+    }
+
+    let content = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title("Preflight"))
+        .wrap(Wrap { trim: false });
+    frame.render_widget(content, chunks[1]);
+
+    let help = Paragraph::new(
+        "Enter to use the suggested ports, i to continue anyway, Esc to edit the ports",
+    )
+    .style(Style::default().fg(Color::DarkGray))
+    .centered();
+    frame.render_widget(help, chunks[2]);
+}
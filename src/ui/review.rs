@@ -0,0 +1,220 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+
+use crate::app::{FormData, PipeEditor, SettingsEditor};
+use crate::compose::ComposeService;
+use crate::templates::{ConfigTemplate, CredentialKind, ModelSummary};
+use crate::ui::{pipe_summary, settings_summary};
+
+pub struct ReviewView<'a> {
+    pub template: &'a ConfigTemplate,
+    pub models: &'a [ModelSummary],
+    pub form_data: &'a FormData,
+    pub pipes: &'a PipeEditor,
+    pub settings: &'a SettingsEditor,
+    pub keep_config: bool,
+    pub services: &'a [ComposeService],
+    pub warnings: &'a [String],
+    pub platform_notes: &'a [String],
+}
+
+pub fn render_review(frame: &mut Frame, view: &ReviewView<'_>) {
+    let area = frame.area();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(12),
+            Constraint::Length(2),
+        ])
+        .split(area);
+
+    let title = Paragraph::new("📋 Step 4/4 · Review and install")
+        .style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+        .block(Block::default().borders(Borders::ALL))
+        .centered();
+    frame.render_widget(title, chunks[0]);
+
+    let heading_style = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    let detail_style = Style::default().fg(Color::Gray);
+    let data = view.form_data;
+
+    let mut lines = vec![
+        Line::from(Span::styled("Template", heading_style)),
+        Line::from(vec![
+            Span::raw("  "),
+            Span::styled(
+                view.template.name.as_str(),
+                Style::default().fg(Color::White),
+            ),
+            Span::styled(format!("  ({})", view.template.key), detail_style),
+        ]),
+        Line::from(""),
+    ];
+
+    if view.keep_config {
+        lines.push(Line::from(Span::styled("config.yaml", heading_style)));
+        lines.push(Line::from(Span::styled(
+            "  kept as is, only .env is written",
+            detail_style,
+        )));
+    } else {
+        lines.push(Line::from(Span::styled("Models", heading_style)));
+        for model in view.models {
+            lines.push(Line::from(vec![
+                Span::raw("  "),
+                Span::styled(format!("{:<9}", model.kind), detail_style),
+                Span::styled(model.alias.clone(), Style::default().fg(Color::White)),
+                Span::styled(format!("  → {}", model.model), detail_style),
+            ]));
+        }
+
+        lines.push(Line::from(vec![
+            Span::styled("  Pipes: ", detail_style),
+            Span::raw(pipe_summary(view.pipes)),
+            Span::styled("  (press p to edit)", detail_style),
+        ]));
+        lines.push(Line::from(vec![
+            Span::styled("  Settings: ", detail_style),
+            Span::raw(settings_summary(view.settings)),
+            Span::styled("  (press s to edit)", detail_style),
+        ]));
+        for warning in view.warnings {
+            lines.push(Line::from(Span::styled(
+                format!("  ⚠ {}", warning),
+                Style::default().fg(Color::Yellow),
+            )));
+        }
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled("Credentials", heading_style)));
+    if data.credentials.is_empty() {
+        lines.push(Line::from(Span::styled("  none required", detail_style)));
+    }
+    for (credential, value) in data.credentials.iter().zip(&data.credential_values) {
+        let shown = match credential.kind {
+            CredentialKind::Secret { .. } => mask(value.trim()),
+            _ => value.trim().to_string(),
+        };
+        lines.push(Line::from(vec![
+            Span::raw("  "),
+            Span::styled(
+                credential.env_var.as_str(),
+                Style::default().fg(Color::White),
+            ),
+            Span::styled(format!("  {}", shown), detail_style),
+        ]));
+    }
+
+    if !data.placeholders.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled("Template values", heading_style)));
+        for (placeholder, value) in data.placeholders.iter().zip(&data.placeholder_values) {
+            lines.push(Line::from(vec![
+                Span::raw("  "),
+                Span::styled(
+                    format!("<{}>", placeholder),
+                    Style::default().fg(Color::White),
+                ),
+                Span::styled(format!("  {}", value.trim()), detail_style),
+            ]));
+        }
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled("Options", heading_style)));
+    lines.push(Line::from(vec![
+        Span::styled("  Generation model: ", detail_style),
+        Span::raw(data.generation_model.as_str()),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("  UI port: ", detail_style),
+        Span::raw(data.host_port.as_str()),
+        Span::styled("  AI service port: ", detail_style),
+        Span::raw(data.ai_service_port.as_str()),
+    ]));
+    for (key, value) in &data.port_overrides {
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {}: ", key), detail_style),
+            Span::raw(value.as_str()),
+        ]));
+    }
+    lines.push(Line::from(vec![
+        Span::styled("  Platform: ", detail_style),
+        Span::raw(data.platform.as_str()),
+    ]));
+    for note in view.platform_notes {
+        lines.push(Line::from(Span::styled(
+            format!("  ⚠ {}", note),
+            Style::default().fg(Color::Yellow),
+        )));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled("Services", heading_style)));
+    if view.services.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  docker-compose.yaml not found",
+            Style::default().fg(Color::Red),
+        )));
+    }
+    for service in view.services {
+        let image = service.image.as_deref().unwrap_or("built locally");
+        let mut spans = vec![
+            Span::raw("  • "),
+            Span::styled(service.name.clone(), Style::default().fg(Color::White)),
+            Span::styled(format!("  {}", image), detail_style),
+        ];
+        if !service.ports.is_empty() {
+            spans.push(Span::styled(
+                format!("  ports {}", service.ports.join(", ")),
+                detail_style,
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        if view.keep_config {
+            "Enter writes .env, then starts the installation."
+        } else {
+            "Enter writes .env and config.yaml, then starts the installation."
+        },
+        Style::default().fg(Color::Green),
+    )));
+
+    let summary = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title("Summary"))
+        .wrap(Wrap { trim: false });
+    frame.render_widget(summary, chunks[1]);
+
+    let help =
+        Paragraph::new("Enter to install, p to edit pipe bindings, s to edit settings, Esc to go back, Ctrl+C to exit")
+            .style(Style::default().fg(Color::DarkGray))
+            .centered();
+    frame.render_widget(help, chunks[2]);
+}
+
+fn mask(value: &str) -> String {
+    let visible: String = value.chars().take(4).collect();
+    if value.chars().count() <= 4 {
+        "****".to_string()
+    } else {
+        format!("{}****", visible)
+    }
+}
@@ -0,0 +1,156 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+
+use crate::app::SettingsEditor;
+use crate::settings::{SETTING_FIELDS, SettingKind, preset_names};
+use crate::templates::ConfigTemplate;
+
+pub struct SettingsView<'a> {
+    pub template: &'a ConfigTemplate,
+    pub editor: &'a SettingsEditor,
+}
+
+pub fn render_settings(frame: &mut Frame, view: &SettingsView<'_>) {
+    let area = frame.area();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(10),
+            Constraint::Length(4),
+        ])
+        .split(area);
+
+    let title = Paragraph::new("⚙️  Service settings")
+        .style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+        .block(Block::default().borders(Borders::ALL))
+        .centered();
+    frame.render_widget(title, chunks[0]);
+
+    let editor = view.editor;
+    let changed = editor.changed_keys();
+    let detail_style = Style::default().fg(Color::Gray);
+    let highlight_style = Style::default()
+        .fg(Color::Black)
+        .bg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let row_style = |index: usize| {
+        if index == editor.selected {
+            Style::default().fg(Color::Cyan)
+        } else {
+            Style::default().fg(Color::White)
+        }
+    };
+    let value_style = |index: usize| {
+        if index == editor.selected {
+            highlight_style
+        } else {
+            Style::default().fg(Color::White)
+        }
+    };
+
+    let preset = editor
+        .settings
+        .preset()
+        .map(|preset| preset.name())
+        .unwrap_or("custom");
+    let mut lines = vec![
+        Line::from(vec![
+            Span::raw("  "),
+            Span::styled(format!("{:<54}", "preset"), row_style(0)),
+            Span::styled(format!(" {} ", preset), value_style(0)),
+            Span::styled(format!("  {}", preset_names()), detail_style),
+        ]),
+        Line::from(""),
+    ];
+    for (_, warning) in &editor.warnings {
+        lines.push(Line::from(Span::styled(
+            format!("  ⚠ {}", warning),
+            Style::default().fg(Color::Yellow),
+        )));
+    }
+    if !editor.warnings.is_empty() {
+        lines.push(Line::from(""));
+    }
+    let header_rows = lines.len() - 1;
+
+    for (position, field) in SETTING_FIELDS.iter().enumerate() {
+        let index = position + 1;
+        let marker = if changed.contains(&field.key) {
+            Span::styled("* ", Style::default().fg(Color::Yellow))
+        } else {
+            Span::raw("  ")
+        };
+        let value = match (&editor.input, index == editor.selected) {
+            (Some(input), true) => format!(" {}█ ", input),
+            _ => format!(" {} ", editor.settings.display(field.key)),
+        };
+        let hint = match field.kind {
+            SettingKind::Toggle => "true/false".to_string(),
+            SettingKind::Integer => "whole number".to_string(),
+            SettingKind::Decimal { min, max } => format!("{:.1}–{:.1}", min, max),
+            SettingKind::Choice(options) => options.join("/"),
+            SettingKind::Url => "URL".to_string(),
+        };
+
+        lines.push(Line::from(vec![
+            marker,
+            Span::styled(format!("{:<54}", field.key), row_style(index)),
+            Span::styled(value, value_style(index)),
+            Span::styled(format!("  {}", hint), detail_style),
+        ]));
+    }
+
+    let visible_rows = chunks[1].height.saturating_sub(2) as usize;
+    let scroll = (editor.selected + header_rows + 1).saturating_sub(visible_rows) as u16;
+
+    let table = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title(format!(
+            "{} · {}",
+            view.template.name,
+            settings_summary(editor)
+        )))
+        .scroll((scroll, 0));
+    frame.render_widget(table, chunks[1]);
+
+    let mut help_lines = Vec::new();
+    if let Some(error) = &editor.error {
+        help_lines.push(Line::from(Span::styled(
+            format!("✗ {}", error),
+            Style::default().fg(Color::Red),
+        )));
+    }
+    let help = if editor.input.is_some() {
+        "Type a value · Enter to save · Esc to cancel"
+    } else {
+        "↑↓ select · ←→ change preset/toggle/level · Enter edit · r reset to template · Esc back to review"
+    };
+    help_lines.push(Line::from(Span::styled(
+        help,
+        Style::default().fg(Color::DarkGray),
+    )));
+    let help = Paragraph::new(help_lines).wrap(Wrap { trim: true });
+    frame.render_widget(help, chunks[2]);
+}
+
+pub fn settings_summary(editor: &SettingsEditor) -> String {
+    let preset = match editor.settings.preset() {
+        Some(preset) => format!("{} preset", preset.name()),
+        None => "custom".to_string(),
+    };
+    match editor.changed_keys().len() {
+        0 => format!("{}, template defaults", preset),
+        count => format!("{}, {} changed", preset, count),
+    }
+}
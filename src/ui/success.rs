@@ -8,6 +8,7 @@ use ratatui::{
 
 pub struct SuccessView<'a> {
     pub logs: &'a [String],
+    pub ui_port: &'a str,
 }
 
 pub fn render_success(frame: &mut Frame, view: &SuccessView<'_>) {
@@ -45,7 +46,7 @@ pub fn render_success(frame: &mut Frame, view: &SuccessView<'_>) {
         Line::from(""),
         Line::from("All services are now running. You can access Analytics UI at:"),
         Line::from(Span::styled(
-            "http://localhost:3000",
+            format!("http://localhost:{}", view.ui_port),
             Style::default()
                 .fg(Color::Cyan)
                 .add_modifier(Modifier::UNDERLINED),
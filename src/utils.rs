@@ -1,4 +1,8 @@
+use std::io::Read;
 use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
 
 pub const ENV_TEMPLATE: &str = include_str!("../env_template");
 
@@ -18,11 +22,76 @@ pub fn project_root() -> PathBuf {
         .to_str()
         .map(|s| s.contains("target"))
         .unwrap_or(false)
+        && let Some(parent) = current.parent().and_then(|p| p.parent())
     {
-        if let Some(parent) = current.parent().and_then(|p| p.parent()) {
-            current = parent.to_path_buf();
-        }
+        current = parent.to_path_buf();
     }
 
     current
 }
+
+pub struct CommandOutput {
+    pub success: bool,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+pub fn run_command(program: &str, args: &[&str], timeout: Duration) -> Option<CommandOutput> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .ok()?;
+
+    let stdout = drain(child.stdout.take());
+    let stderr = drain(child.stderr.take());
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() < deadline => std::thread::sleep(Duration::from_millis(50)),
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+        }
+    };
+
+    Some(CommandOutput {
+        success: status.success(),
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    })
+}
+
+fn drain(pipe: Option<impl Read + Send + 'static>) -> JoinHandle<String> {
+    std::thread::spawn(move || {
+        let mut output = String::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_string(&mut output);
+        }
+        output
+    })
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_command_reads_output_larger_than_the_pipe_buffer() {
+        let output = run_command(
+            "sh",
+            &["-c", "head -c 200000 /dev/zero | tr '\\0' x; echo done >&2"],
+            Duration::from_secs(10),
+        )
+        .unwrap();
+
+        assert!(output.success);
+        assert_eq!(output.stdout.len(), 200_000);
+        assert_eq!(output.stderr, "done\n");
+    }
+}
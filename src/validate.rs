@@ -0,0 +1,374 @@
+use color_eyre::Result;
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::embeddings;
+use crate::settings;
+use crate::templates::Pipe;
+use crate::utils;
+
+const SECRET_KEY_SUFFIXES: &[&str] = &[
+    "api_key",
+    "_secret",
+    "secret_access_key",
+    "access_key_id",
+    "password",
+    "token",
+];
+const SECRET_VALUE_PREFIXES: &[&str] = &["sk-", "AIza", "gsk_", "xai-", "AKIA"];
+const ENV_REFERENCE_PREFIX: &str = "os.environ/";
+
+pub fn config_path() -> PathBuf {
+    utils::project_root().join("config.yaml")
+}
+
+pub fn validate_file(path: &Path) -> Result<Vec<String>> {
+    let content = fs::read_to_string(path)?;
+    Ok(validate_config(&content))
+}
+
+pub fn validate_config(content: &str) -> Vec<String> {
+    let documents = match serde_yaml::Deserializer::from_str(content)
+        .map(serde_yaml::Value::deserialize)
+        .collect::<std::result::Result<Vec<_>, _>>()
+    {
+        Ok(documents) => documents,
+        Err(e) => return vec![format!("config.yaml is not valid YAML: {}", e)],
+    };
+
+    let mut issues = Vec::new();
+    let mut llms = Vec::new();
+    let mut embedders = Vec::new();
+    let mut engines = Vec::new();
+    let mut document_stores = Vec::new();
+    let mut embedder_models = Vec::new();
+    let mut store_dimensions = Vec::new();
+    let mut pipes = Vec::new();
+    let mut has_pipeline = false;
+
+    for (index, document) in documents.iter().enumerate() {
+        let kind = document
+            .get("type")
+            .and_then(|kind| kind.as_str())
+            .unwrap_or_default();
+        let provider = document
+            .get("provider")
+            .and_then(|provider| provider.as_str())
+            .unwrap_or_default();
+        let label = if kind.is_empty() {
+            format!("document {}", index + 1)
+        } else {
+            format!("document {} ({})", index + 1, kind)
+        };
+
+        match kind {
+            "llm" | "embedder" => {
+                let bindings = if kind == "llm" {
+                    &mut llms
+                } else {
+                    &mut embedders
+                };
+                let models = document
+                    .get("models")
+                    .and_then(|models| models.as_sequence())
+                    .into_iter()
+                    .flatten();
+                for model in models {
+                    let Some(name) = model
+                        .get("alias")
+                        .or_else(|| model.get("model"))
+                        .and_then(|name| name.as_str())
+                    else {
+                        issues.push(format!("{} has a model without model or alias", label));
+                        continue;
+                    };
+                    let binding = format!("{}.{}", provider, name);
+                    if kind == "embedder"
+                        && let Some(model) = model.get("model").and_then(|model| model.as_str())
+                    {
+                        embedder_models.push((binding.clone(), model.to_string()));
+                    }
+                    if bindings.contains(&binding) {
+                        issues.push(format!("{} defines {} more than once", label, binding));
+                    } else {
+                        bindings.push(binding);
+                    }
+                }
+            }
+            "engine" => engines.push(provider.to_string()),
+            "document_store" => {
+                document_stores.push(provider.to_string());
+                if let Some(dimension) = document
+                    .get("embedding_model_dim")
+                    .and_then(|dimension| dimension.as_u64())
+                {
+                    store_dimensions.push((provider.to_string(), dimension));
+                }
+            }
+            "pipeline" => {
+                has_pipeline = true;
+                let entries = document
+                    .get("pipes")
+                    .and_then(|pipes| pipes.as_sequence())
+                    .into_iter()
+                    .flatten();
+                for entry in entries {
+                    match serde_yaml::from_value::<Pipe>(entry.clone()) {
+                        Ok(pipe) => pipes.push(pipe),
+                        Err(e) => issues.push(format!("{} has an invalid pipe: {}", label, e)),
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        if let Some(settings) = document
+            .get("settings")
+            .and_then(|settings| settings.as_mapping())
+        {
+            for problem in settings::check_values(settings) {
+                issues.push(format!("{}: {}", label, problem));
+            }
+        }
+
+        find_inline_secrets(document, &label, &mut issues);
+    }
+
+    if !has_pipeline {
+        issues.push("config.yaml has no pipeline document".to_string());
+    }
+
+    for pipe in &pipes {
+        let references = [
+            ("llm", pipe.llm.as_ref(), &llms),
+            ("embedder", pipe.embedder.as_ref(), &embedders),
+            ("engine", pipe.engine.as_ref(), &engines),
+            (
+                "document_store",
+                pipe.document_store.as_ref(),
+                &document_stores,
+            ),
+        ];
+        for (field, reference, defined) in references {
+            if let Some(reference) = reference
+                && !defined.contains(reference)
+            {
+                issues.push(format!(
+                    "pipe {} uses {} {}, which is not defined in config.yaml",
+                    pipe.name, field, reference
+                ));
+            }
+        }
+    }
+
+    let mut mismatches = Vec::new();
+    for pipe in &pipes {
+        let (Some(embedder), Some(store)) = (pipe.embedder.as_ref(), pipe.document_store.as_ref())
+        else {
+            continue;
+        };
+        let Some((_, model)) = embedder_models
+            .iter()
+            .find(|(binding, _)| binding == embedder)
+        else {
+            continue;
+        };
+        let Some((_, configured)) = store_dimensions
+            .iter()
+            .find(|(provider, _)| provider == store)
+        else {
+            continue;
+        };
+        if let Some(expected) = embeddings::dimension(model)
+            && expected != *configured
+            && !mismatches.contains(&(model, store))
+        {
+            mismatches.push((model, store));
+            issues.push(format!(
+                "document_store {} has embedding_model_dim {} but {} produces {} dimensions",
+                store, configured, model, expected
+            ));
+        }
+    }
+
+    issues
+}
+
+fn find_inline_secrets(value: &serde_yaml::Value, path: &str, issues: &mut Vec<String>) {
+    match value {
+        serde_yaml::Value::Mapping(mapping) => {
+            for (key, item) in mapping {
+                let Some(key) = key.as_str() else {
+                    continue;
+                };
+                let item_path = format!("{}.{}", path, key);
+                if let Some(secret) = item.as_str()
+                    && is_secret_key(key)
+                    && !secret.is_empty()
+                    && !secret.starts_with(ENV_REFERENCE_PREFIX)
+                {
+                    issues.push(format!(
+                        "{} is set inline; read it from the environment with {}NAME instead",
+                        item_path, ENV_REFERENCE_PREFIX
+                    ));
+                    continue;
+                }
+                find_inline_secrets(item, &item_path, issues);
+            }
+        }
+        serde_yaml::Value::Sequence(items) => {
+            for (index, item) in items.iter().enumerate() {
+                find_inline_secrets(item, &format!("{}[{}]", path, index), issues);
+            }
+        }
+        serde_yaml::Value::String(content) => {
+            let looks_like_key = content.len() >= 20
+                && !content.contains(char::is_whitespace)
+                && SECRET_VALUE_PREFIXES
+                    .iter()
+                    .any(|prefix| content.starts_with(prefix));
+            if looks_like_key {
+                issues.push(format!("{} looks like an API key pasted inline", path));
+            }
+        }
+        serde_yaml::Value::Tagged(tagged) => find_inline_secrets(&tagged.value, path, issues),
+        _ => {}
+    }
+}
+
+fn is_secret_key(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    SECRET_KEY_SUFFIXES
+        .iter()
+        .any(|suffix| key.ends_with(suffix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(dimension: &str, pipes: &str) -> String {
+        format!(
+            "\
+type: llm
+provider: litellm_llm
+models:
+  - alias: default
+    model: gpt-4o-mini
+    api_key_name: OPENAI_API_KEY
+---
+type: embedder
+provider: litellm_embedder
+models:
+  - alias: default
+    model: text-embedding-3-large
+---
+type: engine
+provider: analytics_ibis
+endpoint: http://ibis-server:8000
+---
+type: document_store
+provider: qdrant
+location: http://qdrant:6333
+embedding_model_dim: {}
+---
+type: pipeline
+pipes:
+{}",
+            dimension, pipes
+        )
+    }
+
+    const GOOD_PIPES: &str = concat!(
+        "  - name: sql_generation\n",
+        "    llm: litellm_llm.default\n",
+        "    engine: analytics_ibis\n",
+        "  - name: db_schema_indexing\n",
+        "    embedder: litellm_embedder.default\n",
+        "    document_store: qdrant\n",
+    );
+
+    #[test]
+    fn validate_config_cases() {
+        let cases: &[(&str, String, &[&str])] = &[
+            ("valid", config("3072", GOOD_PIPES), &[]),
+            (
+                "dangling llm",
+                config(
+                    "3072",
+                    "  - name: sql_answer\n    llm: litellm_llm.missing\n",
+                ),
+                &[
+                    "pipe sql_answer uses llm litellm_llm.missing, which is not defined in config.yaml",
+                ],
+            ),
+            (
+                "dangling embedder",
+                config(
+                    "3072",
+                    "  - name: indexing\n    embedder: litellm_embedder.small\n",
+                ),
+                &[
+                    "pipe indexing uses embedder litellm_embedder.small, which is not defined in config.yaml",
+                ],
+            ),
+            (
+                "dangling engine and store",
+                config(
+                    "3072",
+                    "  - name: retrieval\n    engine: duckdb\n    document_store: pgvector\n",
+                ),
+                &[
+                    "pipe retrieval uses engine duckdb, which is not defined in config.yaml",
+                    "pipe retrieval uses document_store pgvector, which is not defined in config.yaml",
+                ],
+            ),
+            (
+                "wrong dimension",
+                config("1536", GOOD_PIPES),
+                &[
+                    "document_store qdrant has embedding_model_dim 1536 but text-embedding-3-large produces 3072 dimensions",
+                ],
+            ),
+            (
+                "dimension of an unused store",
+                config(
+                    "1536",
+                    "  - name: sql_generation\n    llm: litellm_llm.default\n",
+                ),
+                &[],
+            ),
+            (
+                "no pipeline",
+                "type: llm\nprovider: litellm_llm\nmodels: []\n".to_string(),
+                &["config.yaml has no pipeline document"],
+            ),
+        ];
+
+        for (name, content, expected) in cases {
+            assert_eq!(validate_config(content), *expected, "{}", name);
+        }
+    }
+
+    #[test]
+    fn invalid_yaml_is_one_issue() {
+        let issues = validate_config("type: llm\n  models: [\n");
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].starts_with("config.yaml is not valid YAML"));
+    }
+
+    #[test]
+    fn inline_secrets() {
+        let pipes = format!(
+            "{}---\ntype: llm\nprovider: other_llm\napi_key: sk-abcdefghijklmnopqrstuvwxyz\nmodels: []\n",
+            GOOD_PIPES
+        );
+        assert_eq!(
+            validate_config(&config("3072", &pipes)),
+            [
+                "document 6 (llm).api_key is set inline; read it from the environment with os.environ/NAME instead"
+            ]
+        );
+    }
+}